@@ -0,0 +1,42 @@
+//! Converts the contents of a leptos `view!` macro to `mview!` syntax.
+//!
+//! Reads the contents (without the `view! { ... }` around it) from stdin, and
+//! prints the converted syntax to stdout.
+//!
+//! ```text
+//! cargo run --example view_to_mview < view.txt
+//! ```
+
+use std::{io::Read, process::ExitCode};
+
+use leptos_mview_core::convert::view_to_mview;
+use proc_macro2::TokenStream;
+
+fn main() -> ExitCode {
+    let mut input = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("error: failed to read stdin: {e}");
+        return ExitCode::FAILURE;
+    }
+    let tokens = match input.parse::<TokenStream>() {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match view_to_mview(tokens) {
+        Ok(mview) => {
+            print!("{mview}");
+            ExitCode::SUCCESS
+        }
+        Err(errors) => {
+            for e in errors {
+                let start = e.span().start();
+                eprintln!("{}:{}: error: {e}", start.line, start.column + 1);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,21 +1,19 @@
-//! Custom ASTs.
+//! The syntax tree of the `mview!` macro.
+//!
+//! Use [`parse`] to parse the contents of a macro call into [`Children`]. This
+//! works outside of a proc macro as well, so it can be used by other tools
+//! like formatters. All ASTs implement [`Display`](std::fmt::Display), which
+//! prints them back out as `mview!` syntax.
+//!
+//! Other ASTs also implement [`Parse`](syn::parse::Parse), but these can only
+//! be used inside of a proc macro, as they report some errors through
+//! [`proc_macro_error2`].
 //!
 //! Only 'basic' ASTs (values, idents, etc.) with one obvious way of expanding
 //! them have a [`ToTokens`](quote::ToTokens) implementation. Other ASTs with
 //! context-specific expansions (like expanding differently in components or
-//! HTML elements) or complex ASTs have their expansion implementations in
-//! [`crate::expand`].
-//!
-//! Most ASTs also implement [`Parse`](syn::parse::Parse). The point at which it
-//! errors should not be relied on - i.e. do not run:
-//! ```ignore
-//! if let Ok(x) = X::parse(input) { /* ... */ }
-//! ````
-//! Instead, use [`rollback_err`](crate::recover::rollback_err) to avoid
-//! advancing the input if parsing fails. Note that some parse implementations
-//! use [`proc_macro_error::emit_error`] to try and recover from incomplete
-//! expressions, so use [`input.peek`](syn::parse::ParseBuffer::peek) where
-//! possible if layered errors are not desired.
+//! HTML elements) have their expansion implementations in the private
+//! `expand` module.
 
 pub mod attribute;
 pub use attribute::{Attr, Attrs};
@@ -31,3 +29,29 @@ mod value;
 pub use value::*;
 mod doctype;
 pub use doctype::*;
+mod marked_str;
+pub use marked_str::*;
+mod display;
+
+use proc_macro2::TokenStream;
+
+use crate::error_ext::collect_errors;
+
+/// Parses the contents of an `mview!` call, without the surrounding braces.
+///
+/// Unlike the [`Parse`](syn::parse::Parse) implementations, this can be
+/// called outside of a proc macro.
+///
+/// # Errors
+/// Returns all errors found while parsing, combined into one [`syn::Error`].
+///
+/// # Panics
+/// Never, as failing to parse always reports at least one error.
+pub fn parse(input: TokenStream) -> syn::Result<Children> {
+    collect_errors(|| syn::parse2(input)).map_err(|errors| {
+        let mut errors = errors.into_iter();
+        let mut combined = errors.next().expect("there should be at least one error");
+        errors.for_each(|e| combined.combine(e));
+        combined
+    })
+}
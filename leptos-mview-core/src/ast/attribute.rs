@@ -1,5 +1,6 @@
 pub mod directive;
 pub mod kv;
+pub mod mixin;
 pub mod selector;
 pub mod spread_attrs;
 
@@ -10,7 +11,7 @@ use syn::{
 };
 
 use self::{directive::Directive, kv::KvAttr, spread_attrs::SpreadAttr};
-use crate::{error_ext::ResultExt, parse::rollback_err};
+use crate::{ast::Value, error_ext::ResultExt, parse::rollback_err};
 
 #[derive(Clone)]
 pub enum Attr {
@@ -22,29 +23,40 @@ pub enum Attr {
 impl Parse for Attr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         // ident then colon must be directive
-        // just ident must be regular kv attribute
-        // otherwise, try kv or spread
+        // ident, `-` or `!` must be regular kv attribute
+        // braces are a spread if they start with `..`, otherwise a kv shorthand
         if input.peek(syn::Ident::peek_any) && input.peek2(Token![:]) {
             // cannot be anything else, abort if fails
             let dir = Directive::parse(input).unwrap_or_abort();
             Ok(Self::Directive(dir))
-        } else if input.peek(syn::Ident) {
-            // definitely a k-v attribute
+        } else if input.peek(syn::Ident::peek_any) || input.peek(Token![-]) || input.peek(Token![!])
+        {
             let kv = KvAttr::parse(input)?;
             Ok(Self::Kv(kv))
-        } else if let Some(kv) = rollback_err(input, KvAttr::parse) {
-            // k-v attributes don't necessarily start with ident, try the rest
-            Ok(Self::Kv(kv))
-        } else if let Some(spread) = rollback_err(input, SpreadAttr::parse) {
-            Ok(Self::Spread(spread))
+        } else if input.peek(syn::token::Brace) {
+            if SpreadAttr::peek(input) {
+                Ok(Self::Spread(SpreadAttr::parse(input)?))
+            } else {
+                Ok(Self::Kv(KvAttr::parse(input)?))
+            }
         } else {
             Err(input.error("no attribute found"))
         }
     }
 }
 
+impl Attr {
+    /// Whether the next token could start an attribute.
+    fn peek(input: ParseStream) -> bool {
+        input.peek(syn::Ident::peek_any)
+            || input.peek(Token![-])
+            || input.peek(Token![!])
+            || input.peek(syn::token::Brace)
+    }
+}
+
 /// A space-separated series of attributes.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Attrs(Vec<Attr>);
 
 impl std::ops::Deref for Attrs {
@@ -52,11 +64,38 @@ impl std::ops::Deref for Attrs {
     fn deref(&self) -> &Self::Target { &self.0 }
 }
 
+impl std::ops::DerefMut for Attrs {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl Attrs {
+    pub fn push(&mut self, attr: Attr) { self.0.push(attr); }
+
+    /// Adds all attributes from `other` to the end of this list.
+    pub fn extend(&mut self, other: Self) { self.0.extend(other.0); }
+}
+
 impl Parse for Attrs {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut vec = Vec::new();
-        while let Some(inner) = rollback_err(input, Attr::parse) {
-            vec.push(inner);
+        // only fork if the next token could start an attribute: braces may
+        // also be the children block.
+        while Attr::peek(input) {
+            let Some(inner) = rollback_err(input, Attr::parse) else {
+                break;
+            };
+            // an empty value has already been reported, leave out the attribute
+            // instead of adding more type errors.
+            let is_empty = match &inner {
+                Attr::Kv(kv) => kv.value().is_empty(),
+                Attr::Directive(dir) => dir.value().is_some_and(Value::is_empty),
+                Attr::Spread(_) => false,
+            };
+            if !is_empty {
+                vec.push(inner);
+            }
+            // commas between attributes are allowed, like in component props
+            let _: Option<Token![,]> = input.parse()?;
         }
         Ok(Self(vec))
     }
@@ -102,4 +141,10 @@ mod tests {
         assert!(attrs[4].is_kv());
         assert!(attrs[5].is_spread());
     }
+
+    #[test]
+    fn comma_separated_attrs() {
+        let attrs: Attrs = parse_quote! { type="text", class:x, {..spread}, disabled, };
+        assert_eq!(attrs.len(), 4);
+    }
 }
@@ -6,7 +6,7 @@ use syn::{
 
 use crate::{
     ast::{BracedKebabIdent, KebabIdentOrStr, Value},
-    parse::rollback_err,
+    error_ext::emit_error,
 };
 
 /// A special attribute like `on:click={...}`.
@@ -22,35 +22,69 @@ use crate::{
 /// ```ignore
 /// button class:{primary} style:color="grey";
 /// ```
+/// A kebab-case shorthand keeps the hyphens in the key, and uses the
+/// `snake_case` ident as the value: `class:{is-red}` is the same as
+/// `class:is-red={is_red}`.
 ///
 /// If an extra `:modifier` is added, there will also be a modifier.
 /// ```ignore
 /// button on:click:undelegated={on_click};
 /// ```
 /// `on:{click}:undelegated` also works for the shorthand.
+///
+/// A `?` after the `=` marks the value as optional, which is only supported on
+/// `on:` directives.
+/// ```ignore
+/// button on:click=?{maybe_on_click};
+/// ```
 #[derive(Clone)]
 pub struct Directive {
     pub(crate) dir: syn::Ident,
     pub(crate) key: KebabIdentOrStr,
     pub(crate) modifier: Option<syn::Ident>, // on:event:undelegated
+    pub(crate) optional: Option<Token![?]>,  // on:event=?{handler}
     pub(crate) value: Option<Value>,
 }
 
+impl Directive {
+    /// Returns the name of the directive, like `on` in `on:click`.
+    pub const fn dir(&self) -> &syn::Ident { &self.dir }
+
+    /// Returns the argument of the directive, like `click` in `on:click`.
+    pub const fn key(&self) -> &KebabIdentOrStr { &self.key }
+
+    /// Returns the modifier, like `undelegated` in `on:click:undelegated`.
+    pub const fn modifier(&self) -> Option<&syn::Ident> { self.modifier.as_ref() }
+
+    /// Whether the value is marked as optional with `=?`.
+    pub const fn is_optional(&self) -> bool { self.optional.is_some() }
+
+    /// Returns the value of this directive, if there is one.
+    pub const fn value(&self) -> Option<&Value> { self.value.as_ref() }
+
+    pub const fn value_mut(&mut self) -> Option<&mut Value> { self.value.as_mut() }
+
+    /// Returns the value of this directive, or `true` if there is no value
+    /// (e.g. `class:active;`).
+    pub fn value_or_true(&self) -> Value { self.value.clone().unwrap_or_else(Value::new_true) }
+}
+
 impl Parse for Directive {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let name = syn::Ident::parse_any(input)?;
         <Token![:]>::parse(input)?;
 
-        let try_parse_modifier = |input| {
-            rollback_err(input, <Token![:]>::parse)
-                .is_some()
-                .then(|| syn::Ident::parse_any(input))
+        let try_parse_modifier = |input: ParseStream| {
+            input
+                .parse::<Option<Token![:]>>()?
+                .map(|_| syn::Ident::parse_any(input))
                 .transpose()
         };
 
         let key: KebabIdentOrStr;
         let value: Option<Value>;
         let modifier: Option<syn::Ident>;
+        let mut optional: Option<Token![?]> = None;
 
         if input.peek(syn::token::Brace) {
             // on:{click}:undelegated
@@ -62,14 +96,38 @@ impl Parse for Directive {
             // on:click:undelegated={on_click}
             key = KebabIdentOrStr::parse(input)?;
             modifier = try_parse_modifier(input)?;
-            value = rollback_err(input, <Token![=]>::parse)
-                .map(|eq| Value::parse_or_emit_err(input, eq.span));
+            value = if let Some(eq) = input.parse::<Option<Token![=]>>()? {
+                optional = input.parse()?;
+                let value = Value::parse_or_emit_err(input, eq.span);
+                if name == "clone" {
+                    // the value is still parsed to continue after it
+                    emit_error!(
+                        eq.span,
+                        "`clone:` takes identifiers only; to rename, use a `let` binding in the children"
+                    );
+                    None
+                } else {
+                    Some(value)
+                }
+            } else {
+                None
+            };
         };
 
+        if let Some(question) = optional {
+            if name != "on" {
+                emit_error!(
+                    question.span, "optional values are only supported on `on:` directives";
+                    help = "remove the `?`"
+                );
+            }
+        }
+
         Ok(Self {
             dir: name,
             key,
             modifier,
+            optional,
             value,
         })
     }
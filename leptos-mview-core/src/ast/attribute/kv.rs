@@ -3,14 +3,15 @@ use syn::{parse::Parse, Token};
 
 use crate::{
     ast::{BracedKebabIdent, KebabIdent, Value},
+    error_ext::emit_error,
     parse::rollback_err,
     span,
 };
 
 /// A `key = value` type of attribute.
 ///
-/// This can either be a normal `key = value`, a shorthand `{key}`, or a
-/// boolean attribute `checked`.
+/// This can either be a normal `key = value`, a shorthand `{key}`, a
+/// boolean attribute `checked`, or a negated boolean attribute `!checked`.
 ///
 /// # Examples
 /// ```ignore
@@ -35,12 +36,36 @@ impl KvAttr {
 
     pub const fn value(&self) -> &Value { &self.value }
 
+    pub const fn value_mut(&mut self) -> &mut Value { &mut self.value }
+
     pub fn span(&self) -> Span { span::join(self.key().span(), self.value().span()) }
 }
 
 impl Parse for KvAttr {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let (ident, value) = if input.peek(syn::token::Brace) {
+        let (ident, value) = if let Some(not) = input.parse::<Option<Token![!]>>()? {
+            if let Some(braced_ident) = rollback_err(input, BracedKebabIdent::parse) {
+                emit_error!(
+                    not.span, "`!` can only be used before an attribute name";
+                    help = "move the `!` inside the braces and add a key"
+                );
+                return Ok(Self::new(
+                    braced_ident.ident().clone(),
+                    braced_ident.into_block_value(),
+                ));
+            }
+            // `!flag` is the same as `flag=false`
+            let ident = KebabIdent::parse(input)?;
+            if let Some(eq) = input.parse::<Option<Token![=]>>()? {
+                emit_error!(
+                    eq.span, "negated attributes cannot have a value";
+                    help = "remove the `!` or the value"
+                );
+                _ = Value::parse_or_emit_err(input, eq.span);
+            }
+            let span = span::join(not.span, ident.span());
+            (ident, Value::Lit(syn::LitBool::new(false, span).into()))
+        } else if input.peek(syn::token::Brace) {
             let braced_ident = BracedKebabIdent::parse(input)?;
             (
                 braced_ident.ident().clone(),
@@ -48,7 +73,7 @@ impl Parse for KvAttr {
             )
         } else {
             let ident = KebabIdent::parse(input)?;
-            if let Some(eq) = rollback_err(input, <Token![=]>::parse) {
+            if let Some(eq) = input.parse::<Option<Token![=]>>()? {
                 let value = Value::parse_or_emit_err(input, eq.span);
                 (ident, value)
             } else {
@@ -0,0 +1,51 @@
+use proc_macro2::Span;
+use syn::{
+    parse::{Parse, ParseStream},
+    Token,
+};
+
+use crate::span;
+
+/// An attribute group defined with `define_mview_attrs!`, like
+/// `@@clickable`.
+///
+/// The group is a path to the `macro_rules` generated by
+/// `define_mview_attrs!`. A single `@` is already an id shorthand (`@main`),
+/// so groups use two.
+#[derive(Clone)]
+pub struct Mixin {
+    at: (Token![@], Token![@]),
+    path: syn::Path,
+}
+
+impl Parse for Mixin {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let at = (input.parse()?, input.parse()?);
+        let path = input.call(syn::Path::parse_mod_style)?;
+        Ok(Self { at, path })
+    }
+}
+
+impl Mixin {
+    /// Whether the next tokens are `@@`.
+    pub fn peek(input: ParseStream) -> bool { input.peek(Token![@]) && input.peek2(Token![@]) }
+
+    /// Returns the path to the group's macro.
+    pub const fn path(&self) -> &syn::Path { &self.path }
+
+    /// Returns the span of the `@@`.
+    pub fn at_span(&self) -> Span { span::join(self.at.0.span, self.at.1.span) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Mixin;
+
+    #[test]
+    fn parse_mixin_path() {
+        let mixin: Mixin = syn::parse_quote! { @@ui::clickable };
+        let path = mixin.path();
+        assert_eq!(path.segments.len(), 2);
+        assert_eq!(path.segments[1].ident, "clickable");
+    }
+}
@@ -1,9 +1,17 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream},
     Token,
 };
 
-use crate::{ast::KebabIdent, parse::rollback_err};
+use crate::{
+    ast::{KebabIdent, KebabIdentOrStr},
+    error_ext::emit_error,
+    parse::rollback_err,
+    span,
+};
 
 /// A shorthand for adding class or ids to an element.
 ///
@@ -20,6 +28,19 @@ use crate::{ast::KebabIdent, parse::rollback_err};
 /// ```ignore
 /// div #important .more-classes #another-id .claaass
 /// ```
+///
+/// An `@` can be used instead of the `#`, which doesn't need the space. It is
+/// stored as a `#` with the span of the `@`.
+/// ```ignore
+/// nav@primary
+/// div.card@main
+/// ```
+///
+/// Classes can also be string literals, for class names that are not valid
+/// kebab-idents.
+/// ```ignore
+/// div."-translate-x-1/2"
+/// ```
 #[derive(Clone)]
 pub enum SelectorShorthand {
     Id {
@@ -28,15 +49,25 @@ pub enum SelectorShorthand {
     },
     Class {
         dot_symbol: Token![.],
-        class: KebabIdent,
+        class: KebabIdentOrStr,
     },
 }
 
 impl SelectorShorthand {
-    pub const fn ident(&self) -> &KebabIdent {
+    /// Expands the class or id name to a string literal, along with dummy
+    /// items to color each segment of a kebab-ident. See
+    /// [`KebabIdent::to_str_colored`].
+    pub fn to_str_colored(&self) -> TokenStream {
         match self {
-            Self::Id { id, .. } => id,
-            Self::Class { class, .. } => class,
+            Self::Id { id, .. } => id.to_str_colored(),
+            Self::Class {
+                class: KebabIdentOrStr::KebabIdent(class),
+                ..
+            } => class.to_str_colored(),
+            Self::Class {
+                class: KebabIdentOrStr::Str(class),
+                ..
+            } => quote! { #class },
         }
     }
 
@@ -50,19 +81,39 @@ impl SelectorShorthand {
         punct
     }
 
-    // pub fn span(&self) -> Span { span::join(self.prefix().span(),
-    // self.ident().span()) }
+    /// Returns the span of the whole selector, like `.card` or `#main`.
+    ///
+    /// Only the prefix's span is returned without the `nightly` feature.
+    pub fn span(&self) -> Span {
+        let name = match self {
+            Self::Id { id, .. } => id.span(),
+            Self::Class { class, .. } => class.to_lit_str().span(),
+        };
+        span::join(self.prefix().span(), name)
+    }
 }
 
 impl Parse for SelectorShorthand {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        if let Some(dot) = rollback_err(input, <Token![.]>::parse) {
-            let class = KebabIdent::parse(input)?;
+        if let Some(dot) = input.parse::<Option<Token![.]>>()? {
+            let class = KebabIdentOrStr::parse(input)?;
+            if let KebabIdentOrStr::Str(s) = &class {
+                if s.value().contains(char::is_whitespace) {
+                    emit_error!(s.span(), "class names cannot contain whitespace");
+                }
+            }
             Ok(Self::Class {
                 dot_symbol: dot,
                 class,
             })
-        } else if let Some(pound) = rollback_err(input, <Token![#]>::parse) {
+        } else if input.peek(Token![#]) || input.peek(Token![@]) {
+            let pound = match input.parse::<Option<Token![@]>>()? {
+                Some(at) => Token![#](at.span),
+                None => input.parse::<Token![#]>()?,
+            };
+            if !input.peek(syn::Ident::peek_any) {
+                return Err(input.error("invalid id; ids must be a kebab-case identifier"));
+            }
             let id = KebabIdent::parse(input)?;
             Ok(Self::Id {
                 pound_symbol: pound,
@@ -81,11 +132,61 @@ impl std::ops::Deref for SelectorShorthands {
     type Target = [SelectorShorthand];
     fn deref(&self) -> &Self::Target { &self.0 }
 }
+
+impl std::ops::DerefMut for SelectorShorthands {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
+impl SelectorShorthands {
+    /// Whether the next tokens start a class or id shorthand.
+    ///
+    /// `@@` starts an attribute group instead of an id.
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(Token![.])
+            || (input.peek(Token![@]) && !input.peek2(Token![@]))
+            || (input.peek(Token![#]) && !input.peek2(syn::token::Bracket))
+    }
+
+    /// Adds all selectors from `other` to the end of this list.
+    pub fn extend(&mut self, other: Self) { self.0.extend(other.0); }
+
+    /// Returns the span from the first to the last selector, or `None` if
+    /// there are no selectors.
+    ///
+    /// Only the first selector's prefix is covered without the `nightly`
+    /// feature.
+    pub fn span(&self) -> Option<Span> {
+        let (first, last) = (self.0.first()?, self.0.last()?);
+        Some(span::join(first.span(), last.span()))
+    }
+}
+
 impl Parse for SelectorShorthands {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut vec = Vec::new();
-        while let Some(inner) = rollback_err(input, SelectorShorthand::parse) {
-            vec.push(inner);
+        loop {
+            // `#` or `@` can only be an id here, so report any errors directly
+            if (input.peek(Token![#]) && !input.peek2(syn::token::Bracket))
+                || (input.peek(Token![@]) && !input.peek2(Token![@]))
+            {
+                vec.push(SelectorShorthand::parse(input)?);
+            } else if input.peek(Token![.]) {
+                if !(input.peek2(syn::Ident::peek_any)
+                    || input.peek2(syn::LitStr)
+                    || input.peek2(Token![-]))
+                {
+                    // a stray `.`: skip it and keep going
+                    let dot = input.parse::<Token![.]>()?;
+                    emit_error!(dot.span, "expected a class name after `.`");
+                    continue;
+                }
+                let Some(inner) = rollback_err(input, SelectorShorthand::parse) else {
+                    break;
+                };
+                vec.push(inner);
+            } else {
+                break;
+            }
         }
 
         Ok(Self(vec))
@@ -104,7 +205,7 @@ mod tests {
 
     #[test]
     fn multiple() {
-        let stream = ".class.another-class #id #id2 .wow-class #ida";
+        let stream = r#".class.another-class #id@id2 .wow-class@ida ."-translate-x-1/2""#;
         let selectors: SelectorShorthands = syn::parse_str(stream).unwrap();
         let result = [
             (SelectorKind::Class, "class"),
@@ -113,6 +214,7 @@ mod tests {
             (SelectorKind::Id, "id2"),
             (SelectorKind::Class, "wow-class"),
             (SelectorKind::Id, "ida"),
+            (SelectorKind::Class, "-translate-x-1/2"),
         ]
         .into_iter();
         for (selector, result) in selectors.iter().zip(result) {
@@ -129,9 +231,9 @@ mod tests {
                     assert!(
                         result.0 == SelectorKind::Class,
                         "{} should not be a class",
-                        class.repr()
+                        class.to_unspanned_string()
                     );
-                    assert_eq!(result.1, class.repr());
+                    assert_eq!(result.1, class.to_unspanned_string());
                 }
             }
         }
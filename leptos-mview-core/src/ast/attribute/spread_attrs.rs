@@ -1,4 +1,4 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream};
 use syn::{
     parse::{Parse, ParseStream},
     Token,
@@ -36,12 +36,32 @@ impl Parse for SpreadAttr {
 }
 
 impl SpreadAttr {
+    /// Whether the next token is a brace group starting with `..`.
+    ///
+    /// Only looks at the tokens without parsing anything.
+    pub fn peek(input: ParseStream) -> bool {
+        let Some((inner, _, _)) = input.cursor().group(Delimiter::Brace) else {
+            return false;
+        };
+        let Some((first, rest)) = inner.punct() else {
+            return false;
+        };
+        first.as_char() == '.'
+            && first.spacing() == Spacing::Joint
+            && rest
+                .punct()
+                .is_some_and(|(second, _)| second.as_char() == '.')
+    }
+
     /// Returns the `..` in the spread attr
     pub const fn dotdot(&self) -> &Token![..] { &self.dotdot }
 
     /// Returns the expression after the `..`.
     pub const fn expr(&self) -> &TokenStream { &self.rest }
 
+    /// Returns a mutable reference to the expression after the `..`.
+    pub const fn expr_mut(&mut self) -> &mut TokenStream { &mut self.rest }
+
     /// Returns the span of the wrapping braces.
     pub fn span(&self) -> Span { self.braces.span.join() }
 }
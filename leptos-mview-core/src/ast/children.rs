@@ -1,18 +1,16 @@
-use proc_macro2::Span;
-use proc_macro_error2::emit_error;
-use quote::{quote, ToTokens};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_quote, Token,
 };
 
-use super::{Doctype, Element};
+use super::{Doctype, Element, MarkedStr};
 use crate::{
     ast::Value,
-    error_ext::SynErrorExt,
-    kw,
-    parse::{self, rollback_err},
+    error_ext::{emit_error, emit_recovered_error, SynErrorExt},
+    kw, span,
 };
 
 /// A child that is an actual HTML value (i.e. not a slot).
@@ -22,6 +20,7 @@ pub enum NodeChild {
     Value(Value),
     Element(Element),
     Doctype(Doctype),
+    MarkedStr(MarkedStr),
 }
 
 impl ToTokens for NodeChild {
@@ -30,6 +29,7 @@ impl ToTokens for NodeChild {
             Self::Value(v) => v.into_token_stream(),
             Self::Element(e) => e.into_token_stream(),
             Self::Doctype(d) => d.into_token_stream(),
+            Self::MarkedStr(m) => m.into_token_stream(),
         };
         tokens.extend(quote! {
             #child_tokens
@@ -43,6 +43,7 @@ impl NodeChild {
             Self::Value(v) => v.span(),
             Self::Element(e) => e.tag().span(),
             Self::Doctype(d) => d.span(),
+            Self::MarkedStr(m) => m.span(),
         }
     }
 }
@@ -61,28 +62,58 @@ pub enum Child {
 
 impl Parse for Child {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if let Some(value) = rollback_err(input, Value::parse) {
+        if Value::peek(input) {
+            let value = Value::parse(input)?;
             // only allow literals if they are a string.
             if let Value::Lit(ref lit) = value {
                 if let syn::Lit::Str(_) = lit {
                     Ok(Self::Node(NodeChild::Value(value)))
                 } else {
                     emit_error!(lit.span(), "only string literals are allowed in children");
+                    skip_to_child_start(input);
                     Ok(Self::Node(NodeChild::Value(Value::Lit(parse_quote!("")))))
                 }
             } else {
                 Ok(Self::Node(NodeChild::Value(value)))
             }
+        // `children;` renders the `children` prop of the surrounding component
+        } else if input.peek(kw::children) && input.peek2(Token![;]) {
+            let children = kw::children::parse(input)?;
+            let semi = <Token![;]>::parse(input)?;
+            Ok(Self::Node(NodeChild::Value(children_call(children, semi))))
         // parse slot: make sure its not a qualified path (slot::)
         } else if input.peek(kw::slot) && input.peek2(Token![:]) && !input.peek2(Token![::]) {
             let slot = kw::slot::parse(input).unwrap();
             <Token![:]>::parse(input).unwrap();
             let elem = Element::parse(input)?;
+            if let Some((mixin, _)) = elem.mixin() {
+                emit_error!(mixin.at_span(), "attribute groups can't be used on slots");
+            }
             Ok(Self::Slot(slot, elem))
+        // `await {future} |data| { ... }` is sugar for the `Await` component
+        } else if input.peek(Token![await]) {
+            let elem = Element::parse_await(input)?;
+            Ok(Self::Node(NodeChild::Element(elem)))
         } else if input.peek(syn::Ident::peek_any) {
             let elem = Element::parse(input)?;
             Ok(Self::Node(NodeChild::Element(elem)))
-        } else if let Some(doctype) = rollback_err(input, Doctype::parse) {
+        } else if peek_allow_strict(input) {
+            input.parse::<Token![#]>()?;
+            let allow;
+            syn::bracketed!(allow in input);
+            allow.parse::<TokenStream>()?;
+            let mut elem = Element::parse(input)?;
+            elem.set_allow_strict();
+            Ok(Self::Node(NodeChild::Element(elem)))
+        } else if input.peek(Token![#]) && input.peek2(syn::token::Bracket) {
+            let marked = MarkedStr::parse(input)?;
+            Ok(Self::Node(NodeChild::MarkedStr(marked)))
+        } else if input.peek(Token![.]) || input.peek(Token![#]) || input.peek(Token![@]) {
+            // selector with an implicit `div` tag
+            let elem = Element::parse(input)?;
+            Ok(Self::Node(NodeChild::Element(elem)))
+        } else if input.peek(Token![!]) {
+            let doctype = Doctype::parse(input)?;
             Ok(Self::Node(NodeChild::Doctype(doctype)))
         } else {
             Err(input.error("invalid child: expected literal, block, bracket or element"))
@@ -90,6 +121,36 @@ impl Parse for Child {
     }
 }
 
+/// Whether the next tokens are `#[allow(strict)]`, which turns off the
+/// attribute name lints for the element after it.
+fn peek_allow_strict(input: ParseStream) -> bool {
+    let parse = |input: ParseStream| -> syn::Result<bool> {
+        input.parse::<Token![#]>()?;
+        let attr;
+        syn::bracketed!(attr in input);
+        let allow = attr.parse::<syn::Ident>()?;
+        let lints;
+        syn::parenthesized!(lints in attr);
+        let lint = lints.parse::<syn::Ident>()?;
+        Ok(allow == "allow" && lint == "strict" && lints.is_empty() && attr.is_empty())
+    };
+    parse(&input.fork()).unwrap_or(false)
+}
+
+/// Expands the `children;` shorthand to a block calling `children`.
+///
+/// Calling works for both `Children` and `ChildrenFn`: a `Box<dyn FnOnce>` is
+/// moved, while an `Arc<dyn Fn>` is only borrowed.
+fn children_call(children: kw::children, semi: Token![;]) -> Value {
+    // use the keyword span so that rustc points at it if `children` isn't
+    // in scope
+    let ident = syn::Ident::new("children", children.span);
+    Value::Block {
+        tokens: quote_spanned! { children.span=> #ident() },
+        braces: syn::token::Brace(span::join(children.span, semi.span)),
+    }
+}
+
 /// A space-separated series of children.
 ///
 /// Parsing does not include the surrounding braces.
@@ -101,6 +162,10 @@ impl std::ops::Deref for Children {
     fn deref(&self) -> &Self::Target { &self.0 }
 }
 
+impl std::ops::DerefMut for Children {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.0 }
+}
+
 impl Parse for Children {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut vec = Vec::new();
@@ -109,21 +174,31 @@ impl Parse for Children {
             if input.is_empty() {
                 break;
             }
+            // commas are allowed as separators between children
+            if input.parse::<Option<Token![,]>>()?.is_some() {
+                continue;
+            }
+            let start = input.cursor();
             match Child::parse(input) {
                 Ok(child) => vec.push(child),
                 Err(e) => {
                     if input.peek(Token![;]) {
                         // an extra semi-colon: just skip it and keep parsing
-                        emit_error!(
+                        emit_recovered_error!(
                             e.span(), "extra semi-colon found";
                             help="remove this semi-colon"
                         );
                         <Token![;]>::parse(input).unwrap();
+                    } else if input.cursor() == start {
+                        // not the start of a child: skip it and try again from
+                        // the next child, reporting the stray tokens once
+                        e.emit_as_recovered_error();
+                        input.parse::<TokenTree>().unwrap();
+                        skip_to_child_start(input);
                     } else {
-                        e.emit_as_error();
-                        // skip the rest of the tokens
-                        // need to consume all tokens otherwise an error is made on drop
-                        parse::take_rest(input);
+                        // leave out the invalid child, and keep parsing its siblings
+                        e.emit_as_recovered_error();
+                        skip_to_next_child(input);
                     }
                 }
             };
@@ -133,7 +208,57 @@ impl Parse for Children {
     }
 }
 
+/// Whether the next token could start a child, used to skip over stray tokens
+/// after an error without reporting each of them.
+fn peek_child_start(input: ParseStream) -> bool {
+    input.peek(syn::Ident::peek_any)
+        || input.peek(syn::LitStr)
+        || input.peek(syn::token::Brace)
+        || input.peek(syn::token::Bracket)
+        || input.peek(Token![.])
+        || input.peek(Token![#])
+        || input.peek(Token![@])
+        || input.peek(Token![!])
+        || input.peek(Token![,])
+}
+
+/// Skips tokens up to the next one that could start a child, or up to and
+/// including the next `;`.
+pub fn skip_to_child_start(input: ParseStream) {
+    while !peek_child_start(input) {
+        match input.parse::<TokenTree>() {
+            Ok(TokenTree::Punct(punct)) if punct.as_char() == ';' => return,
+            Ok(_) => (),
+            Err(_) => return,
+        }
+    }
+}
+
+/// Skips the rest of an invalid child, up to and including the next `;` or
+/// children block.
+///
+/// Blocks after an `=` are taken as attribute values, which are skipped over
+/// as well.
+fn skip_to_next_child(input: ParseStream) {
+    let mut after_eq = false;
+    while let Ok(tt) = input.parse::<TokenTree>() {
+        match tt {
+            TokenTree::Punct(punct) if punct.as_char() == ';' => return,
+            TokenTree::Group(group)
+                if !after_eq
+                    && matches!(group.delimiter(), Delimiter::Brace | Delimiter::Parenthesis) =>
+            {
+                return;
+            }
+            TokenTree::Punct(ref punct) => after_eq = punct.as_char() == '=',
+            _ => after_eq = false,
+        }
+    }
+}
+
 impl Children {
+    pub const fn new(children: Vec<Child>) -> Self { Self(children) }
+
     pub fn into_vec(self) -> Vec<Child> { self.0 }
 
     /// Returns an iterator of all children that are not slots.
@@ -0,0 +1,332 @@
+//! [`Display`](fmt::Display) implementations that print the ASTs back out as
+//! `mview!` syntax.
+//!
+//! The output parses back to the same AST, but is not always the same as the
+//! source: shorthands are written out in full, children blocks always use
+//! braces, and everything is separated by a single space. Rust expressions
+//! inside values are printed from the source text where available, so they
+//! keep their original formatting.
+
+use std::fmt::{self, Display, Formatter, Write};
+
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::ext::IdentExt;
+
+use super::{
+    attribute::{
+        directive::Directive, kv::KvAttr, selector::SelectorShorthand, spread_attrs::SpreadAttr,
+    },
+    Attr, Attrs, Child, Children, Doctype, Element, KebabIdent, KebabIdentOrStr, MarkedStr,
+    NodeChild, StrMarker, Tag, Value,
+};
+
+/// Writes the tokens as they appear in the source, or as their
+/// [`to_string`](ToString::to_string) if the source is not available.
+fn write_tokens(f: &mut Formatter, tokens: &TokenStream) -> fmt::Result {
+    let mut spans = tokens.clone().into_iter().map(|tt| tt.span());
+    // joining fails on stable inside of a proc macro, which would only give
+    // the source of the first token
+    let source = spans.next().and_then(|first| {
+        let last = spans.last().unwrap_or(first);
+        first.join(last)?.source_text()
+    });
+    let tokens = unraw_idents(tokens);
+    // idents made by the macro (like the value of a `{value}` shorthand) can be
+    // spanned to source that doesn't start with them
+    let first = |tokens: TokenStream| tokens.into_iter().next().map(|tt| tt.to_string());
+    let source = source.filter(|source| {
+        source
+            .parse::<TokenStream>()
+            .is_ok_and(|parsed| first(parsed) == first(tokens.clone()))
+    });
+    match source {
+        Some(source) => f.write_str(&source),
+        None => write!(f, "{tokens}"),
+    }
+}
+
+/// Removes the `r#` from raw idents that aren't keywords, like the ones made
+/// from kebab-case idents.
+fn unraw_idents(tokens: &TokenStream) -> TokenStream {
+    tokens
+        .clone()
+        .into_iter()
+        .map(|tt| match tt {
+            TokenTree::Ident(ident) => {
+                let unraw = syn::Ident::new(&ident.unraw().to_string(), ident.span());
+                // keywords fail to parse as an ident
+                if syn::parse2::<syn::Ident>(unraw.to_token_stream()).is_ok() {
+                    TokenTree::Ident(unraw)
+                } else {
+                    TokenTree::Ident(ident)
+                }
+            }
+            tt => tt,
+        })
+        .collect()
+}
+
+/// Writes each item separated by a space.
+fn write_spaced<T: Display>(f: &mut Formatter, items: &[T]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_char(' ')?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+impl Display for Children {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write_spaced(f, self) }
+}
+
+impl Display for Child {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Node(node) => write!(f, "{node}"),
+            Self::Slot(_, element) => write!(f, "slot:{element}"),
+        }
+    }
+}
+
+impl Display for NodeChild {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Value(value) => write!(f, "{value}"),
+            Self::Element(element) => write!(f, "{element}"),
+            Self::Doctype(doctype) => write!(f, "{doctype}"),
+            Self::MarkedStr(marked) => write!(f, "{marked}"),
+        }
+    }
+}
+
+impl Display for Element {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.tag())?;
+        for selector in self.selectors().iter() {
+            write!(f, "{selector}")?;
+        }
+        if !self.attrs().is_empty() {
+            write!(f, " {}", self.attrs())?;
+        }
+        if let Some(args) = self.children_args() {
+            f.write_char(' ')?;
+            write_tokens(f, args)?;
+        }
+        match self.children() {
+            Some(children) if children.is_empty() => f.write_str(" {}"),
+            Some(children) => write!(f, " {{ {children} }}"),
+            None => f.write_char(';'),
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Html(ident) | Self::Svg(ident) | Self::Math(ident) => {
+                write!(f, "{}", ident.unraw())
+            }
+            Self::Component(path) => write_tokens(f, &path.to_token_stream()),
+            Self::WebComponent(ident) => write!(f, "{ident}"),
+        }
+    }
+}
+
+impl Display for SelectorShorthand {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            // `card#main` is a reserved prefix in rust 2021, so ids need a space
+            Self::Id { id, .. } => write!(f, " #{id}"),
+            Self::Class { class, .. } => write!(f, ".{class}"),
+        }
+    }
+}
+
+impl Display for Attrs {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write_spaced(f, self) }
+}
+
+impl Display for Attr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Kv(kv) => write!(f, "{kv}"),
+            Self::Directive(dir) => write!(f, "{dir}"),
+            Self::Spread(spread) => write!(f, "{spread}"),
+        }
+    }
+}
+
+impl Display for KvAttr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { write!(f, "{}={}", self.key(), self.value()) }
+}
+
+impl Display for Directive {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.dir(), self.key())?;
+        if let Some(modifier) = self.modifier() {
+            write!(f, ":{modifier}")?;
+        }
+        if let Some(value) = self.value() {
+            let optional = if self.is_optional() { "?" } else { "" };
+            write!(f, "={optional}{value}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for SpreadAttr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("{..")?;
+        write_tokens(f, self.expr())?;
+        f.write_char('}')
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Lit(lit) => write!(f, "{}", lit.to_token_stream()),
+            Self::Block { tokens, .. } => {
+                f.write_char('{')?;
+                write_tokens(f, tokens)?;
+                f.write_char('}')
+            }
+            Self::Bracket {
+                tokens, prefixes, ..
+            } => {
+                if let Some(prefix) = prefixes {
+                    write!(f, "{prefix}")?;
+                }
+                f.write_char('[')?;
+                write_tokens(f, tokens)?;
+                f.write_char(']')
+            }
+        }
+    }
+}
+
+impl Display for KebabIdent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result { f.write_str(self.repr()) }
+}
+
+impl Display for KebabIdentOrStr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::KebabIdent(ident) => write!(f, "{ident}"),
+            Self::Str(s) => write!(f, "{}", s.to_token_stream()),
+        }
+    }
+}
+
+impl Display for Doctype {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_char('!')?;
+        if let Some(doctype) = self.doctype() {
+            write!(f, "{doctype}")?;
+        }
+        if let Some(html) = self.html() {
+            write!(f, " {html}")?;
+        }
+        if self.semi().is_some() {
+            f.write_char(';')?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for StrMarker {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Dedent => f.write_str("#[dedent]"),
+            Self::Entities => f.write_str("#[entities]"),
+        }
+    }
+}
+
+impl Display for MarkedStr {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for marker in self.markers() {
+            write!(f, "{marker} ")?;
+        }
+        write!(f, "{}", self.lit().to_token_stream())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ast::parse;
+
+    #[track_caller]
+    fn print(input: &str) -> String {
+        let children = parse(input.parse().unwrap()).unwrap();
+        let printed = children.to_string();
+        // printing again should give the same result
+        let reparsed = parse(printed.parse().unwrap()).unwrap();
+        assert_eq!(reparsed.to_string(), printed);
+        printed
+    }
+
+    #[test]
+    fn round_trip() {
+        let inputs = [
+            "!DOCTYPE html;",
+            r#"div.card."a/b" #main data-index=1 { "text" strong { "bold" } br; }"#,
+            "p title=[count.get()] data-f=f[\"{}-x\", count.get()] { {count} [count.get() * 2] }",
+            r#"button class:active=[active.get()] class:"w-1/2" on:click:undelegated={f} use:no_arg_dir;"#,
+            "button on:click=?{maybe};",
+            "div {..attrs} { Generic<i32> value=3; Generic::<Str>; }",
+            "For each=[vec![1, 2]] key={|n| *n} |n| { span { {n} } }",
+            "List { slot:Item name=\"a\"; slot:Item name=\"b\" {} }",
+            r#"#[dedent] #[entities] "a &amp; b""#,
+            "svg viewBox=\"0 0 24 24\" { path d=\"M0\"; } my-element;",
+        ];
+        for input in inputs {
+            assert_eq!(print(input), input);
+        }
+    }
+
+    #[test]
+    fn shorthands_are_expanded() {
+        assert_eq!(
+            print("input {value} checked !hidden;"),
+            "input value={value} checked=true hidden=false;"
+        );
+        assert_eq!(
+            print("button class:{primary};"),
+            "button class:primary={primary};"
+        );
+        assert_eq!(
+            print(".card > strong(\"a\")"),
+            "div.card { strong { \"a\" } }"
+        );
+        assert_eq!(print("Show { children; }"), "Show { {children} }");
+        assert_eq!(
+            print("input {aria-label} {self.data-index};"),
+            "input aria-label={aria_label} data-index={self . data_index};"
+        );
+    }
+
+    #[track_caller]
+    fn parse_err(input: &str) -> syn::Error {
+        let Err(e) = parse(input.parse().unwrap()) else {
+            panic!("expected {input:?} to fail");
+        };
+        e
+    }
+
+    #[test]
+    fn errors_outside_macro() {
+        let err = parse_err("div class=;");
+        assert_eq!(err.to_string(), "expected value after =");
+
+        // multiple errors are all returned
+        let err = parse_err("div class=; span id=;");
+        assert_eq!(err.into_iter().count(), 2);
+
+        // aborting errors are returned as well
+        parse_err("div on:=1;");
+    }
+}
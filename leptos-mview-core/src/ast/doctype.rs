@@ -1,12 +1,11 @@
 use proc_macro2::Span;
-use proc_macro_error2::emit_error;
 use quote::{quote, quote_spanned, ToTokens};
 use syn::{
     parse::{Parse, ParseStream},
     Token,
 };
 
-use crate::{parse::rollback_err, span};
+use crate::{error_ext::emit_error, span};
 
 /// The `!DOCTYPE html;` element.
 ///
@@ -31,55 +30,78 @@ impl Doctype {
 
         span::join(self.bang.span, last_tok)
     }
-}
 
-impl Parse for Doctype {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let Some(bang) = rollback_err(input, <Token![!]>::parse) else {
-            return Err(input.error("expected ! to start DOCTYPE"));
-        };
+    /// Returns the `DOCTYPE` ident after the `!`, if there is one.
+    pub const fn doctype(&self) -> Option<&syn::Ident> { self.doctype.as_ref() }
 
-        Ok(Self {
-            bang,
-            doctype: rollback_err(input, syn::Ident::parse),
-            html: rollback_err(input, syn::Ident::parse),
-            semi: rollback_err(input, <Token![;]>::parse),
-        })
+    /// Returns the `html` ident after `!DOCTYPE`, if there is one.
+    pub const fn html(&self) -> Option<&syn::Ident> { self.html.as_ref() }
+
+    /// Returns the closing `;`, if there is one.
+    pub const fn semi(&self) -> Option<&Token![;]> { self.semi.as_ref() }
+
+    fn doctype_span(&self) -> Span {
+        self.doctype
+            .as_ref()
+            .map_or(self.bang.span, syn::Ident::span)
     }
-}
 
-impl ToTokens for Doctype {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        let doctype_span = self
-            .doctype
+    /// Returns the span of `html`, or the closest token before it if it is
+    /// missing.
+    pub fn html_span(&self) -> Span {
+        self.html
             .as_ref()
-            .map(|d| d.span())
-            .unwrap_or(self.bang.span);
-        let html_span = self.html.as_ref().map(|h| h.span()).unwrap_or(doctype_span);
+            .map_or(self.doctype_span(), syn::Ident::span)
+    }
 
+    /// Emits an error if the doctype is not exactly `!DOCTYPE html;`.
+    pub fn emit_error_if_incomplete(&self) {
         if self
             .doctype
             .as_ref()
             .is_none_or(|d| d.to_string() != "DOCTYPE")
         {
             emit_error!(
-                doctype_span,
+                self.doctype_span(),
                 "expected `DOCTYPE` after `!`";
                 help = "Add `!DOCTYPE html;`"
             );
         } else if self.html.as_ref().is_none_or(|h| h.to_string() != "html") {
             emit_error!(
-                html_span,
+                self.html_span(),
                 "expected `html` after `!DOCTYPE`";
                 help = "Add `!DOCTYPE html;`"
             );
         } else if self.semi.is_none() {
             emit_error!(
-                html_span,
+                self.html_span(),
                 "expected `;` after `!DOCTYPE html`";
                 help = "Add `!DOCTYPE html;`"
             )
         }
+    }
+}
+
+impl Parse for Doctype {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let Some(bang) = input.parse::<Option<Token![!]>>()? else {
+            return Err(input.error("expected ! to start DOCTYPE"));
+        };
+
+        Ok(Self {
+            bang,
+            doctype: input.parse()?,
+            html: input.parse()?,
+            semi: input.parse()?,
+        })
+    }
+}
+
+impl ToTokens for Doctype {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.emit_error_if_incomplete();
+
+        let doctype_span = self.doctype_span();
 
         let doctype_fn = quote_spanned!(doctype_span=> doctype);
         let eq = quote_spanned!(self.bang.span=> =);
@@ -1,16 +1,21 @@
-use proc_macro2::{TokenStream, TokenTree};
-use proc_macro_error2::emit_error;
-use quote::{ToTokens, TokenStreamExt};
+use std::cell::Cell;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
+    buffer::Cursor,
     parse::{Parse, ParseStream},
     Token,
 };
 
-use super::{attribute::selector::SelectorShorthands, Attrs, Children, Tag};
+use super::{
+    attribute::{kv::KvAttr, mixin::Mixin, selector::SelectorShorthands},
+    skip_to_child_start, Attr, Attrs, Child, Children, KebabIdent, NodeChild, Tag, TagKind, Value,
+};
 use crate::{
-    expand::{component_to_tokens, xml_to_tokens},
-    parse::{self, rollback_err},
-    span,
+    error_ext::emit_error,
+    expand::{component_to_tokens, mixin_call_tokens, xml_to_tokens},
+    kw, parse, span,
 };
 
 /// A HTML or custom component.
@@ -24,8 +29,10 @@ use crate::{
 /// 4. [`children_args`](TokenStream): Optional arguments for the children,
 ///    placed in closure pipes `|...|` immediately before the children block.
 ///    The closure pipes **are included** in the stored [`TokenStream`].
-/// 5. [`children`](Children): Either no children (ends with `;`) or a children
-///    block `{ ... }` that contains more elements/values.
+/// 5. [`children`](Children): Either no children (ends with `;` or `/`) or a
+///    children block `{ ... }` that contains more elements/values. The span of
+///    the block's delimiters is kept as well. A `>` followed by another element
+///    also counts as a children block with only that element.
 ///
 /// Syntax mostly looks like this:
 /// ```text
@@ -34,12 +41,18 @@ use crate::{
 /// tag attributes     children
 /// ```
 ///
-/// If the element ends in a semicolon, `children` is `None`.
+/// If the element ends in a semicolon (or a `/`), `children` is `None`.
 /// ```text
 /// input type="text";
 /// br;
+/// img src="x" /
 /// ```
 ///
+/// Attribute groups (`@@clickable`) can be mixed in with the attributes. Only
+/// the first group is stored, along with the tokens of the whole element
+/// without it: the element is expanded by passing those tokens to the group's
+/// macro, which resolves any other groups.
+///
 /// Whether the element is a slot or not is distinguished by
 /// [`Child`](crate::ast::Child).
 pub struct Element {
@@ -48,42 +61,199 @@ pub struct Element {
     attrs: Attrs,
     children_args: Option<TokenStream>,
     children: Option<Children>,
+    children_span: Option<Span>,
+    mixin: Option<(Mixin, TokenStream)>,
+    allow_strict: bool,
+}
+
+/// The maximum number of elements that can be nested inside each other, if
+/// [`MAX_NESTING_DEPTH_VAR`] isn't set.
+///
+/// Each level of nesting recurses while parsing and expanding, so this gives an
+/// error instead of overflowing the stack.
+const DEFAULT_MAX_NESTING_DEPTH: usize = 128;
+
+/// The environment variable that overrides [`DEFAULT_MAX_NESTING_DEPTH`].
+const MAX_NESTING_DEPTH_VAR: &str = "MVIEW_MAX_NESTING_DEPTH";
+
+thread_local! {
+    /// The number of elements that are currently being parsed.
+    static NESTING_DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// The maximum nesting depth, read from [`MAX_NESTING_DEPTH_VAR`] on first
+    /// use in each macro call.
+    static MAX_NESTING_DEPTH: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Makes the next element read [`MAX_NESTING_DEPTH_VAR`] again, so that an
+/// invalid value is reported by every macro call.
+pub fn reset_max_nesting_depth() { MAX_NESTING_DEPTH.set(None); }
+
+/// Returns the maximum number of elements that can be nested inside each
+/// other.
+fn max_nesting_depth() -> usize {
+    if let Some(max) = MAX_NESTING_DEPTH.get() {
+        return max;
+    }
+    let max = std::env::var(MAX_NESTING_DEPTH_VAR).map_or(DEFAULT_MAX_NESTING_DEPTH, |value| {
+        value.trim().parse().unwrap_or_else(|_| {
+            emit_error!(
+                Span::call_site(),
+                "`{}` is not a valid nesting depth: `{}`",
+                MAX_NESTING_DEPTH_VAR,
+                value
+            );
+            DEFAULT_MAX_NESTING_DEPTH
+        })
+    });
+    MAX_NESTING_DEPTH.set(Some(max));
+    max
 }
 
+/// Increases the nesting depth until it is dropped.
+struct NestingGuard;
+
+impl NestingGuard {
+    /// Errors at the next element if it would be nested too deep, which is the
+    /// innermost element as parsing stops there.
+    fn enter(input: ParseStream) -> syn::Result<Self> {
+        let depth = NESTING_DEPTH.get() + 1;
+        let max = max_nesting_depth();
+        if depth > max {
+            return Err(input.error(format!("maximum nesting depth {max} exceeded")));
+        }
+        NESTING_DEPTH.set(depth);
+        Ok(Self)
+    }
+}
+
+impl Drop for NestingGuard {
+    fn drop(&mut self) { NESTING_DEPTH.set(NESTING_DEPTH.get() - 1); }
+}
+
+/// The first attribute group of an element, along with the cursors before and
+/// after it.
+type FirstMixin<'a> = Option<(Mixin, Cursor<'a>, Cursor<'a>)>;
+
 impl Parse for Element {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        let start = input.cursor();
+        // the group's macro needs the implicit `div` of `.card @@clickable` written out
+        let implicit_div = (SelectorShorthands::peek(input) || Mixin::peek(input))
+            .then(|| syn::Ident::new("div", input.span()));
+        let mut first_mixin = None;
+        let mut element = Self::parse_parts(input, &mut first_mixin)?;
+        if let Some((mixin, before, after)) = first_mixin {
+            let mut tokens = implicit_div.into_token_stream();
+            tokens.extend(tokens_between(start, before));
+            tokens.extend(tokens_between(after, input.cursor()));
+            element.mixin = Some((mixin, tokens));
+        }
+        Ok(element)
+    }
+}
+
+impl Element {
+    fn parse_parts<'a>(
+        input: ParseStream<'a>,
+        first_mixin: &mut FirstMixin<'a>,
+    ) -> syn::Result<Self> {
+        let _guard = NestingGuard::enter(input)?;
+        let start = input.fork();
         let tag = Tag::parse(input)?;
-        let selectors = SelectorShorthands::parse(input)?;
-        let attrs = Attrs::parse(input)?;
+        let mut selectors = SelectorShorthands::parse(input)?;
+        let mut attrs = Attrs::parse(input)?;
 
-        if rollback_err(input, <Token![;]>::parse).is_some() {
+        loop {
+            if SelectorShorthands::peek(input) {
+                // selectors can also be mixed in with the attributes
+                selectors.extend(SelectorShorthands::parse(input)?);
+            } else if Mixin::peek(input) {
+                // later groups stay in the tokens, to be resolved by the first
+                let before = input.cursor();
+                let mixin = Mixin::parse(input)?;
+                if first_mixin.is_none() {
+                    *first_mixin = Some((mixin, before, input.cursor()));
+                }
+            } else if tag.kind() == TagKind::Component
+                && input.peek(syn::LitStr)
+                && input.peek2(Token![=])
+            {
+                // component props must be identifiers: report any string keys and
+                // keep parsing the rest of the attributes.
+                let key = <syn::LitStr as Parse>::parse(input)?;
+                emit_error!(
+                    key.span(),
+                    "component props cannot be string literals; use a kebab-case or snake_case identifier"
+                );
+                let eq = <Token![=]>::parse(input)?;
+                let value = Value::parse_or_emit_err(input, eq.span);
+                // continue as if the identifier was written, if possible
+                if let Ok(mut ident) = syn::parse_str::<syn::Ident>(&key.value().replace('-', "_"))
+                {
+                    ident.set_span(key.span());
+                    attrs.push(Attr::Kv(KvAttr::new(KebabIdent::from(ident), value)));
+                }
+            } else {
+                break;
+            }
+            attrs.extend(Attrs::parse(input)?);
+        }
+
+        if let Some(gt) = input.parse::<Option<Token![>]>>()? {
+            // chained single child: `div.outer > span { "x" }`.
+            // the child finishes parsing the rest of the chain.
+            let child = Self::parse(input)?;
+            let children = Children::new(vec![Child::Node(NodeChild::Element(child))]);
+            Ok(Self::new(
+                tag,
+                selectors,
+                attrs,
+                None,
+                Some((gt.span, children)),
+            ))
+        } else if let Some(semi) = input.parse::<Option<Token![;]>>()? {
             // no children, terminated by semicolon.
+            emit_error_if_block_after_semi(input, semi.span);
+            Ok(Self::new(tag, selectors, attrs, None, None))
+        } else if let Some(slash) = input.parse::<Option<Token![/]>>()? {
+            // self-closing like in html: `img src="x" /` is the same as `;`.
+            // a block after a blank line is a separate child, like after a `;`.
+            if (input.peek(syn::token::Brace) || input.peek(syn::token::Paren))
+                && span::lines_between(slash.span, input.span()).is_some_and(|lines| lines <= 1)
+            {
+                emit_error!(
+                    slash.span, "`/` ends an element without children, but a children block follows";
+                    help = "remove the `/` to keep the children"
+                );
+                // keep the children to avoid more errors
+                let children = parse_children_block(input)?;
+                skip_extra_children_blocks(input, children.0);
+                return Ok(Self::new(tag, selectors, attrs, None, Some(children)));
+            }
             Ok(Self::new(tag, selectors, attrs, None, None))
         } else if input.is_empty() {
             // allow no ending token if its the last child
             // makes for better editing experience when writing sequentially,
             // as syntax highlighting/autocomplete doesn't work if macro
             // can't fully compile.
+            // point at the end of the element, where the `;` should go
+            let end = last_token_span(&start).unwrap_or_else(|| tag.span());
             emit_error!(
-                tag.span(), "unterminated element";
-                help = "add a `;` to terminate the element with no children"
+                end, "unterminated element";
+                help = "add a `;` after this to terminate the element with no children"
             );
             Ok(Self::new(tag, selectors, attrs, None, None))
         } else if input.peek(syn::token::Brace) || input.peek(syn::token::Paren) {
-            let children = if input.peek(syn::token::Brace) {
-                parse::braced::<Children>(input)?.1
-            } else {
-                parse::parenthesized::<Children>(input)?.1
-            };
-
+            let children = parse_children_block(input)?;
+            skip_extra_children_blocks(input, children.0);
             Ok(Self::new(tag, selectors, attrs, None, Some(children)))
         } else if input.peek(Token![|]) {
             // extra args for the children
             let args = parse_closure_args(input)?;
-            let children = if input.peek(syn::token::Brace) {
-                Some(parse::braced::<Children>(input)?.1)
-            } else if input.peek(syn::token::Paren) {
-                Some(parse::parenthesized::<Children>(input)?.1)
+            let children = if input.peek(syn::token::Brace) || input.peek(syn::token::Paren) {
+                let children = parse_children_block(input)?;
+                skip_extra_children_blocks(input, children.0);
+                Some(children)
             } else {
                 // continue trying to parse as if there are no children
                 emit_error!(
@@ -96,18 +266,97 @@ impl Parse for Element {
         } else {
             // add error at the unknown token
             // continue trying to parse as if there are no children
-            emit_error!(input.span(), "unknown attribute");
             emit_error!(
-                span::join(tag.span(), input.span()), "child elements not found";
-                help = "add a `;` at the end to terminate the element"
+                input.span(), "unknown attribute";
+                help = "add a `;` before this to terminate the element with no children"
             );
+            skip_to_child_start(input);
             Ok(Self::new(tag, selectors, attrs, None, None))
         }
     }
 }
 
+impl Element {
+    /// Parses an `await` child into Leptos' `Await` component.
+    ///
+    /// ```text
+    /// await blocking {fetch_thing()} |data| { p { {data.title.clone()} } }
+    /// ```
+    /// is the same as
+    /// ```text
+    /// Await future={fetch_thing()} blocking=true |data| { p { {data.title.clone()} } }
+    /// ```
+    /// `blocking` is optional. The closure arguments and children are required,
+    /// as the resolved value is only available through them.
+    pub(crate) fn parse_await(input: ParseStream) -> syn::Result<Self> {
+        let _guard = NestingGuard::enter(input)?;
+        let await_token = <Token![await]>::parse(input)?;
+        let blocking = input.parse::<Option<kw::blocking>>()?;
+        let future = Value::parse_opt(input).unwrap_or_else(|| {
+            // take everything up to the closure arguments as the future
+            let mut tokens = TokenStream::new();
+            let mut span = None::<Span>;
+            while !(input.peek(Token![|]) || input.peek(Token![||])) {
+                let Ok(tt) = input.parse::<TokenTree>() else { break };
+                span = Some(span.map_or(tt.span(), |start| span::join(start, tt.span())));
+                tokens.append(tt);
+            }
+            let span = span.unwrap_or(await_token.span);
+            emit_error!(
+                span,
+                "expected the future in braces: `await {fut} |x| { .. }`"
+            );
+            if tokens.is_empty() {
+                tokens = quote_spanned!(span=> ::leptos_mview::MissingValueAfterEq);
+            }
+            Value::Block {
+                tokens,
+                braces: syn::token::Brace(span),
+            }
+        });
+
+        let tag = Tag::Component(syn::parse_quote_spanned! { await_token.span=>
+            ::leptos::prelude::Await
+        });
+        let mut attrs = Attrs::default();
+        let prop = |name: &str, span: Span, value: Value| {
+            Attr::Kv(KvAttr::new(
+                KebabIdent::from(syn::Ident::new(name, span)),
+                value,
+            ))
+        };
+        attrs.push(prop("future", await_token.span, future));
+        if let Some(blocking) = blocking {
+            attrs.push(prop("blocking", blocking.span, Value::new_true()));
+        }
+
+        if !input.peek(Token![|]) {
+            return Err(input.error(
+                "expected closure arguments for the resolved value, like `|data| { ... }`",
+            ));
+        }
+        let args = parse_closure_args(input)?;
+        if !(input.peek(syn::token::Brace) || input.peek(syn::token::Paren)) {
+            return Err(input.error("expected children block after closure arguments"));
+        }
+        let children = parse_children_block(input)?;
+        skip_extra_children_blocks(input, children.0);
+        Ok(Self::new(
+            tag,
+            SelectorShorthands::default(),
+            attrs,
+            Some(args),
+            Some(children),
+        ))
+    }
+}
+
 impl ToTokens for Element {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if let Some(call) = mixin_call_tokens(self, &TokenStream::new()) {
+            tokens.extend(call);
+            return;
+        }
         tokens.extend(xml_to_tokens(self).unwrap_or_else(|| {
             component_to_tokens::<false>(self).expect("element should be a component")
         }));
@@ -115,19 +364,23 @@ impl ToTokens for Element {
 }
 
 impl Element {
-    pub const fn new(
+    pub fn new(
         tag: Tag,
         selectors: SelectorShorthands,
         attrs: Attrs,
         children_args: Option<TokenStream>,
-        children: Option<Children>,
+        children: Option<(Span, Children)>,
     ) -> Self {
+        let (children_span, children) = children.unzip();
         Self {
             tag,
             selectors,
             attrs,
             children_args,
             children,
+            children_span,
+            mixin: None,
+            allow_strict: false,
         }
     }
 
@@ -140,6 +393,103 @@ impl Element {
     pub const fn children_args(&self) -> Option<&TokenStream> { self.children_args.as_ref() }
 
     pub const fn children(&self) -> Option<&Children> { self.children.as_ref() }
+
+    pub const fn tag_mut(&mut self) -> &mut Tag { &mut self.tag }
+
+    pub const fn selectors_mut(&mut self) -> &mut SelectorShorthands { &mut self.selectors }
+
+    pub const fn attrs_mut(&mut self) -> &mut Attrs { &mut self.attrs }
+
+    pub const fn children_mut(&mut self) -> Option<&mut Children> { self.children.as_mut() }
+
+    /// Returns the span of the delimiters around the children block, if there
+    /// is one.
+    pub const fn children_span(&self) -> Option<Span> { self.children_span }
+
+    /// Returns the first attribute group of the element, along with the
+    /// element's tokens without it.
+    pub const fn mixin(&self) -> Option<&(Mixin, TokenStream)> { self.mixin.as_ref() }
+
+    /// Whether the element was marked with `#[allow(strict)]`.
+    pub const fn allows_strict(&self) -> bool { self.allow_strict }
+
+    /// Marks the element with `#[allow(strict)]`, so that its attribute names
+    /// are not linted.
+    pub const fn set_allow_strict(&mut self) { self.allow_strict = true; }
+}
+
+/// Collects the tokens from `from` up to `to`, or to the end of `from`'s
+/// group.
+fn tokens_between(mut from: Cursor, to: Cursor) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    while from != to {
+        let Some((tt, next)) = from.token_tree() else {
+            break;
+        };
+        tokens.append(tt);
+        from = next;
+    }
+    tokens
+}
+
+/// Returns the span of the last token in the input.
+fn last_token_span(input: ParseStream) -> Option<Span> {
+    let mut last = None;
+    while let Ok(tt) = input.parse::<TokenTree>() {
+        last = Some(tt.span());
+    }
+    last
+}
+
+/// Parses a children block wrapped in either braces or parens, along with
+/// the span of the delimiters.
+fn parse_children_block(input: ParseStream) -> syn::Result<(Span, Children)> {
+    if input.peek(syn::token::Brace) {
+        let (brace, children) = parse::braced::<Children>(input)?;
+        Ok((brace.span.join(), children))
+    } else {
+        let (paren, children) = parse::parenthesized::<Children>(input)?;
+        Ok((paren.span.join(), children))
+    }
+}
+
+/// Emits an error for each brace or paren group on the same line directly after
+/// a completed children block, like `div { "a" } { "b" }`.
+///
+/// The extra groups are skipped so that they don't become children of the
+/// parent instead. Blocks on a following line are left alone, as they are
+/// usually value children of the parent.
+fn skip_extra_children_blocks(input: ParseStream, children_span: Span) {
+    let mut end = children_span;
+    while (input.peek(syn::token::Brace) || input.peek(syn::token::Paren))
+        && span::lines_between(end, input.span()) == Some(0)
+    {
+        let Ok(TokenTree::Group(group)) = input.parse::<TokenTree>() else {
+            return;
+        };
+        emit_error!(
+            group.span_open(),
+            "element already has children; merge the blocks or add a semicolon to start a new element"
+        );
+        end = group.span();
+    }
+}
+
+/// Emits an error if a brace group comes right after the `;` of an element,
+/// like `div; { "oops" }`, as it was probably meant to be the children.
+///
+/// The block is still parsed as a child of the parent. Putting a blank line
+/// between the `;` and the block suppresses the error.
+fn emit_error_if_block_after_semi(input: ParseStream, semi_span: Span) {
+    if input.peek(syn::token::Brace)
+        && span::lines_between(semi_span, input.span()).is_some_and(|lines| lines <= 1)
+    {
+        emit_error!(
+            input.span(), "block directly after an element with no children";
+            help = "remove the `;` to make the block the element's children, \
+                    or add a blank line before the block to keep it as a separate child"
+        );
+    }
 }
 
 /// Parses closure arguments like `|binding|` or `|(index, item)|`.
@@ -163,11 +513,11 @@ fn parse_closure_args(input: ParseStream) -> syn::Result<TokenStream> {
 
     loop {
         // parse until second `|` is found
-        if let Some(pipe) = rollback_err(input, <Token![|]>::parse) {
+        if let Some(pipe) = input.parse::<Option<Token![|]>>()? {
             pipe.to_tokens(&mut tokens);
             break Ok(tokens);
-        } else if let Some(tt) = rollback_err(input, TokenTree::parse) {
-            tokens.append(tt);
+        } else if !input.is_empty() {
+            tokens.append(TokenTree::parse(input)?);
         } else {
             break Err(syn::Error::new_spanned(
                 first_pipe,
@@ -180,7 +530,10 @@ fn parse_closure_args(input: ParseStream) -> syn::Result<TokenStream> {
 #[cfg(test)]
 mod tests {
 
-    use super::Element;
+    use quote::ToTokens;
+
+    use super::{reset_max_nesting_depth, Element, DEFAULT_MAX_NESTING_DEPTH, MAX_NESTING_DEPTH};
+    use crate::ast::Tag;
 
     #[test]
     fn full_element() {
@@ -199,6 +552,22 @@ mod tests {
         assert!(element.children().is_none());
     }
 
+    #[test]
+    fn slash_terminated_element() {
+        let input = r#"img src="x" /"#;
+        let element: Element = syn::parse_str(input).unwrap();
+        assert_eq!(element.attrs().len(), 1);
+        assert!(element.children().is_none());
+    }
+
+    #[test]
+    fn selectors_between_attrs() {
+        let input = r#"input type="text" .wide disabled #main.big;"#;
+        let element: Element = syn::parse_str(input).unwrap();
+        assert_eq!(element.attrs().len(), 2);
+        assert_eq!(element.selectors().len(), 3);
+    }
+
     #[test]
     fn no_child_or_attrs() {
         let input = "br;";
@@ -206,4 +575,97 @@ mod tests {
         assert_eq!(element.attrs().len(), 0);
         assert!(element.children.is_none());
     }
+
+    #[test]
+    fn wide_and_deep() {
+        // a 400 attribute element, 4000 siblings, and the deepest allowed nesting
+        let attrs = (0..400)
+            .map(|i| format!("data-a{i}={{a{i}}}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let siblings = (0..4000)
+            .map(|i| format!("span.s{i}(\"{i}\")"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let depth = DEFAULT_MAX_NESTING_DEPTH - 1;
+        let nested = format!("{}\"deep\"{}", "div { ".repeat(depth), " }".repeat(depth));
+        let input = format!("main {attrs} {{ {siblings} {nested} }}");
+
+        // rustc runs proc macros with an 8 MiB stack, more than test threads get
+        let parse_and_expand = move || {
+            let start = std::time::Instant::now();
+            let element: Element = syn::parse_str(&input).unwrap();
+            let _ = element.to_token_stream();
+            assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+            assert_eq!(element.attrs().len(), 400);
+            assert_eq!(element.children().unwrap().len(), 4001);
+        };
+        std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn(parse_and_expand)
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn too_deep() {
+        let depth = DEFAULT_MAX_NESTING_DEPTH + 1;
+        let input = format!("{}br;", "div > ".repeat(depth - 1));
+        let Err(e) = syn::parse_str::<Element>(&input) else {
+            panic!("nesting should be too deep")
+        };
+        assert_eq!(e.to_string(), "maximum nesting depth 128 exceeded");
+        // the error is at the innermost element
+        assert_eq!(e.span().start().column, input.len() - "br;".len());
+    }
+
+    #[test]
+    fn far_too_deep() {
+        // like a generated view, which used to overflow the stack
+        let input = format!("{}\"deep\"{}", "div { ".repeat(600), " }".repeat(600));
+        let expand = move || {
+            let errors = crate::expand(input.parse().unwrap()).unwrap_err();
+            errors.iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        let errors = std::thread::Builder::new()
+            .stack_size(8 * 1024 * 1024)
+            .spawn(expand)
+            .unwrap()
+            .join()
+            .unwrap();
+        assert_eq!(errors, ["maximum nesting depth 128 exceeded"]);
+    }
+
+    #[test]
+    fn configured_nesting_depth() {
+        let parse = || {
+            MAX_NESTING_DEPTH.set(Some(4));
+            let shallow = syn::parse_str::<Element>("div > div > div > br;");
+            let deep = syn::parse_str::<Element>("div > div > div > div > br;");
+            // the next macro call reads the variable again
+            reset_max_nesting_depth();
+            let next_call = syn::parse_str::<Element>("div > div > div > div > br;");
+            (
+                shallow.is_ok(),
+                deep.err().map(|e| e.to_string()),
+                next_call.is_ok(),
+            )
+        };
+        // the limit is per thread, so this doesn't change the other tests
+        let (shallow, deep, next_call) = std::thread::spawn(parse).join().unwrap();
+        assert!(shallow);
+        assert_eq!(deep.as_deref(), Some("maximum nesting depth 4 exceeded"));
+        assert!(next_call);
+    }
+
+    #[test]
+    fn implicit_div() {
+        let input = r#".card.primary #main data-index=0 { "child" }"#;
+        let element: Element = syn::parse_str(input).unwrap();
+        assert!(matches!(element.tag(), Tag::Html(ident) if ident == "div"));
+        assert_eq!(element.selectors().len(), 3);
+        assert_eq!(element.attrs().len(), 1);
+    }
 }
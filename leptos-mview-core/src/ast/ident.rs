@@ -1,6 +1,5 @@
 use proc_macro2::{Span, TokenStream};
-use proc_macro_error2::emit_error;
-use quote::{quote, ToTokens};
+use quote::quote;
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
@@ -10,6 +9,7 @@ use syn::{
 
 use super::Value;
 use crate::{
+    error_ext::emit_error,
     parse::{self, rollback_err},
     span,
 };
@@ -52,8 +52,11 @@ impl KebabIdent {
     /// Returns the span of this [`KebabIdent`].
     ///
     /// The span of the first and last 'section' (dash, ident or lit int) are
-    /// joined. This only works on nightly, so only the first section's span is
-    /// returned on stable.
+    /// joined. This only works with the `nightly` feature, so only the first
+    /// section's span is returned otherwise.
+    ///
+    /// # Panics
+    /// Never, as a parsed kebab ident always has at least one section.
     pub fn span(&self) -> Span {
         span::join(
             self.spans[0],
@@ -91,8 +94,8 @@ impl KebabIdent {
     /// Converts this ident to a `syn::Ident` with the appropriate span, by
     /// replacing all `-`s with `_`.
     ///
-    /// The span will only be the first 'section' on stable, but correctly
-    /// covers the full ident on nightly. See [`KebabIdent::span`] for more
+    /// The span will only be the first 'section' by default, but covers the
+    /// full ident with the `nightly` feature. See [`KebabIdent::span`] for more
     /// details.
     ///
     /// The ident will also be a raw identifier.
@@ -104,6 +107,19 @@ impl KebabIdent {
         // since the ident does not start with a number.
         syn::Ident::new_raw(&snake_string, self.span())
     }
+
+    /// Converts this ident to a `syn::Ident` like
+    /// [`to_snake_ident`](Self::to_snake_ident), but only spanned to the first
+    /// 'section'.
+    ///
+    /// A span joined over every section doesn't match any token in the input,
+    /// so rust-analyzer can't map it back for hovering or renaming. Use this
+    /// for idents that refer to an item, like a prop's builder method.
+    pub fn to_snake_ident_first_span(&self) -> syn::Ident {
+        let mut ident = self.to_snake_ident();
+        ident.set_span(self.spans[0]);
+        ident
+    }
 }
 
 impl Parse for KebabIdent {
@@ -112,12 +128,13 @@ impl Parse for KebabIdent {
         let mut spans = Vec::new();
 
         // Start with `-` or letter.
-        if let Some(ident) = rollback_err(input, syn::Ident::parse_any) {
+        if input.peek(syn::Ident::peek_any) {
+            let ident = syn::Ident::parse_any(input)?;
             // only store the non-raw representation: in expansion,
             // this should expand to a raw ident.
             repr.push_str(&ident.unraw().to_string());
             spans.push(ident.span());
-        } else if let Some(dash) = rollback_err(input, <Token![-]>::parse) {
+        } else if let Some(dash) = input.parse::<Option<Token![-]>>()? {
             repr.push('-');
             spans.push(dash.span);
         } else {
@@ -134,7 +151,7 @@ impl Parse for KebabIdent {
             // After every loop, the next ident should be a `-`.
             // Otherwise, this means it was two idents separated by a space,
             // e.g. `one two`.
-            if rollback_err(input, <Token![-]>::parse).is_some() {
+            if input.parse::<Option<Token![-]>>()?.is_some() {
                 repr.push('-');
             } else if !(is_second_token && repr == "-") {
                 // unless the ident starts with a single `-`, then the next
@@ -145,7 +162,8 @@ impl Parse for KebabIdent {
             is_second_token = false;
 
             // add ident or number
-            if let Some(ident) = rollback_err(input, syn::Ident::parse_any) {
+            if input.peek(syn::Ident::peek_any) {
+                let ident = syn::Ident::parse_any(input)?;
                 let unraw = ident.unraw();
                 if ident != unraw {
                     emit_error!(ident.span(), "invalid raw identifier within kebab-ident");
@@ -217,36 +235,45 @@ impl KebabIdentOrStr {
 
 impl Parse for KebabIdentOrStr {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        if let Some(str) = rollback_err(input, |input| <syn::LitStr as Parse>::parse(input)) {
-            Ok(Self::Str(str))
+        if input.peek(syn::LitStr) {
+            Ok(Self::Str(input.parse()?))
         } else {
             Ok(Self::KebabIdent(KebabIdent::parse(input)?))
         }
     }
 }
 
-/// Parses a braced kebab-cased ident like `{abc-123}`
+/// Parses a braced kebab-cased ident like `{abc-123}`, or a field access like
+/// `{config.abc-123}`.
+///
+/// The last segment is used as the key, and the field access (converted to
+/// snake case) as the value.
 ///
 /// Equivalent to `parse::braced::<KebabIdent>(input)`, but provides a few
 /// methods to help with conversions.
 pub struct BracedKebabIdent {
     brace_token: Brace,
-    ident: KebabIdent,
+    field: KebabField,
 }
 
 impl BracedKebabIdent {
-    pub const fn new(brace: Brace, ident: KebabIdent) -> Self {
-        Self {
-            brace_token: brace,
-            ident,
-        }
-    }
-
-    pub const fn ident(&self) -> &KebabIdent { &self.ident }
+    pub const fn ident(&self) -> &KebabIdent { &self.field.ident }
 
+    /// Converts the field access to a block, like `{config.abc_123}`.
+    ///
+    /// An ident without any `-`s keeps its own span, so that rust-analyzer can
+    /// map it back to the variable for hovering and renaming. A kebab-case
+    /// ident has no single token to map back to, so the `snake_case` ident is
+    /// spanned to the whole shorthand instead: errors like a missing variable
+    /// still point at the braces written in the view.
     pub fn into_block_value(self) -> Value {
+        let base = self.field.base;
+        let mut ident = self.field.ident.to_snake_ident();
+        if self.field.ident.spans().len() > 1 {
+            ident.set_span(self.brace_token.span.join());
+        }
         Value::Block {
-            tokens: self.ident.to_snake_ident().into_token_stream(),
+            tokens: quote! { #base #ident },
             braces: self.brace_token,
         }
     }
@@ -254,8 +281,29 @@ impl BracedKebabIdent {
 
 impl Parse for BracedKebabIdent {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let (brace, ident) = parse::braced::<KebabIdent>(input)?;
-        Ok(Self::new(brace, ident))
+        let (brace_token, field) = parse::braced::<KebabField>(input)?;
+        Ok(Self { brace_token, field })
+    }
+}
+
+/// A kebab ident with any number of field accesses before it, like
+/// `self.config.abc-123`.
+struct KebabField {
+    /// Everything before the last segment, including the trailing `.`.
+    base: TokenStream,
+    ident: KebabIdent,
+}
+
+impl Parse for KebabField {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut base = TokenStream::new();
+        while input.peek(syn::Ident::peek_any) && input.peek2(Token![.]) {
+            let ident = syn::Ident::parse_any(input)?;
+            let dot = <Token![.]>::parse(input)?;
+            base.extend(quote! { #ident #dot });
+        }
+        let ident = KebabIdent::parse(input)?;
+        Ok(Self { base, ident })
     }
 }
 
@@ -263,7 +311,9 @@ impl Parse for BracedKebabIdent {
 mod tests {
     use std::iter;
 
-    use super::KebabIdent;
+    use quote::ToTokens;
+
+    use super::{BracedKebabIdent, KebabIdent};
 
     #[test]
     fn valid_reprs() {
@@ -280,6 +330,9 @@ mod tests {
             "-",
             "-_-_a",
             "for",
+            "όνομα-κύριο",
+            "名字-2",
+            "data-按钮",
         ];
 
         for stream in streams {
@@ -317,4 +370,31 @@ mod tests {
             assert_eq!(ident.repr(), res);
         }
     }
+
+    #[test]
+    fn non_ascii_snake_ident() {
+        let ident = syn::parse_str::<KebabIdent>("τίτλος-名").unwrap();
+        assert_eq!(ident.spans().len(), 2);
+        assert_eq!(ident.to_snake_ident(), "r#τίτλος_名");
+    }
+
+    #[test]
+    fn braced_field_access() {
+        let streams = ["{title}", "{config.title}", "{self.config.data-title}"];
+        let keys = ["title", "title", "data-title"];
+        let values = [
+            "{ r#title }",
+            "{ config . r#title }",
+            "{ self . config . r#data_title }",
+        ];
+
+        for ((stream, key), value) in iter::zip(iter::zip(streams, keys), values) {
+            let braced = syn::parse_str::<BracedKebabIdent>(stream).unwrap();
+            assert_eq!(braced.ident().repr(), key);
+            assert_eq!(
+                braced.into_block_value().to_token_stream().to_string(),
+                value
+            );
+        }
+    }
 }
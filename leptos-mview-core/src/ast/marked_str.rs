@@ -0,0 +1,236 @@
+use proc_macro2::Span;
+use quote::{quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    Token,
+};
+
+use crate::{error_ext::emit_error, span};
+
+/// A transformation applied to a [`MarkedStr`] at compile time.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StrMarker {
+    /// `#[dedent]`: removes the common indentation and joins the lines.
+    Dedent,
+    /// `#[entities]`: decodes HTML entities like `&nbsp;`.
+    Entities,
+}
+
+/// A string child with one or more markers, like `#[dedent] "some long text"`.
+///
+/// The markers are applied at compile time, so the result is always a single
+/// string literal. See [`StrMarker`] for the supported markers.
+pub struct MarkedStr {
+    markers: Vec<(StrMarker, Span)>,
+    lit: syn::LitStr,
+}
+
+impl MarkedStr {
+    /// Returns the markers in the order they were written.
+    pub fn markers(&self) -> impl ExactSizeIterator<Item = StrMarker> + '_ {
+        self.markers.iter().map(|(marker, _)| *marker)
+    }
+
+    /// Returns the string literal without any markers applied.
+    pub const fn lit(&self) -> &syn::LitStr { &self.lit }
+
+    /// Returns the span of the given marker, if this string has it.
+    pub fn marker_span(&self, marker: StrMarker) -> Option<Span> {
+        self.markers
+            .iter()
+            .find(|(m, _)| *m == marker)
+            .map(|(_, span)| *span)
+    }
+
+    pub fn span(&self) -> Span {
+        let first = self
+            .markers
+            .first()
+            .map_or(self.lit.span(), |(_, span)| *span);
+        span::join(first, self.lit.span())
+    }
+
+    /// Returns the string with all the markers applied.
+    ///
+    /// Emits an error if the markers cannot be applied.
+    pub fn value(&self) -> String {
+        self.try_value().unwrap_or_else(|unknown| {
+            emit_error!(
+                self.lit.span(),
+                "unknown HTML entities: {}",
+                unknown.join(", ")
+            );
+            self.lit.value()
+        })
+    }
+
+    /// Returns the string with all the markers applied.
+    ///
+    /// # Errors
+    /// Returns the unknown entities if `#[entities]` fails.
+    pub fn try_value(&self) -> Result<String, Vec<String>> {
+        let mut string = self.lit.value();
+        if self.marker_span(StrMarker::Dedent).is_some() {
+            string = dedent(&string);
+        }
+        if self.marker_span(StrMarker::Entities).is_some() {
+            string = decode_entities(&string)?;
+        }
+        Ok(string)
+    }
+}
+
+impl Parse for MarkedStr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut markers = Vec::new();
+        while input.peek(Token![#]) {
+            let pound = <Token![#]>::parse(input)?;
+            let content;
+            let brackets = syn::bracketed!(content in input);
+            let span = span::join(pound.span, brackets.span.join());
+
+            let name = content.parse::<syn::Ident>()?;
+            if !content.is_empty() {
+                emit_error!(content.span(), "child attributes do not take arguments");
+                content.parse::<proc_macro2::TokenStream>()?;
+            }
+            if name == "dedent" {
+                markers.push((StrMarker::Dedent, span));
+            } else if name == "entities" {
+                markers.push((StrMarker::Entities, span));
+            } else {
+                // the string is still parsed as normal
+                emit_error!(
+                    name.span(), "unknown child attribute";
+                    help = "only `#[dedent]` and `#[entities]` are supported"
+                );
+            }
+        }
+
+        let lit = input.parse::<syn::LitStr>().map_err(|e| {
+            syn::Error::new(e.span(), "expected a string literal after child attributes")
+        })?;
+
+        Ok(Self { markers, lit })
+    }
+}
+
+impl ToTokens for MarkedStr {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let string = self.value();
+        tokens.extend(quote_spanned!(self.lit.span()=> #string));
+    }
+}
+
+/// Removes the common leading whitespace from each line, then joins the
+/// non-blank lines with a single space.
+///
+/// The first line is not used to find the common whitespace if it is on the
+/// same line as the opening quote.
+fn dedent(s: &str) -> String {
+    let leading_whitespace = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+
+    let lines: Vec<&str> = s.lines().map(str::trim_end).collect();
+    let indent = lines
+        .iter()
+        .enumerate()
+        // the first line is only indented if it starts after the opening quote
+        .filter(|(i, line)| !line.is_empty() && (*i > 0 || s.starts_with(char::is_whitespace)))
+        .map(|(_, line)| leading_whitespace(line))
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            // strip at most `indent` whitespace
+            let strip = leading_whitespace(line).min(indent);
+            line.chars().skip(strip).collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Decodes all named and numeric HTML entities.
+///
+/// A `&` that doesn't start something like an entity (`&name;`, `&#123;`,
+/// `&#x1F;`) is left as-is. Returns all the entity-like tokens that could not
+/// be decoded as an [`Err`].
+fn decode_entities(s: &str) -> Result<String, Vec<String>> {
+    let mut decoded = String::with_capacity(s.len());
+    let mut unknown = Vec::new();
+    let mut rest = s;
+
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+
+        let name_len = rest[1..]
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '#'))
+            .unwrap_or(rest.len() - 1);
+        if name_len > 0 && rest[1 + name_len..].starts_with(';') {
+            let entity = &rest[..name_len + 2];
+            let decoded_entity = html_escape::decode_html_entities(entity);
+            if decoded_entity == entity {
+                unknown.push(entity.to_string());
+            }
+            decoded.push_str(&decoded_entity);
+            rest = &rest[entity.len()..];
+        } else {
+            decoded.push('&');
+            rest = &rest[1..];
+        }
+    }
+    decoded.push_str(rest);
+
+    if unknown.is_empty() {
+        Ok(decoded)
+    } else {
+        Err(unknown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_entities, dedent};
+
+    #[test]
+    fn dedents() {
+        let cases = [
+            ("one line", "one line"),
+            ("\n    a\n    b\n", "a b"),
+            ("first\n    second\n    third", "first second third"),
+            ("\n    a\n      indented\n\n    b\n  ", "a   indented b"),
+            ("\n\ta\n\tb", "a b"),
+            ("", ""),
+            ("\n\u{3000}a\n\u{3000}b", "a b"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(dedent(input), expected);
+        }
+    }
+
+    #[test]
+    fn decodes_entities() {
+        let cases = [
+            ("a&nbsp;b", "a\u{a0}b"),
+            ("&amp;&lt;&gt;", "&<>"),
+            ("1&mdash;2", "1\u{2014}2"),
+            ("&#65;&#x42;", "AB"),
+            ("Tom & Jerry", "Tom & Jerry"),
+            ("a&b", "a&b"),
+            ("trailing &", "trailing &"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(decode_entities(input).unwrap(), expected);
+        }
+
+        assert_eq!(
+            decode_entities("&nbsp;&notanentity;&amp;&bad;").unwrap_err(),
+            ["&notanentity;", "&bad;"]
+        );
+    }
+}
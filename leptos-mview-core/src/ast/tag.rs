@@ -6,7 +6,13 @@ use syn::{
     Token,
 };
 
-use crate::ast::KebabIdent;
+use crate::{
+    ast::{
+        attribute::{mixin::Mixin, selector::SelectorShorthands},
+        KebabIdent,
+    },
+    error_ext::emit_error,
+};
 
 #[allow(clippy::doc_markdown)]
 /// The name of the element, like `div`, `path`, `For`, `leptos-island`, etc.
@@ -57,16 +63,55 @@ impl Tag {
             Tag::WebComponent(_) => TagKind::WebComponent,
         }
     }
+
+    /// Checks for generics on an element that is not a component, like
+    /// `div<String>`.
+    ///
+    /// Emits an error and returns the element without the generics if found.
+    fn from_generic_element(path: &syn::Path) -> Option<Self> {
+        if path.leading_colon.is_some() || path.segments.len() != 1 {
+            return None;
+        }
+        let segment = &path.segments[0];
+        let syn::PathArguments::AngleBracketed(generics) = &segment.arguments else {
+            return None;
+        };
+        let ident = segment.ident.clone();
+        let kind = TagKind::from(&*ident.unraw().to_string());
+        if kind == TagKind::Component {
+            return None;
+        }
+
+        emit_error!(
+            generics.lt_token.span, "HTML elements cannot take generic arguments";
+            note = "components must start with an uppercase letter, like `MyComponent<T>`"
+        );
+        Some(match kind {
+            TagKind::Svg => Self::Svg(ident),
+            TagKind::Math => Self::Math(ident),
+            _ => Self::Html(ident),
+        })
+    }
 }
 
 impl Parse for Tag {
     fn parse(input: ParseStream) -> syn::Result<Self> {
+        // a selector with no tag name is a div: `.card`, `#sidebar` or `@sidebar`.
+        // so is an attribute group: `@@clickable`.
+        // nothing is consumed, the selectors are parsed after the tag.
+        if SelectorShorthands::peek(input) || Mixin::peek(input) {
+            return Ok(Self::Html(syn::Ident::new("div", input.span())));
+        }
+
         // peek 1 in case it's a leading ::
         // this will also include any generics
         // also look for generics without a full path
         if input.peek2(Token![::]) || input.peek(Token![::]) || input.peek2(Token![<]) {
             // this is a path segment: must be a component
             let path = syn::Path::parse(input)?;
+            if let Some(tag) = Self::from_generic_element(&path) {
+                return Ok(tag);
+            }
             return Ok(Self::Component(path));
         }
 
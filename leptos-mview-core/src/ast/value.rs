@@ -1,21 +1,27 @@
-use proc_macro2::{Span, TokenStream};
-use proc_macro_error2::{emit_error, Diagnostic};
-use quote::{quote, quote_spanned, ToTokens};
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use proc_macro_error2::Diagnostic;
+use quote::{quote, quote_spanned, ToTokens, TokenStreamExt};
 use syn::{
     ext::IdentExt,
     parse::{Parse, ParseStream},
     parse_quote,
     spanned::Spanned,
+    Token,
 };
 
-use crate::parse::{self, rollback_err};
+use crate::{
+    error_ext::{emit_diagnostic, emit_error},
+    parse::{self, rollback_err},
+    span,
+};
 
 /// Interpolated Rust expressions within the macro.
 ///
 /// Block expressions like `{move || !is_red.get()}` are placed as so.
 ///
 /// Expressions within brackets are wrapped in a closure, e.g. `[!is_red.get()]`
-/// is expanded to `{move || !is_red.get()}`.
+/// is expanded to `{move || !is_red.get()}`. A bracket that already contains a
+/// closure, like `[|item| item.id]`, is left as that closure.
 ///
 /// Only literals can have no delimiter, to avoid ambiguity.
 ///
@@ -94,10 +100,24 @@ impl ToTokens for Value {
                         quote_spanned!(brackets.span.join()=> move || ::std::#format(#tokens))
                     } else if prefix == "a" {
                         quote_spanned!(brackets.span.join()=> move |#[allow(unused_variables)] a| {#tokens})
+                    } else if prefix == "memo" {
+                        // the memo is created once with the view, outside of
+                        // the closure that reads it, so it persists between runs
+                        let memo = syn::Ident::new("__mview_memo", Span::mixed_site());
+                        let memo_new = quote_spanned!(prefix.span()=> ::leptos::prelude::Memo::new);
+                        quote_spanned!(brackets.span.join()=> {
+                            let #memo = #memo_new(move |_| {#tokens});
+                            move || ::leptos::prelude::Get::get(&#memo)
+                        })
                     } else {
-                        emit_error!(prefix.span(), "unsupported prefix: only `f` is supported.");
+                        emit_error!(
+                            prefix.span(),
+                            "unsupported prefix: only `f` and `memo` are supported."
+                        );
                         quote! {}
                     }
+                } else if self.is_closure_bracket() {
+                    quote_spanned!(brackets.span.join()=> #tokens)
                 } else {
                     quote_spanned!(brackets.span.join()=> move || {#tokens})
                 }
@@ -106,6 +126,276 @@ impl ToTokens for Value {
     }
 }
 
+/// Takes an `if` (including any `else if`/`else` branches) or `match`
+/// expression, and returns the span of the last block.
+fn control_flow_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    let is_if = input.peek(Token![if]);
+    tokens.append(input.parse::<TokenTree>()?);
+
+    let mut has_condition = false;
+    let block = loop {
+        match input.parse::<TokenTree>() {
+            Ok(TokenTree::Group(group))
+                if group.delimiter() == Delimiter::Brace && has_condition =>
+            {
+                break group;
+            }
+            Ok(tt) => {
+                has_condition = true;
+                tokens.append(tt);
+            }
+            Err(_) => return Err(input.error("expected a block")),
+        }
+    };
+    let mut end = block.span();
+    tokens.append(block);
+
+    if is_if && input.peek(Token![else]) {
+        tokens.append(input.parse::<TokenTree>()?);
+        if input.peek(Token![if]) {
+            end = control_flow_tokens(input, tokens)?;
+        } else {
+            let (braces, body) = parse::braced_tokens(input)?;
+            let mut block = Group::new(Delimiter::Brace, body);
+            block.set_span(braces.span.join());
+            end = block.span();
+            tokens.append(block);
+        }
+    }
+    Ok(end)
+}
+
+/// Takes a closure like `move |ev| count.set(ev)`, and returns the span of its
+/// last token.
+///
+/// The body is a block, or an expression made of paths, literals, calls,
+/// method calls, operators and blocks. It ends at the first token that can't
+/// continue the expression, like the next attribute or a `;`. This leaves a
+/// few things that need the closure to be wrapped in braces:
+/// - A `|` (bitwise or) after an operand, as it starts the children's closure
+///   arguments.
+/// - A `/` or `>` followed by anything other than a number, parenthesized
+///   expression or prefix operator (like `a / b`), as it ends the element or
+///   starts its child.
+/// - A second call like `f()()`, as the parens are a children block.
+/// - Struct literals, as the braces are a children block.
+/// - Statement-like expressions, like `return` or `loop`.
+fn closure_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    if input.peek(Token![move]) {
+        take_token(input, tokens)?;
+    }
+    if input.peek(Token![||]) {
+        input.parse::<Token![||]>()?.to_tokens(tokens);
+    } else {
+        input.parse::<Token![|]>()?.to_tokens(tokens);
+        while !input.peek(Token![|]) {
+            take_token(input, tokens)?;
+        }
+        input.parse::<Token![|]>()?.to_tokens(tokens);
+    }
+
+    // a return type needs a block body
+    if input.peek(Token![->]) {
+        input.parse::<Token![->]>()?.to_tokens(tokens);
+        while !input.peek(syn::token::Brace) {
+            take_token(input, tokens)?;
+        }
+        return take_token(input, tokens);
+    }
+    expr_tokens(input, tokens)
+}
+
+/// Takes operands separated by binary operators, and returns the span of the
+/// last token.
+fn expr_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    loop {
+        let end = operand_tokens(input, tokens)?;
+        let Some((punct, _)) = input.cursor().punct() else {
+            return Ok(end);
+        };
+        let is_operator = match punct.as_char() {
+            // a single `|` starts the children's closure arguments instead
+            '|' => input.peek(Token![||]) || input.peek(Token![|=]),
+            '!' => input.peek(Token![!=]),
+            '.' => input.peek(Token![..]),
+            // a lone `/` or `>` ends the element or starts its child, unless an
+            // operand that can't be a child follows
+            '/' | '>' if punct.spacing() == proc_macro2::Spacing::Alone => {
+                divides_or_compares(input)
+            }
+            c => "+-*/%^&=<>".contains(c),
+        };
+        if !is_operator {
+            return Ok(end);
+        }
+        // multi-character operators are made of joint puncts
+        while let TokenTree::Punct(punct) = input.parse::<TokenTree>()? {
+            let spacing = punct.spacing();
+            tokens.append(punct);
+            if spacing == proc_macro2::Spacing::Alone || input.cursor().punct().is_none() {
+                break;
+            }
+        }
+    }
+}
+
+/// Whether the lone `/` or `>` at the start of `input` is an operator, as it
+/// is followed by a number, a parenthesized expression or a prefix operator.
+///
+/// Anything else (like `;`, an element, a string or the end of the input)
+/// means that it is the element terminator `/` or the child shorthand `>`.
+fn divides_or_compares(input: ParseStream) -> bool {
+    let fork = input.fork();
+    let _ = fork.parse::<TokenTree>();
+    (fork.peek(syn::Lit) && !fork.peek(syn::LitStr))
+        || fork.peek(syn::token::Paren)
+        || fork.peek(Token![-])
+        || fork.peek(Token![!])
+        || fork.peek(Token![*])
+        || fork.peek(Token![&])
+        || fork.peek(Token![self])
+}
+
+/// Takes a single operand with any prefix operators, method calls, field
+/// accesses, calls, indexes, `?` or `as` casts, and returns the span of the
+/// last token.
+fn operand_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    while input.peek(Token![!]) || input.peek(Token![-]) || input.peek(Token![*]) {
+        take_token(input, tokens)?;
+    }
+    while input.peek(Token![&]) {
+        take_token(input, tokens)?;
+        if input.peek(Token![mut]) {
+            take_token(input, tokens)?;
+        }
+    }
+
+    // whether a paren group after this is a call
+    let mut callable = false;
+    let mut end = if input.peek(Token![if]) || input.peek(Token![match]) {
+        control_flow_tokens(input, tokens)?
+    } else if input.peek(Token![move]) || input.peek(Token![|]) {
+        // the body of a nested closure goes as far as possible
+        return closure_tokens(input, tokens);
+    } else if input.peek(Token![async]) {
+        take_token(input, tokens)?;
+        if input.peek(Token![move]) {
+            take_token(input, tokens)?;
+        }
+        if !input.peek(syn::token::Brace) {
+            return Err(input.error("expected a block"));
+        }
+        take_token(input, tokens)?
+    } else if input.peek(syn::Lit)
+        || input.peek(syn::token::Paren)
+        || input.peek(syn::token::Bracket)
+        || input.peek(syn::token::Brace)
+    {
+        take_token(input, tokens)?
+    } else if input.peek(syn::Ident)
+        || input.peek(Token![self])
+        || input.peek(Token![Self])
+        || input.peek(Token![super])
+        || input.peek(Token![crate])
+        || input.peek(Token![::])
+    {
+        let (end, is_macro) = path_tokens(input, tokens)?;
+        callable = !is_macro;
+        end
+    } else {
+        return Err(input.error("expected an expression"));
+    };
+
+    loop {
+        if input.peek(Token![.]) && !input.peek(Token![..]) {
+            take_token(input, tokens)?;
+            if !(input.peek(syn::Ident::peek_any) || input.peek(syn::LitInt)) {
+                return Err(input.error("expected a method or field"));
+            }
+            end = take_token(input, tokens)?;
+            if input.peek(Token![::]) {
+                input.parse::<Token![::]>()?.to_tokens(tokens);
+                end = generic_args_tokens(input, tokens)?;
+            }
+            callable = true;
+        } else if callable && input.peek(syn::token::Paren) {
+            // only one call, another paren group is a children block
+            end = take_token(input, tokens)?;
+            callable = false;
+        } else if input.peek(syn::token::Bracket) || input.peek(Token![?]) {
+            end = take_token(input, tokens)?;
+            callable = false;
+        } else if input.peek(Token![as]) {
+            take_token(input, tokens)?;
+            let ty = input.parse::<syn::Type>()?;
+            end = ty.span();
+            ty.to_tokens(tokens);
+            callable = false;
+        } else {
+            return Ok(end);
+        }
+    }
+}
+
+/// Takes a path like `a::b::<T>::c`, or a macro call like `format!(..)`, and
+/// returns the span of the last token and whether it was a macro call.
+fn path_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<(Span, bool)> {
+    if input.peek(Token![::]) {
+        input.parse::<Token![::]>()?.to_tokens(tokens);
+    }
+    let mut end = take_token(input, tokens)?;
+    while input.peek(Token![::]) {
+        input.parse::<Token![::]>()?.to_tokens(tokens);
+        end = if input.peek(Token![<]) {
+            generic_args_tokens(input, tokens)?
+        } else {
+            let ident = syn::Ident::parse_any(input)?;
+            let span = ident.span();
+            tokens.append(ident);
+            span
+        };
+    }
+    if input.peek(Token![!])
+        && (input.peek2(syn::token::Paren)
+            || input.peek2(syn::token::Bracket)
+            || input.peek2(syn::token::Brace))
+    {
+        take_token(input, tokens)?;
+        return Ok((take_token(input, tokens)?, true));
+    }
+    Ok((end, false))
+}
+
+/// Takes generic arguments like `<Vec<T>, U>`, and returns the span of the
+/// closing `>`.
+fn generic_args_tokens(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    input.parse::<Token![<]>()?.to_tokens(tokens);
+    let mut depth = 1_usize;
+    loop {
+        let tt = input.parse::<TokenTree>()?;
+        let span = tt.span();
+        if let TokenTree::Punct(punct) = &tt {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => (),
+            }
+        }
+        tokens.append(tt);
+        if depth == 0 {
+            return Ok(span);
+        }
+    }
+}
+
+/// Takes a single [`TokenTree`], returning its span.
+fn take_token(input: ParseStream, tokens: &mut TokenStream) -> syn::Result<Span> {
+    let tt = input.parse::<TokenTree>()?;
+    let span = tt.span();
+    tokens.append(tt);
+    Ok(span)
+}
+
 impl Value {
     /// Returns the [`Span`] of this [`Value`].
     ///
@@ -118,10 +408,67 @@ impl Value {
         }
     }
 
+    /// Whether this is a bracket without a prefix that already contains a
+    /// closure, like `[|item| item.id]` or `[move |ev| count.set(ev)]`.
+    ///
+    /// These are used as-is, instead of being wrapped in another closure.
+    pub fn is_closure_bracket(&self) -> bool {
+        let Self::Bracket {
+            tokens,
+            prefixes: None,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        let mut tokens = tokens.clone().into_iter();
+        let is_pipe =
+            |tt: Option<TokenTree>| matches!(tt, Some(TokenTree::Punct(p)) if p.as_char() == '|');
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) if ident == "move" => is_pipe(tokens.next()),
+            first => is_pipe(first),
+        }
+    }
+
+    /// Whether this is a block or bracket with nothing inside it, like `{}`.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            Self::Lit(_) => false,
+            Self::Block { tokens, .. } | Self::Bracket { tokens, .. } => tokens.is_empty(),
+        }
+    }
+
+    /// Whether the next tokens are a [`Value`], without parsing anything.
+    ///
+    /// [`Value::parse`] never consumes any tokens when this is `false`.
+    pub fn peek(input: ParseStream) -> bool {
+        input.peek(syn::token::Bracket)
+            || (input.peek(syn::Ident::peek_any) && input.peek2(syn::token::Bracket))
+            || input.peek(syn::token::Brace)
+            || input.peek(syn::Lit)
+    }
+
+    /// Parses any value allowed after an `=`, or returns `None` without
+    /// consuming anything if there isn't one.
+    pub fn parse_opt(input: ParseStream) -> Option<Self> {
+        let value = if Self::peek(input) {
+            Self::parse(input).ok()
+        } else if input.peek(Token![async]) {
+            rollback_err(input, Self::parse_async_block)
+        } else if input.peek(Token![if]) || input.peek(Token![match]) {
+            rollback_err(input, Self::parse_control_flow)
+        } else if Self::peek_closure(input) {
+            rollback_err(input, Self::parse_closure)
+        } else {
+            rollback_err(input, Self::parse_call)
+        };
+        value.inspect(Self::emit_error_if_empty)
+    }
+
     /// Either parses a valid [`Value`], or inserts a `MissingValueAfterEq`
     /// never-type enum.
     pub fn parse_or_emit_err(input: ParseStream, fallback_span: Span) -> Self {
-        if let Some(value) = rollback_err(input, Self::parse) {
+        if let Some(value) = Self::parse_opt(input) {
             value
         } else {
             // avoid call-site span
@@ -135,13 +482,19 @@ impl Value {
             );
             // if the token after the `=` is an ident, perhaps the user forgot to wrap in
             // braces.
-            let error = if input.peek(syn::Ident) {
+            let error = if Self::peek_closure(input) {
+                error.help(
+                    "this closure is too complex to be used without braces, wrap it in braces: \
+                     `{|..| ...}`"
+                        .to_string(),
+                )
+            } else if input.peek(syn::Ident) {
                 error.help("you may have meant to wrap this in braces".to_string())
             } else {
                 error
             };
 
-            error.emit();
+            emit_diagnostic(span, error);
             Self::Block {
                 tokens: quote_spanned!(span => ::leptos_mview::MissingValueAfterEq),
                 braces: syn::token::Brace(span),
@@ -149,6 +502,108 @@ impl Value {
         }
     }
 
+    /// Parses a single function call like `len(items)` or `t("home.title")`,
+    /// treating it as a block.
+    ///
+    /// Only a path followed by one parenthesized argument list is accepted, so
+    /// parsing stops at the closing paren. Anything more complex (method
+    /// calls, operators) still needs braces.
+    ///
+    /// This is only allowed in attribute values, as an element followed by
+    /// parens is an element with children.
+    fn parse_call(input: ParseStream) -> syn::Result<Self> {
+        let path = input.parse::<syn::ExprPath>()?;
+        let args = input.step(|cursor| match cursor.token_tree() {
+            Some((TokenTree::Group(group), rest))
+                if group.delimiter() == Delimiter::Parenthesis =>
+            {
+                Ok((group, rest))
+            }
+            _ => Err(cursor.error("expected function arguments")),
+        })?;
+
+        let span = span::join(path.span(), args.span());
+        Ok(Self::Block {
+            tokens: quote! { #path #args },
+            braces: syn::token::Brace(span),
+        })
+    }
+
+    /// Parses an async block like `async move { ... }`, treating it as a block.
+    ///
+    /// The `async` block is kept as a single expression, so it can be passed
+    /// straight to a prop that takes a future without any extra braces.
+    fn parse_async_block(input: ParseStream) -> syn::Result<Self> {
+        let async_token = <Token![async]>::parse(input)?;
+        let move_token = input.parse::<Option<Token![move]>>()?;
+        let (braces, tokens) = parse::braced_tokens(input)?;
+
+        let mut body = Group::new(Delimiter::Brace, tokens);
+        body.set_span(braces.span.join());
+        let span = span::join(async_token.span, braces.span.join());
+        Ok(Self::Block {
+            tokens: quote! { #async_token #move_token #body },
+            braces: syn::token::Brace(span),
+        })
+    }
+
+    /// Parses an `if` or `match` expression like
+    /// `if dark { "dark" } else { "light" }`, treating it as a block.
+    ///
+    /// As in Rust, the condition (or the matched value) ends at the first
+    /// block, so the expression doesn't need to be parsed.
+    fn parse_control_flow(input: ParseStream) -> syn::Result<Self> {
+        let start = input.span();
+        let mut tokens = TokenStream::new();
+        let end = control_flow_tokens(input, &mut tokens)?;
+        Ok(Self::Block {
+            tokens,
+            braces: syn::token::Brace(span::join(start, end)),
+        })
+    }
+
+    /// Whether the next tokens start a closure, like `|ev| ...` or
+    /// `move || ...`.
+    fn peek_closure(input: ParseStream) -> bool {
+        input.peek(Token![|])
+            || (input.peek(Token![move]) && (input.peek2(Token![|]) || input.peek2(Token![||])))
+    }
+
+    /// Parses a closure like `move |_| count.update(|c| *c += 1)`, treating it
+    /// as a block.
+    ///
+    /// See [`closure_tokens`] for where the closure is taken to end.
+    fn parse_closure(input: ParseStream) -> syn::Result<Self> {
+        let start = input.span();
+        let mut tokens = TokenStream::new();
+        let end = closure_tokens(input, &mut tokens)?;
+        Ok(Self::Block {
+            tokens,
+            braces: syn::token::Brace(span::join(start, end)),
+        })
+    }
+
+    /// Emits an error if this is a block or bracket with nothing inside it.
+    ///
+    /// Only used on attribute values: an empty block is still a valid child.
+    /// Attributes with an empty value are left out of the expansion, so that
+    /// this is the only error reported for them.
+    fn emit_error_if_empty(&self) {
+        match self {
+            Self::Block { tokens, braces } if tokens.is_empty() => emit_error!(
+                braces.span.join(), "expected an expression inside the braces";
+                note = "if you meant to use the attribute shorthand, remove the key and `=` before the braces"
+            ),
+            Self::Bracket {
+                tokens, brackets, ..
+            } if tokens.is_empty() => emit_error!(
+                brackets.span.join(), "expected an expression inside the brackets";
+                note = "`[...]` expands to `move || ...`, so the closure body is missing"
+            ),
+            _ => (),
+        }
+    }
+
     /// Constructs self as a literal `true` with no span.
     pub fn new_true() -> Self { Self::Lit(parse_quote!(true)) }
 }
@@ -157,6 +612,8 @@ impl Value {
 mod tests {
     use std::collections::HashMap;
 
+    use quote::ToTokens;
+
     use super::Value;
 
     /// Variant-only version of `Value` for quick checking.
@@ -203,4 +660,84 @@ mod tests {
             assert!(kind.value_is(value))
         }
     }
+
+    #[test]
+    fn call_values() {
+        use proc_macro2::TokenStream;
+        use syn::parse::{ParseStream, Parser};
+
+        for expr in ["len(items)", "t(\"home.title\")", "a::b::<i32>(1, 2)"] {
+            let value = Value::parse_call.parse_str(expr).unwrap();
+            assert!(value.is_block());
+        }
+
+        // stops after the call's closing paren
+        let parser = |input: ParseStream| {
+            let value = Value::parse_call(input)?;
+            Ok((value, input.parse::<TokenStream>()?))
+        };
+        let (value, rest) = parser.parse_str("len(items) next=3").unwrap();
+        assert!(value.is_block());
+        assert_eq!(rest.to_string(), "next = 3");
+
+        for invalid in ["len", "len[items]", "{len}(items)"] {
+            assert!(Value::parse_call.parse_str(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn closure_values() {
+        use proc_macro2::TokenStream;
+        use syn::parse::{ParseStream, Parser};
+
+        let parser = |input: ParseStream| {
+            let value = Value::parse_closure(input)?;
+            Ok((
+                value.into_token_stream().to_string(),
+                input.parse::<TokenStream>()?,
+            ))
+        };
+        let cases = [
+            (
+                "move |_| count.update(|c| *c += 1);",
+                "{ move | _ | count . update (| c | * c += 1) }",
+                ";",
+            ),
+            (
+                "|id| select(id) class=\"x\"",
+                "{ | id | select (id) }",
+                "class = \"x\"",
+            ),
+            (
+                "|| a.get() && !b.get() { \"x\" }",
+                "{ || a . get () && ! b . get () }",
+                "{ \"x\" }",
+            ),
+            (
+                "|ev| { ev.prevent_default(); }",
+                "{ | ev | { ev . prevent_default () ; } }",
+                "",
+            ),
+            (
+                "|_| log!(\"hi\") (\"child\")",
+                "{ | _ | log ! (\"hi\") }",
+                "(\"child\")",
+            ),
+            ("|x| x as u8 |args| {}", "{ | x | x as u8 }", "| args | { }"),
+            (
+                "|v| v.iter().map(|x| x.0).sum::<i32>()",
+                "{ | v | v . iter () . map (| x | x . 0) . sum :: < i32 > () }",
+                "",
+            ),
+        ];
+        for (input, value, rest) in cases {
+            let (parsed, parsed_rest) = parser.parse_str(input).unwrap();
+            assert_eq!(parsed, value);
+            assert_eq!(parsed_rest.to_string(), rest);
+        }
+
+        for invalid in ["|_| return", "|_| let x = 1", "|x|", "|_| -> u8 x"] {
+            assert!(Value::parse_closure.parse_str(invalid).is_err());
+        }
+    }
 }
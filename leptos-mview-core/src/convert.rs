@@ -0,0 +1,467 @@
+//! Converts the contents of a leptos `view!` macro to `mview!` syntax.
+//!
+//! Only the common parts of the `view!` syntax are supported. Anything that
+//! cannot be converted is reported as an error on that node, nothing is left
+//! out of the output.
+
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::{
+    ext::IdentExt,
+    parse::{ParseStream, Parser},
+    Token,
+};
+
+use crate::ast;
+
+/// Elements that have no closing tag, so `<br>` is allowed without a `/`.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Converts the contents of a `view!` call, without the surrounding braces,
+/// to the equivalent `mview!` syntax.
+///
+/// # Errors
+/// Returns an error on the first node that could not be converted.
+///
+/// # Examples
+/// ```
+/// # use leptos_mview_core::convert::view_to_mview;
+/// let view = r#"<div class="card"><p>"Hello" {name}</p><br/></div>"#;
+/// let mview = view_to_mview(view.parse().unwrap()).unwrap();
+/// assert_eq!(
+///     mview,
+///     "div class=\"card\" {\n    p { \"Hello\" {name} }\n    br;\n}\n"
+/// );
+/// ```
+pub fn view_to_mview(input: TokenStream) -> syn::Result<String> {
+    let nodes = parse_nodes_to_end.parse2(input)?;
+    let mut out = String::new();
+    for node in &nodes {
+        node.write(&mut out, 0);
+    }
+    // make sure that nothing went wrong while converting
+    ast::parse(out.parse()?)?;
+    Ok(out)
+}
+
+/// A converted node.
+enum Node {
+    /// A string literal, a block or a doctype.
+    Inline(String),
+    Element {
+        /// The tag, attributes and children arguments.
+        head: String,
+        /// `None` if the element is self-closing.
+        children: Option<Vec<Self>>,
+    },
+}
+
+impl Node {
+    /// Writes the node on its own line(s) at the given indentation.
+    fn write(&self, out: &mut String, indent: usize) {
+        out.push_str(&"    ".repeat(indent));
+        match self {
+            Self::Inline(s) => out.push_str(s),
+            Self::Element { head, children } => {
+                out.push_str(head);
+                match children.as_deref() {
+                    None => out.push(';'),
+                    Some([]) => out.push_str(" {}"),
+                    // keep short elements like `p { "text" }` on one line
+                    Some([Self::Inline(s)]) => {
+                        out.push_str(" { ");
+                        out.push_str(s);
+                        out.push_str(" }");
+                    }
+                    Some(children) if children.iter().all(|c| matches!(c, Self::Inline(_))) => {
+                        out.push_str(" { ");
+                        let inline = children.iter().map(|c| match c {
+                            Self::Inline(s) => s.as_str(),
+                            Self::Element { .. } => unreachable!(),
+                        });
+                        out.push_str(&inline.collect::<Vec<_>>().join(" "));
+                        out.push_str(" }");
+                    }
+                    Some(children) => {
+                        out.push_str(" {\n");
+                        for child in children {
+                            child.write(out, indent + 1);
+                        }
+                        out.push_str(&"    ".repeat(indent));
+                        out.push('}');
+                    }
+                }
+            }
+        }
+        out.push('\n');
+    }
+}
+
+fn unsupported(span: Span, reason: &str) -> syn::Error {
+    syn::Error::new(span, format!("couldn't convert this node: {reason}"))
+}
+
+/// Returns the tokens as they appear in the source, or their `to_string` if
+/// the source is not available.
+fn source_text(tokens: &[TokenTree]) -> String {
+    let (Some(first), Some(last)) = (tokens.first(), tokens.last()) else {
+        return String::new();
+    };
+    first
+        .span()
+        .join(last.span())
+        .and_then(|span| span.source_text())
+        .unwrap_or_else(|| tokens.iter().cloned().collect::<TokenStream>().to_string())
+}
+
+fn parse_nodes_to_end(input: ParseStream) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    while !input.is_empty() {
+        parse_node(input, &mut nodes)?;
+    }
+    Ok(nodes)
+}
+
+/// Parses children until the closing tag `</name>`, which is also consumed.
+///
+/// Use an empty name for the closing tag of a fragment.
+fn parse_nodes_until_close(input: ParseStream, name: &str, open: Span) -> syn::Result<Vec<Node>> {
+    let mut nodes = Vec::new();
+    loop {
+        if input.is_empty() {
+            return Err(unsupported(
+                open,
+                &format!("missing closing tag `</{name}>`"),
+            ));
+        }
+        if input.peek(Token![<]) && input.peek2(Token![/]) {
+            let lt = input.parse::<Token![<]>()?;
+            input.parse::<Token![/]>()?;
+            let close = if input.peek(Token![>]) { String::new() } else { parse_name(input)?.0 };
+            input.parse::<Token![>]>()?;
+            if close != name {
+                return Err(unsupported(
+                    lt.span,
+                    &format!("expected closing tag `</{name}>`, found `</{close}>`"),
+                ));
+            }
+            return Ok(nodes);
+        }
+        parse_node(input, &mut nodes)?;
+    }
+}
+
+/// Parses a single node, pushing it to `nodes`. Fragments push all of their
+/// children instead.
+fn parse_node(input: ParseStream, nodes: &mut Vec<Node>) -> syn::Result<()> {
+    if input.peek(Token![<]) {
+        if input.peek2(Token![!]) {
+            nodes.push(parse_doctype(input)?);
+        } else if input.peek2(Token![>]) {
+            let lt = input.parse::<Token![<]>()?;
+            input.parse::<Token![>]>()?;
+            nodes.extend(parse_nodes_until_close(input, "", lt.span)?);
+        } else {
+            nodes.push(parse_element(input)?);
+        }
+    } else if input.peek(syn::LitStr) {
+        let lit = input.parse::<syn::LitStr>()?;
+        nodes.push(Node::Inline(lit.to_token_stream().to_string()));
+    } else if let Some((TokenTree::Group(group), _)) = input.cursor().token_tree() {
+        if group.delimiter() != Delimiter::Brace {
+            return Err(unsupported(
+                group.span(),
+                "expected an element, text or a block",
+            ));
+        }
+        input.parse::<TokenTree>()?;
+        nodes.push(Node::Inline(source_text(&[TokenTree::Group(group)])));
+    } else {
+        nodes.push(parse_text(input)?);
+    }
+    Ok(())
+}
+
+/// Parses unquoted text up to the next element or block.
+fn parse_text(input: ParseStream) -> syn::Result<Node> {
+    let mut tokens = Vec::new();
+    while !input.is_empty() && !input.peek(Token![<]) && !input.peek(syn::token::Brace) {
+        let tt = input.parse::<TokenTree>()?;
+        if let TokenTree::Group(group) = &tt {
+            if group.delimiter() == Delimiter::None {
+                return Err(unsupported(
+                    group.span(),
+                    "expected an element, text or a block",
+                ));
+            }
+        }
+        tokens.push(tt);
+    }
+    let text = source_text(&tokens);
+    let lit = syn::LitStr::new(&text, tokens[0].span());
+    Ok(Node::Inline(lit.to_token_stream().to_string()))
+}
+
+/// Parses `<!DOCTYPE html>`.
+fn parse_doctype(input: ParseStream) -> syn::Result<Node> {
+    let lt = input.parse::<Token![<]>()?;
+    input.parse::<Token![!]>()?;
+    if input.peek(Token![-]) {
+        return Err(unsupported(lt.span, "comments are not supported"));
+    }
+    let doctype = syn::Ident::parse_any(input)?;
+    let html = syn::Ident::parse_any(input)?;
+    if !doctype.to_string().eq_ignore_ascii_case("doctype") || html != "html" {
+        return Err(unsupported(lt.span, "only `<!DOCTYPE html>` is supported"));
+    }
+    input.parse::<Token![>]>()?;
+    Ok(Node::Inline("!DOCTYPE html;".to_string()))
+}
+
+/// Parses a tag or attribute name like `div`, `my-element`, `on:click` or
+/// `leptos::Foo`, along with its span.
+fn parse_name(input: ParseStream) -> syn::Result<(String, Span)> {
+    let first = syn::Ident::parse_any(input)?;
+    let span = first.span();
+    let mut name = first.unraw().to_string();
+    loop {
+        if input.peek(Token![::]) {
+            input.parse::<Token![::]>()?;
+            name.push_str("::");
+        } else if input.peek(Token![-]) {
+            input.parse::<Token![-]>()?;
+            name.push('-');
+        } else if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            name.push(':');
+        } else {
+            break;
+        }
+        if input.peek(syn::LitInt) {
+            name.push_str(&input.parse::<syn::LitInt>()?.to_string());
+        } else {
+            name.push_str(&syn::Ident::parse_any(input)?.unraw().to_string());
+        }
+    }
+    Ok((name, span))
+}
+
+/// Parses an element from the `<` to its closing tag.
+fn parse_element(input: ParseStream) -> syn::Result<Node> {
+    let lt = input.parse::<Token![<]>()?;
+    let (name, name_span) = parse_name(input)?;
+    if input.peek(Token![<]) {
+        return Err(unsupported(
+            name_span,
+            "generic components are not supported",
+        ));
+    }
+
+    let mut attrs = Vec::new();
+    let mut args = None;
+    let mut is_slot = false;
+    let self_closing = loop {
+        if input.peek(Token![/]) && input.peek2(Token![>]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            break true;
+        }
+        if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            break false;
+        }
+        if input.is_empty() {
+            return Err(unsupported(lt.span, "unclosed tag"));
+        }
+
+        if input.peek(syn::token::Brace) {
+            let group = input.parse::<TokenTree>()?;
+            let source = source_text(std::slice::from_ref(&group));
+            if !source
+                .trim_start_matches('{')
+                .trim_start()
+                .starts_with("..")
+            {
+                return Err(unsupported(
+                    group.span(),
+                    "only spread attributes are supported",
+                ));
+            }
+            attrs.push(source);
+            continue;
+        }
+
+        let (key, key_span) = parse_name(input)?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(parse_attr_value(input, &key)?)
+        } else {
+            None
+        };
+
+        if key == "slot" && value.is_none() {
+            is_slot = true;
+        } else if let Some(arg) = key.strip_prefix("let:") {
+            if value.is_some() {
+                return Err(unsupported(
+                    key_span,
+                    "`let:` with a value is not supported",
+                ));
+            }
+            if args.replace(format!("|{arg}|")).is_some() {
+                return Err(unsupported(key_span, "only one `let:` is supported"));
+            }
+        } else {
+            let key = if key == "node_ref" { "ref" } else { &key };
+            attrs.push(match value {
+                // `view!` takes any html, `mview!` needs the opt-in if it isn't a literal
+                Some(AttrValue::Value(value)) if key == "inner_html" && !value.starts_with('"') => {
+                    format!("unsafe_inner_html={value}")
+                }
+                Some(AttrValue::Value(value)) => format!("{key}={value}"),
+                // `class=("name", value)` is the same as `class:"name"=value`
+                Some(AttrValue::Tuple(name, value)) => format!("{key}:{name}={value}"),
+                None => key.to_string(),
+            });
+        }
+    };
+
+    let mut head = String::new();
+    if is_slot {
+        head.push_str("slot:");
+    }
+    head.push_str(&name);
+    for attr in &attrs {
+        head.push(' ');
+        head.push_str(attr);
+    }
+    if let Some(args) = args {
+        head.push(' ');
+        head.push_str(&args);
+    }
+
+    let children = if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+        None
+    } else {
+        Some(parse_nodes_until_close(input, &name, lt.span)?)
+    };
+    Ok(Node::Element { head, children })
+}
+
+enum AttrValue {
+    /// The value in mview syntax.
+    Value(String),
+    /// A `("name", value)` tuple, as used by `class=` and `style=`.
+    Tuple(String, String),
+}
+
+/// Parses the value after the `=` of an attribute.
+fn parse_attr_value(input: ParseStream, key: &str) -> syn::Result<AttrValue> {
+    if input.peek(syn::Lit) {
+        let lit = input.parse::<syn::Lit>()?;
+        return Ok(AttrValue::Value(lit.to_token_stream().to_string()));
+    }
+    let tt = input.parse::<TokenTree>()?;
+    match &tt {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+            Ok(AttrValue::Value(source_text(&[tt])))
+        }
+        TokenTree::Group(group)
+            if group.delimiter() == Delimiter::Parenthesis
+                && (key == "class" || key == "style") =>
+        {
+            let mut inner = group.stream().into_iter().collect::<Vec<_>>();
+            match inner.get(..2) {
+                Some([TokenTree::Literal(name), TokenTree::Punct(comma)])
+                    if comma.as_char() == ',' =>
+                {
+                    let name = name.to_string();
+                    let value = source_text(&inner.split_off(2));
+                    Ok(AttrValue::Tuple(name, format!("{{{value}}}")))
+                }
+                _ => Err(unsupported(
+                    group.span(),
+                    "expected a `(\"name\", value)` tuple",
+                )),
+            }
+        }
+        // a single variable like `id=my_id`
+        TokenTree::Ident(ident)
+            if !input.peek(Token![.]) && !input.peek(Token![::]) && !input.peek(Token![|]) =>
+        {
+            Ok(AttrValue::Value(format!("{{{ident}}}")))
+        }
+        _ => Err(unsupported(
+            tt.span(),
+            "wrap the value in braces to convert it",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::view_to_mview;
+
+    #[track_caller]
+    fn convert(input: &str) -> String { view_to_mview(input.parse().unwrap()).unwrap() }
+
+    #[track_caller]
+    fn convert_err(input: &str) -> String {
+        view_to_mview(input.parse().unwrap())
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn elements() {
+        assert_eq!(
+            convert(r#"<!DOCTYPE html><div id="main" data-index=1 checked><span>"a"</span><br><input type="text"/></div>"#),
+            "!DOCTYPE html;\ndiv id=\"main\" data-index=1 checked {\n    span { \"a\" }\n    br;\n    input type=\"text\";\n}\n"
+        );
+        assert_eq!(
+            convert("<p>Hello, world! {name}</p>"),
+            "p { \"Hello, world!\" {name} }\n"
+        );
+        assert_eq!(convert("<><b/><i></i></>"), "b;\ni {}\n");
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(
+            convert(
+                r#"<button class:active={move || on.get()} on:click={move |_| set(1)} node_ref=r class=("w-1/2", true) {..attrs}/>"#
+            ),
+            "button class:active={move || on.get()} on:click={move |_| set(1)} ref={r} class:\"w-1/2\"={true} {..attrs};\n"
+        );
+        assert_eq!(
+            convert(r#"<p inner_html="<b>hi</b>"/><p inner_html=html/>"#),
+            "p inner_html=\"<b>hi</b>\";\np unsafe_inner_html={html};\n"
+        );
+    }
+
+    #[test]
+    fn components() {
+        assert_eq!(
+            convert(
+                "<For each={move || items.get()} key={|i| i.id} let:item><Item value=item/></For>"
+            ),
+            "For each={move || items.get()} key={|i| i.id} |item| {\n    Item value={item};\n}\n"
+        );
+        assert_eq!(
+            convert(r#"<Show when={x}><Fallback slot>"no"</Fallback></Show>"#),
+            "Show when={x} {\n    slot:Fallback { \"no\" }\n}\n"
+        );
+    }
+
+    #[test]
+    fn unsupported() {
+        assert!(convert_err("<div></span>").contains("expected closing tag `</div>`"));
+        assert!(convert_err("<div>").contains("missing closing tag"));
+        assert!(convert_err("<!-- hi -->").contains("comments"));
+        assert!(convert_err("<div on:click=move |_| x()/>").contains("wrap the value in braces"));
+        assert!(convert_err("<Comp<i32>/>").contains("generic"));
+    }
+}
@@ -2,8 +2,155 @@
 //! `.unwrap_or_abort()` and related extension methods do not work.
 //!
 //! A simplified version of the extension traits have been added here.
+//!
+//! Parsing and expanding also need to work outside of a proc macro (see
+//! [`crate::expand`] and [`crate::ast::parse`]), where `proc_macro_error2`
+//! panics. The [`emit_error!`] and [`abort!`] macros and the extension methods
+//! here collect the diagnostics instead when run through
+//! [`collect_diagnostics`].
+
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+};
+
+use proc_macro2::Span;
+use proc_macro_error2::{Diagnostic, Level};
+
+/// An error emitted while collecting diagnostics.
+///
+/// The [`Diagnostic`] keeps any notes and help messages, which are lost in the
+/// [`syn::Error`].
+pub struct Collected {
+    pub error: syn::Error,
+    pub diagnostic: Diagnostic,
+    /// Whether the input that caused the error was left out of the
+    /// expansion, so that the rest of the expansion is still valid.
+    pub recovered: bool,
+}
+
+thread_local! {
+    /// Errors emitted inside of [`collect_diagnostics`], or `None` if errors
+    /// should be emitted through `proc_macro_error2`.
+    static COLLECTED: RefCell<Option<Vec<Collected>>> = const { RefCell::new(None) };
+}
+
+/// Payload used to unwind out of [`collect_diagnostics`] on an abort.
+struct Aborted;
+
+/// Like [`proc_macro_error2::emit_error!`], but works with
+/// [`collect_diagnostics`].
+macro_rules! emit_error {
+    ($span:expr, $($tts:tt)*) => {{
+        let span: ::proc_macro2::Span = $span;
+        $crate::error_ext::emit_diagnostic(
+            span,
+            ::proc_macro_error2::diagnostic!(span, ::proc_macro_error2::Level::Error, $($tts)*),
+        )
+    }};
+}
+pub(crate) use emit_error;
+
+/// Like [`emit_error!`], but marks the error as recovered from: the input
+/// that caused it is left out of the expansion, so the rest of the view can
+/// still be expanded.
+macro_rules! emit_recovered_error {
+    ($span:expr, $($tts:tt)*) => {{
+        let span: ::proc_macro2::Span = $span;
+        $crate::error_ext::emit_recovered_diagnostic(
+            span,
+            ::proc_macro_error2::diagnostic!(span, ::proc_macro_error2::Level::Error, $($tts)*),
+        )
+    }};
+}
+pub(crate) use emit_recovered_error;
+
+/// Like [`proc_macro_error2::abort!`], but works with [`collect_diagnostics`].
+macro_rules! abort {
+    ($span:expr, $($tts:tt)*) => {{
+        let span: ::proc_macro2::Span = $span;
+        $crate::error_ext::abort_diagnostic(
+            span,
+            ::proc_macro_error2::diagnostic!(span, ::proc_macro_error2::Level::Error, $($tts)*),
+        )
+    }};
+}
+pub(crate) use abort;
+
+/// Pushes the diagnostic to the collected errors, returning it back if
+/// diagnostics are not being collected.
+fn try_collect(span: Span, diagnostic: Diagnostic, recovered: bool) -> Option<Diagnostic> {
+    COLLECTED.with_borrow_mut(|collected| match collected {
+        Some(collected) => {
+            collected.push(Collected {
+                error: syn::Error::new(span, diagnostic.message()),
+                diagnostic,
+                recovered,
+            });
+            None
+        }
+        None => Some(diagnostic),
+    })
+}
+
+/// Emits the diagnostic, or collects it as an error on `span` if inside of
+/// [`collect_diagnostics`].
+pub fn emit_diagnostic(span: Span, diagnostic: Diagnostic) {
+    if let Some(diagnostic) = try_collect(span, diagnostic, false) {
+        diagnostic.emit();
+    }
+}
+
+/// Like [`emit_diagnostic`], but marks the error as recovered from.
+pub fn emit_recovered_diagnostic(span: Span, diagnostic: Diagnostic) {
+    if let Some(diagnostic) = try_collect(span, diagnostic, true) {
+        diagnostic.emit();
+    }
+}
+
+/// Emits the diagnostic and stops the macro, or stops
+/// [`collect_diagnostics`] if inside of it.
+pub fn abort_diagnostic(span: Span, diagnostic: Diagnostic) -> ! {
+    match try_collect(span, diagnostic, false) {
+        Some(diagnostic) => diagnostic.abort(),
+        None => panic::resume_unwind(Box::new(Aborted)),
+    }
+}
+
+/// Runs `f` with all emitted errors collected instead of reported, so that it
+/// can be run outside of a proc macro.
+///
+/// Any errors returned by `f` are added after the collected errors. The
+/// output of `f` is only returned if every error was recovered from (or there
+/// were no errors at all).
+pub fn collect_diagnostics<T>(f: impl FnOnce() -> syn::Result<T>) -> (Option<T>, Vec<Collected>) {
+    let previous = COLLECTED.replace(Some(Vec::new()));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let mut collected = COLLECTED.replace(previous).unwrap_or_default();
+
+    match result {
+        Ok(Ok(value)) if collected.iter().all(|c| c.recovered) => return (Some(value), collected),
+        Ok(Ok(_)) => (),
+        Ok(Err(errors)) => collected.extend(errors.into_iter().map(|error| Collected {
+            diagnostic: Diagnostic::spanned(error.span(), Level::Error, error.to_string()),
+            error,
+            recovered: false,
+        })),
+        // the error that caused the abort has already been collected
+        Err(payload) if payload.is::<Aborted>() => (),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+    (None, collected)
+}
 
-use proc_macro_error2::{abort, emit_error};
+/// Like [`collect_diagnostics`], but returns `Err` with only the errors if
+/// there were any errors at all.
+pub fn collect_errors<T>(f: impl FnOnce() -> syn::Result<T>) -> Result<T, Vec<syn::Error>> {
+    match collect_diagnostics(f) {
+        (Some(value), collected) if collected.is_empty() => Ok(value),
+        (_, collected) => Err(collected.into_iter().map(|c| c.error).collect()),
+    }
+}
 
 pub trait ResultExt {
     type Ok;
@@ -25,9 +172,11 @@ impl<T> ResultExt for Result<T, syn::Error> {
 }
 
 pub trait SynErrorExt {
-    fn emit_as_error(self);
+    /// Emits the error, marking it as recovered from with
+    /// [`emit_recovered_error!`].
+    fn emit_as_recovered_error(self);
 }
 
 impl SynErrorExt for syn::Error {
-    fn emit_as_error(self) { emit_error!(self.span(), "{}", self) }
+    fn emit_as_recovered_error(self) { emit_recovered_error!(self.span(), "{}", self) }
 }
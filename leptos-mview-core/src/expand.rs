@@ -6,24 +6,80 @@
 use std::collections::HashMap;
 
 use proc_macro2::{Span, TokenStream};
-use proc_macro_error2::emit_error;
 use quote::{quote, quote_spanned};
 use syn::{ext::IdentExt, parse_quote, parse_quote_spanned, spanned::Spanned};
 
-use crate::ast::{
-    attribute::{directive::Directive, selector::SelectorShorthand},
-    Attr, Element, KebabIdent, KebabIdentOrStr, NodeChild, Tag, Value,
+use crate::{
+    ast::{
+        attribute::{directive::Directive, selector::SelectorShorthand},
+        Attr, Element, NodeChild, StrMarker, Tag, Value,
+    },
+    error_ext::emit_error,
 };
 
 /// Functions for specific parts of an element's expansion.
 mod subroutines;
 #[allow(clippy::wildcard_imports)]
 use subroutines::*;
+/// Passing class names to a user macro, enabled by the `class-macro` feature.
+mod class_macro;
+#[cfg(all(test, feature = "class-macro"))]
+pub use class_macro::with_class_macro;
+use class_macro::{class_macro, has_macro_classes, macro_class_name};
+/// Writing the static class names to a file, enabled by the `class-manifest`
+/// feature.
+mod class_manifest;
+pub use class_manifest::record_classes;
+/// Expanding to a `leptos::view!` call, enabled by the `delegate` feature.
+mod delegate;
+pub use delegate::view_macro_tokens;
+/// Setting form state as properties, enabled by `#![form_props]`.
+mod form_props;
+pub use form_props::with_form_props;
+use form_props::{form_prop_tokens, is_form_prop};
+/// Caching static elements in templates, enabled by the `hoist` feature.
+mod hoist;
+use hoist::hoisted_element_tokens;
+/// Pre-rendering static elements to HTML, enabled by the `ssr-opt` feature.
+mod inert;
+use inert::inert_element_tokens;
+/// Logging for re-running closures, enabled by the `trace` feature.
+mod mixin;
+pub use mixin::{merge_mixin_attrs, mixin_call_tokens};
+
+mod strict;
+pub use strict::with_strict;
+use strict::{inline_handler_event, strict_lint_tokens};
+
+mod trace;
+pub use trace::trace_root_child;
+use trace::{trace_attrs, trace_children};
+/// Marking views with where they were made, enabled by the `nightly` feature.
+mod view_marker;
+pub use view_marker::view_marker;
 /// Small helper functions for converting types or emitting errors.
 mod utils;
 #[allow(clippy::wildcard_imports)]
 use utils::*;
 
+/// Imports the traits that the expansion calls methods from, like
+/// `ElementChild` for `.child(...)`, so that the macro works without
+/// `use leptos::prelude::*`.
+///
+/// The traits are imported as `_`, so no names are added to the scope of the
+/// user's expressions.
+pub fn prelude_trait_imports() -> TokenStream {
+    quote! {
+        #[allow(unused_imports)]
+        use ::leptos::prelude::{
+            AddAnyAttr as _, AriaAttributes as _, BindAttribute as _, ClassAttribute as _,
+            CustomAttribute as _, DirectiveAttribute as _, ElementChild as _,
+            GlobalAttributes as _, InnerHtmlAttribute as _, IntoAttribute as _, NodeRefAttribute as _,
+            OnAttribute as _, PropAttribute as _, StyleAttribute as _,
+        };
+    }
+}
+
 /// Converts the children into a `View::new()` token stream.
 ///
 /// Example:
@@ -45,14 +101,21 @@ pub fn root_children_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
     span: Span,
 ) -> TokenStream {
+    let children = trace_children(children, None);
+    let children = children.iter().collect::<Vec<_>>();
+    let tuple = tuple_tokens(&children, span);
     quote_spanned! { span=>
-        ::leptos::prelude::View::new((
-            #( #children, )*
-        ))
+        ::leptos::prelude::View::new(#tuple)
     }
 }
 
-// used for component children
+/// Puts the children of a component in a tuple, or returns the child by itself
+/// if there is only one.
+///
+/// The children keep their own types instead of being converted with
+/// `into_any()` or through shared helper functions: every closure and block is
+/// a distinct type anyway, and type-erasing them produces more code, not less
+/// (about 9% more LLVM IR and a 60% larger release binary for 150 components).
 pub fn children_fragment_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
     span: Span,
@@ -61,9 +124,7 @@ pub fn children_fragment_tokens<'a>(
     let has_multiple_children = children.len() > 1;
 
     if has_multiple_children {
-        quote_spanned! { span=>
-            ( #( #children, )* )
-        }
+        tuple_tokens(&children, span)
     } else {
         quote_spanned! { span=>
             #( #children )*
@@ -71,6 +132,36 @@ pub fn children_fragment_tokens<'a>(
     }
 }
 
+/// The largest tuple that is put in a fragment at once.
+///
+/// Leptos implements its view traits for tuples of up to 26 items, and for
+/// tuples of tuples, so larger fragments are split into nested tuples.
+const MAX_TUPLE_LEN: usize = 16;
+
+/// Puts all the items in a tuple, nesting them in chunks of
+/// [`MAX_TUPLE_LEN`] if there are too many.
+///
+/// Example with a max length of 2:
+/// ```ignore
+/// a b c d e
+/// ```
+///
+/// Should expand to:
+/// ```ignore
+/// (((a, b,), (c, d,),), ((e,),),)
+/// ```
+fn tuple_tokens<T: quote::ToTokens>(items: &[T], span: Span) -> TokenStream {
+    if items.len() <= MAX_TUPLE_LEN {
+        quote_spanned! { span=> ( #( #items, )* ) }
+    } else {
+        let chunks = items
+            .chunks(MAX_TUPLE_LEN)
+            .map(|chunk| tuple_tokens(chunk, span))
+            .collect::<Vec<_>>();
+        tuple_tokens(&chunks, span)
+    }
+}
+
 /// Converts an xml (like html, svg or math) element to tokens.
 ///
 /// Returns `None` if the element is not an xml element (custom component).
@@ -103,8 +194,8 @@ pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
     let tag_path = match element.tag() {
         Tag::Component(..) => return None,
         Tag::Html(ident) => quote! { ::leptos::tachys::html::element::#ident() },
-        Tag::Svg(ident) => quote! { ::leptos::tachys::svg::element::#ident() },
-        Tag::Math(ident) => quote! { ::leptos::tachys::math::element::#ident() },
+        Tag::Svg(ident) => quote! { ::leptos::tachys::svg::#ident() },
+        Tag::Math(ident) => quote! { ::leptos::tachys::mathml::#ident() },
         Tag::WebComponent(ident) => {
             let ident = ident.to_lit_str();
             let custom = syn::Ident::new("custom", ident.span());
@@ -112,8 +203,16 @@ pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
         }
     };
 
+    // where the tag was written, for tools that open the source of an element
+    let source_loc = source_loc(element.tag()).map(|loc| quote! { .attr("data-source-loc", #loc) });
+
     // add selector-style ids/classes (div.some-class #some-id)
-    let selector_methods = xml_selectors_tokens(element.selectors());
+    // all classes are merged into one string if none are dynamic
+    let static_classes = xml_static_classes_tokens(element);
+    let selector_methods = xml_selectors_tokens(element.selectors(), static_classes.is_none());
+    // static styles are merged into the `style` attribute, with the dynamic
+    // `style:` directives applied on top
+    let static_styles = xml_static_styles_tokens(element);
 
     // parse normal attributes first
     let mut attrs = TokenStream::new();
@@ -121,27 +220,184 @@ pub fn xml_to_tokens(element: &Element) -> Option<TokenStream> {
     // put directives at the end so conditional attributes like `class:` work
     // with `class="..."` attributes
     let mut directives = TokenStream::new();
+    // `dbg:` creates effects next to the element instead of adding to it
+    let mut dbg_effects = TokenStream::new();
+
+    // the groups above are called out of source order, so keep the values
+    // themselves evaluated in the order they were written
+    let traced_attrs = trace_attrs(element.attrs(), element.tag());
+    let (reordered, element_attrs) = hoist_reordered_values(&traced_attrs, |a| match a {
+        Attr::Kv(_) => 0,
+        Attr::Directive(dir) if is_plain_attr_directive(dir) => 0,
+        Attr::Directive(_) => 1,
+        Attr::Spread(_) => 2,
+    });
+
+    // `value` on a `textarea` sets its contents instead
+    let is_textarea = matches!(element.tag(), Tag::Html(ident) if ident.unraw() == "textarea");
+    let mut textarea_binding = TokenStream::new();
+    let mut textarea_child = None;
+    let mut form_prop_bindings = TokenStream::new();
 
-    for a in element.attrs().iter() {
+    for a in element_attrs.iter() {
         match a {
+            Attr::Kv(attr) if static_classes.is_some() && attr.key().repr() == "class" => (),
+            Attr::Kv(attr) if static_styles.is_some() && attr.key().repr() == "style" => (),
+            Attr::Directive(dir) if static_styles.is_some() && is_static_style_directive(dir) => (),
+            Attr::Kv(attr) if is_textarea && attr.key().repr() == "value" => {
+                if element.children().is_some() {
+                    emit_error!(
+                        attr.key().span(),
+                        "`value` sets the contents of the textarea, so it can't have children";
+                        help = "remove the children, or put the text in `value`"
+                    );
+                }
+                let (binding, prop, child) = textarea_value_tokens(attr);
+                textarea_binding.extend(binding);
+                attrs.extend(prop);
+                textarea_child = Some(child);
+            }
+            Attr::Kv(attr) if is_data_attrs(element.tag(), attr) => {
+                attrs.extend(data_attrs_tokens(attr));
+            }
+            Attr::Kv(attr) if is_form_prop(element.tag(), attr) => {
+                let (binding, methods) = form_prop_tokens(element.tag(), attr);
+                form_prop_bindings.extend(binding);
+                attrs.extend(methods);
+            }
             Attr::Kv(attr) => attrs.extend(xml_kv_attribute_tokens(attr, element.tag().kind())),
-            Attr::Directive(dir) => directives.extend(xml_directive_tokens(dir)),
+            // plain attributes are rendered in source order
+            Attr::Directive(dir) if is_plain_attr_directive(dir) => {
+                attrs.extend(xml_directive_tokens(dir, element.tag().kind()));
+            }
+            Attr::Directive(dir) if dir.dir == "dbg" => {
+                dbg_effects.extend(dbg_directive_tokens(dir));
+            }
+            Attr::Directive(dir) => {
+                directives.extend(xml_directive_tokens(dir, element.tag().kind()));
+            }
             Attr::Spread(spread) => spread_attrs.extend(xml_spread_tokens(spread)),
         }
     }
 
+    emit_error_if_dedent_in_pre(element);
+    emit_error_if_slot_children(element);
+    emit_error_if_closure_in_inline_handler(element);
+    let lints = strict_lint_tokens(element);
+    let template_content = template_content_tokens(element);
+
     let children = element
         .children()
-        .map(|children| xml_child_methods_tokens(children.node_children()));
+        .map(|children| trace_children(children.node_children(), Some(element.tag())))
+        .map(|children| xml_child_methods_tokens(children.iter()));
 
-    Some(quote! {
+    let expansion = quote! {
         #tag_path
+            #source_loc
             #attrs
+            #static_styles
             #directives
+            #static_classes
             #selector_methods
             #spread_attrs
+            #template_content
+            #textarea_child
             #children
-    })
+    };
+    let expansion = wrap_bindings(
+        &quote! { #textarea_binding #form_prop_bindings #dbg_effects #lints },
+        expansion,
+    );
+    Some(bind_reordered_values(reordered, expansion))
+}
+
+/// Emits an error for any `#[dedent]` strings inside a `pre` or `textarea`,
+/// as whitespace is significant in these elements so their text is never
+/// changed.
+///
+/// All descendants are checked, so `pre { code { #[dedent] "..." } }` is also
+/// an error. Nested `pre` or `textarea` elements are left to report their own
+/// children.
+fn emit_error_if_dedent_in_pre(element: &Element) {
+    fn dedent_spans<'a>(children: impl Iterator<Item = &'a NodeChild>, spans: &mut Vec<Span>) {
+        for child in children {
+            match child {
+                NodeChild::MarkedStr(marked) => spans.extend(marked.marker_span(StrMarker::Dedent)),
+                NodeChild::Element(element) if whitespace_sensitive_tag(element).is_none() => {
+                    if let Some(children) = element.children() {
+                        dedent_spans(children.node_children(), spans);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    let (Some(tag_name), Some(children)) = (whitespace_sensitive_tag(element), element.children())
+    else {
+        return;
+    };
+    let mut spans = Vec::new();
+    dedent_spans(children.node_children(), &mut spans);
+    for dedent_span in spans {
+        emit_error!(
+            dedent_span,
+            "`#[dedent]` cannot be used inside `{}`", tag_name;
+            help = "whitespace is significant here, so remove the `#[dedent]`"
+        );
+    }
+}
+
+/// Returns the tag name if the element is a `pre` or `textarea`.
+fn whitespace_sensitive_tag(element: &Element) -> Option<String> {
+    let Tag::Html(ident) = element.tag() else {
+        return None;
+    };
+    let tag_name = ident.unraw().to_string();
+    (tag_name == "pre" || tag_name == "textarea").then_some(tag_name)
+}
+
+/// Emits an error for any `slot:` children of an element, as only components
+/// have slots.
+///
+/// The children of a web component are put in its slots with a plain `slot`
+/// attribute instead.
+fn emit_error_if_slot_children(element: &Element) {
+    let Some(children) = element.children() else {
+        return;
+    };
+    for slot in children.slot_children() {
+        emit_error!(
+            slot.tag().span(), "slots are only supported on components";
+            help = "to put an element in a slot of a web component, use a `slot` attribute: `{} slot=\"name\"`", slot.tag()
+        );
+    }
+}
+
+/// Emits an error for a closure given to an inline handler attribute like
+/// `onclick={move |_| ...}`, as it would be turned into the string of an HTML
+/// attribute instead of being attached as an event listener.
+///
+/// A string like `onclick="..."` is still allowed, for JavaScript that should
+/// run before the page is hydrated.
+fn emit_error_if_closure_in_inline_handler(element: &Element) {
+    for attr in element.attrs().iter() {
+        let Attr::Kv(attr) = attr else {
+            continue;
+        };
+        let key = attr.key().repr();
+        if let Some(event) = inline_handler_event(key) {
+            if is_closure_value(attr.value()) {
+                emit_error!(
+                    attr.key().span(),
+                    "`{}` would set an HTML attribute; use the `on:{}` directive to attach \
+                     a Rust event handler",
+                    key,
+                    event
+                );
+            }
+        }
+    }
 }
 
 /// Transforms a component into a `TokenStream` of a leptos component view.
@@ -179,6 +435,8 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
     let Tag::Component(path) = element.tag() else {
         return None;
     };
+    // the path keeps the spans of the tag, so that rust-analyzer can map it back
+    // to the component for go to definition and renaming.
     let path = turbofishify(path.clone());
 
     // collect a bunch of info about the element attributes //
@@ -189,61 +447,51 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
     // the variables (idents) to clone before making children
     // in the form `let name = name.clone();`
     let mut clones = TokenStream::new();
+    // `dbg:` creates effects next to the component instead of adding to it
+    let mut dbg_effects = TokenStream::new();
 
     // shorthands are not supported on slots
     if IS_SLOT {
-        if let Some(first) = element.selectors().first() {
-            emit_error!(
-                first.prefix(),
-                "selector shorthands are not supported on slots"
-            )
+        if let Some(span) = element.selectors().span() {
+            emit_error!(span, "selector shorthands are not supported on slots");
         }
     } else {
-        // all the ids need to be collected together
-        // as multiple attr:id=... creates multiple `id=...` attributes on teh element
-        let mut ids = Vec::<KebabIdent>::new();
-        let mut first_pound_symbol = None;
+        emit_error_if_multiple_ids(element.selectors());
         for sel in element.selectors().iter() {
-            match sel {
+            let directive = match sel {
+                // desugar to attr:id="the-id"
                 SelectorShorthand::Id { id, pound_symbol } => {
-                    first_pound_symbol.get_or_insert(*pound_symbol);
-                    ids.push(id.clone());
+                    let id = id.repr();
+                    Directive {
+                        dir: syn::Ident::new("attr", Span::call_site()),
+                        key: parse_quote_spanned! { pound_symbol.span=> id },
+                        modifier: None,
+                        optional: None,
+                        value: Some(Value::Lit(parse_quote!(#id))),
+                    }
                 }
-                SelectorShorthand::Class { class, dot_symbol } => {
-                    // desugar to class:the-class
-                    directive_paths.push(
-                        directive_to_any_attr_path(&Directive {
-                            dir: syn::Ident::new("class", dot_symbol.span),
-                            key: KebabIdentOrStr::KebabIdent(class.clone()),
-                            modifier: None,
-                            value: None,
-                        })
-                        .expect("class directive is known"),
-                    );
-                }
-            };
-        }
-        // push all the ids as directive
-        if let Some(first_pound_symbol) = first_pound_symbol {
-            let joined_ids = ids
-                .iter()
-                .map(|ident| ident.repr())
-                .collect::<Vec<_>>()
-                .join(" ");
-            // desugar to attr:id="the-id id2 id3"
-            directive_paths.push(
-                directive_to_any_attr_path(&Directive {
-                    dir: syn::Ident::new("attr", Span::call_site()),
-                    key: parse_quote_spanned! { first_pound_symbol.span=> id },
+                // desugar to class:the-class
+                SelectorShorthand::Class { class, dot_symbol } => Directive {
+                    dir: syn::Ident::new("class", dot_symbol.span),
+                    key: class.clone(),
                     modifier: None,
-                    value: Some(Value::Lit(parse_quote!(#joined_ids))),
-                })
-                .expect("attr directive is known"),
-            );
+                    optional: None,
+                    value: None,
+                },
+            };
+            directive_paths
+                .push(directive_to_any_attr_path(&directive).expect("directive is known"));
         }
     }
 
-    element.attrs().iter().for_each(|a| match a {
+    // props are given to the builder before any directives are added
+    let traced_attrs = trace_attrs(element.attrs(), element.tag());
+    let (reordered, element_attrs) = hoist_reordered_values(&traced_attrs, |a| match a {
+        Attr::Kv(_) => 0,
+        Attr::Directive(_) | Attr::Spread(_) => 1,
+    });
+
+    element_attrs.iter().for_each(|a| match a {
         Attr::Kv(attr) => attrs.extend(component_kv_attribute_tokens(attr)),
         Attr::Spread(spread) => {
             if IS_SLOT {
@@ -252,35 +500,50 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                 directive_paths.push(component_spread_tokens(spread));
             }
         }
-        Attr::Directive(dir) => match dir.dir.to_string().as_str() {
-            // clone works on both components and slots
-            "clone" => {
-                emit_error_if_modifier(dir.modifier.as_ref());
-                clones.extend(component_clone_tokens(dir));
-            }
-            // slots support no other directives
-            other if IS_SLOT => {
-                emit_error!(dir.dir.span(), "`{}:` is not supported on slots", other);
-            }
-            _ => {
-                if let Some(path) = directive_to_any_attr_path(dir) {
-                    directive_paths.push(path);
-                } else {
-                    emit_error!(dir.dir.span(), "unknown directive");
+        Attr::Directive(dir) => {
+            match dir.dir.to_string().as_str() {
+                // clone works on both components and slots
+                "clone" => {
+                    emit_error_if_modifier(dir.modifier.as_ref());
+                    clones.extend(component_clone_tokens(dir));
+                }
+                // slots support no other directives
+                other if IS_SLOT => {
+                    emit_error!(dir.dir.span(), "`{}:` is not supported on slots", other);
+                }
+                "dbg" => dbg_effects.extend(dbg_directive_tokens(dir)),
+                "testid" => directive_paths.extend(testid_to_attr_directive(dir).map(|attr| {
+                    directive_to_any_attr_path(&attr).expect("attr directive is known")
+                })),
+                _ => {
+                    if let Some(path) = directive_to_any_attr_path(dir) {
+                        directive_paths.push(path);
+                    } else {
+                        emit_error!(dir.dir.span(), "unknown directive");
+                    }
                 }
             }
-        },
+        }
     });
 
+    let lints = strict_lint_tokens(element);
+    let bindings = quote! { #dbg_effects #lints };
+
     // convert the collected info into tokens //
 
     let children = element.children().map(|children| {
-        let mut it = children.node_children().peekable();
+        let children = trace_children(children.node_children(), Some(element.tag()));
+        let mut it = children.iter().peekable();
         // need to check that there are any element children at all,
         // as components that accept slots may not accept children.
-        it.peek()
-            .is_some()
-            .then(|| component_children_tokens(it, element.children_args(), &clones))
+        it.peek().is_some().then(|| {
+            component_children_tokens(
+                it,
+                element.children_args(),
+                element.children_span().unwrap_or_else(Span::call_site),
+                &clones,
+            )
+        })
     });
 
     let slot_children = element
@@ -293,14 +556,18 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
 
     if IS_SLOT {
         // Into is for turning a single slot into a vec![slot] if needed
-        Some(quote! {
+        let expansion = quote! {
             ::std::convert::Into::into(
                 #path::builder()
                     #attrs
                     #children
                     #build
             )
-        })
+        };
+        Some(bind_reordered_values(
+            reordered,
+            wrap_bindings(&bindings, expansion),
+        ))
     } else {
         // this whole thing needs to be spanned to avoid errors occurring at the whole
         // call site.
@@ -314,7 +581,7 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
             }
         });
 
-        Some(quote! {
+        let expansion = quote! {
             ::leptos::component::component_view(
                 &#path,
                 #component_props_builder
@@ -324,7 +591,11 @@ pub fn component_to_tokens<const IS_SLOT: bool>(element: &Element) -> Option<Tok
                     #build
             )
             #directive_paths
-        })
+        };
+        Some(bind_reordered_values(
+            reordered,
+            wrap_bindings(&bindings, expansion),
+        ))
     }
 }
 
@@ -0,0 +1,88 @@
+use std::cell::{OnceCell, RefCell};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+use crate::{
+    ast::{attribute::selector::SelectorShorthand, Attr, Element, Value},
+    error_ext::emit_error,
+};
+
+/// The environment variable naming the macro that class names are passed to.
+const CLASS_MACRO_VAR: &str = "MVIEW_CLASS_MACRO";
+
+thread_local! {
+    /// The class macro from [`CLASS_MACRO_VAR`], read on first use.
+    static CLASS_MACRO: RefCell<OnceCell<Option<syn::Path>>> = const { RefCell::new(OnceCell::new()) };
+}
+
+/// The macro that static class names are wrapped in, like `css!` or
+/// `style!`, named by the `MVIEW_CLASS_MACRO` environment variable.
+///
+/// Always `None` unless the `class-macro` feature is enabled.
+pub fn class_macro() -> Option<syn::Path> {
+    if !cfg!(feature = "class-macro") {
+        return None;
+    }
+    CLASS_MACRO.with_borrow(|class_macro| {
+        class_macro
+            .get_or_init(|| {
+                let path = std::env::var(CLASS_MACRO_VAR).ok()?;
+                syn::parse_str(&path)
+                    .inspect_err(|_| {
+                        emit_error!(
+                            Span::call_site(),
+                            "`{}` is not a valid macro path: `{}`",
+                            CLASS_MACRO_VAR,
+                            path
+                        );
+                    })
+                    .ok()
+            })
+            .clone()
+    })
+}
+
+/// Runs `f` with class names passed to `path` instead of the macro from the
+/// environment.
+#[cfg(all(test, feature = "class-macro"))]
+pub fn with_class_macro<T>(path: syn::Path, f: impl FnOnce() -> T) -> T {
+    let prev = CLASS_MACRO.replace(OnceCell::from(Some(path)));
+    let res = f();
+    CLASS_MACRO.set(prev);
+    res
+}
+
+/// Passes a static class name to the class macro, if there is one.
+///
+/// ```ignore
+/// div.card;
+/// ```
+/// Expands to (with `MVIEW_CLASS_MACRO=css`):
+/// ```ignore
+/// div().class((css!("card"), true))
+/// ```
+pub fn macro_class_name(name: &str, span: Span) -> Option<TokenStream> {
+    let path = class_macro()?;
+    let name = syn::LitStr::new(name, span);
+    Some(quote_spanned! { span=> #path!(#name) })
+}
+
+/// Whether the element has any static class names that need to be passed to
+/// the class macro, so that it can't be rendered ahead of time.
+pub fn has_macro_classes(element: &Element) -> bool {
+    if class_macro().is_none() {
+        return false;
+    }
+    element
+        .selectors()
+        .iter()
+        .any(|sel| matches!(sel, SelectorShorthand::Class { .. }))
+        || element.attrs().iter().any(|attr| match attr {
+            Attr::Kv(kv) => {
+                kv.key().repr() == "class" && matches!(kv.value(), Value::Lit(syn::Lit::Str(_)))
+            }
+            Attr::Directive(dir) => dir.dir == "class",
+            Attr::Spread(_) => false,
+        })
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::BTreeSet,
+    fs::{self, OpenOptions},
+    io::{self, Read, Write},
+    path::Path,
+};
+
+use proc_macro2::Span;
+
+use crate::{
+    ast::{
+        attribute::{directive::Directive, selector::SelectorShorthand},
+        Attr, Children, Element, Tag, Value,
+    },
+    error_ext::emit_error,
+    visit::{self, Visitor},
+};
+
+/// The environment variable naming the directory to write the manifests to.
+const CLASS_MANIFEST_VAR: &str = "MVIEW_CLASS_MANIFEST";
+
+/// Appends every static class name in the view to the class manifest of the
+/// crate being compiled, at `$MVIEW_CLASS_MANIFEST/<crate name>.classes`.
+///
+/// Does nothing unless the `class-manifest` feature is enabled and the
+/// environment variable is set.
+pub fn record_classes(children: &Children) {
+    if !cfg!(feature = "class-manifest") {
+        return;
+    }
+    let Some(dir) = std::env::var_os(CLASS_MANIFEST_VAR) else {
+        return;
+    };
+    let classes = static_classes(children);
+    if classes.is_empty() {
+        return;
+    }
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_else(|_| "unknown".into());
+    let path = Path::new(&dir).join(format!("{crate_name}.classes"));
+    if let Err(e) = append_classes(&path, &classes) {
+        emit_error!(
+            Span::call_site(),
+            "failed to write the class manifest `{}`: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// Collects the class selectors, the names in string `class` attributes, and
+/// the keys of `class:` directives.
+///
+/// Dynamic classes can't be known at compile time, so they are left out.
+fn static_classes(children: &Children) -> BTreeSet<String> {
+    #[derive(Default)]
+    struct Classes(BTreeSet<String>);
+
+    impl<'ast> Visitor<'ast> for Classes {
+        fn visit_element(&mut self, element: &'ast Element) {
+            // `class` is a prop on components
+            if !matches!(element.tag(), Tag::Component(_)) {
+                for attr in element.attrs().iter() {
+                    if let Attr::Kv(kv) = attr {
+                        if let ("class", Value::Lit(syn::Lit::Str(classes))) =
+                            (kv.key().repr(), kv.value())
+                        {
+                            self.0
+                                .extend(classes.value().split_whitespace().map(String::from));
+                        }
+                    }
+                }
+            }
+            visit::walk_element(self, element);
+        }
+
+        fn visit_selector(&mut self, selector: &'ast SelectorShorthand) {
+            if let SelectorShorthand::Class { class, .. } = selector {
+                self.0.insert(class.to_unspanned_string());
+            }
+        }
+
+        fn visit_directive(&mut self, dir: &'ast Directive) {
+            if dir.dir == "class" {
+                let key = dir.key.to_unspanned_string();
+                self.0.extend(key.split_whitespace().map(String::from));
+            }
+            visit::walk_directive(self, dir);
+        }
+    }
+
+    let mut classes = Classes::default();
+    classes.visit_children(children);
+    classes.0
+}
+
+/// Adds the classes that aren't in the manifest yet, one per line.
+///
+/// The file is locked while it is read and appended to, as the crates (and
+/// the macros in each crate) may be expanded in parallel.
+fn append_classes(path: &Path, classes: &BTreeSet<String>) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .create(true)
+        .open(path)?;
+    file.lock()?;
+
+    let mut existing = String::new();
+    file.read_to_string(&mut existing)?;
+    let existing = existing.lines().collect::<BTreeSet<_>>();
+    let new = classes
+        .iter()
+        .filter(|class| !existing.contains(class.as_str()))
+        .fold(String::new(), |mut new, class| {
+            new.push_str(class);
+            new.push('\n');
+            new
+        });
+    file.write_all(new.as_bytes())
+    // the lock is released when the file is closed
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{append_classes, static_classes};
+
+    #[test]
+    fn collects_static_classes() {
+        let children = syn::parse_str(
+            r#"div.a class="b  c" class:d={x} class={dynamic} { Comp.e class="prop"; span.a; }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            static_classes(&children),
+            BTreeSet::from(["a", "b", "c", "d", "e"].map(String::from))
+        );
+    }
+
+    #[test]
+    fn appends_new_classes() {
+        let dir = std::env::temp_dir().join(format!("mview-manifest-{}", std::process::id()));
+        let path = dir.join("krate.classes");
+        let classes = |names: &[&str]| names.iter().copied().map(String::from).collect();
+
+        append_classes(&path, &classes(&["b", "a"])).unwrap();
+        append_classes(&path, &classes(&["c", "a"])).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "a\nb\nc\n");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,427 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use syn::{ext::IdentExt, spanned::Spanned};
+
+use super::{
+    mixin::mixin_call_tokens,
+    subroutines::{dbg_directive_tokens, event_listener_handler, testid_to_attr_directive},
+    utils::{emit_error_if_modifier, emit_error_if_multiple_ids, source_loc},
+};
+use crate::{
+    ast::{
+        attribute::{directive::Directive, selector::SelectorShorthand},
+        Attr, Child, Children, Doctype, Element, KebabIdent, KebabIdentOrStr, NodeChild, Tag,
+        Value,
+    },
+    error_ext::emit_error,
+};
+
+/// Converts the macro input into an equivalent `leptos::view!` invocation,
+/// used instead of the builder expansion when the `delegate` feature is
+/// enabled.
+///
+/// # Example
+/// ```ignore
+/// div.card ref={div} {
+///     Show when=[visible.get()] { "hi" }
+/// }
+/// ```
+/// Expands to:
+/// ```ignore
+/// ::leptos::view! {
+///     <div class:card=true node_ref={div}>
+///         <Show when={move || visible.get()}>"hi"</Show>
+///     </div>
+/// }
+/// ```
+pub fn view_macro_tokens(children: &Children) -> TokenStream {
+    let nodes = children_tokens(children);
+    quote! {
+        ::leptos::view! { #nodes }
+    }
+}
+
+fn children_tokens(children: &Children) -> TokenStream {
+    children
+        .iter()
+        .map(|child| match child {
+            Child::Node(NodeChild::Value(value)) => child_value_tokens(value),
+            Child::Node(NodeChild::Element(element)) => element_tokens(element, None),
+            Child::Node(NodeChild::Doctype(doctype)) => doctype_tokens(doctype),
+            Child::Node(NodeChild::MarkedStr(marked)) => {
+                let lit = syn::LitStr::new(&marked.value(), marked.span());
+                quote! { #lit }
+            }
+            Child::Slot(slot, element) => element_tokens(element, Some(slot.span)),
+        })
+        .collect()
+}
+
+/// Converts an element, component or slot to a `<tag ...>...</tag>`.
+///
+/// Slots are marked with a `slot` attribute.
+fn element_tokens(element: &Element, slot: Option<Span>) -> TokenStream {
+    if let Some(call) = mixin_call_tokens(element, &TokenStream::new()) {
+        return call;
+    }
+    let (name, generics) = match element.tag() {
+        Tag::Html(ident) | Tag::Svg(ident) | Tag::Math(ident) => {
+            let ident = ident.unraw();
+            (quote! { #ident }, None)
+        }
+        Tag::WebComponent(ident) => (kebab_tokens(ident), None),
+        Tag::Component(path) => {
+            // generics go after the name in the opening tag only
+            let mut path = path.clone();
+            let last = path.segments.last_mut().expect("path should not be empty");
+            let generics = std::mem::replace(&mut last.arguments, syn::PathArguments::None);
+            (quote! { #path }, generics_tokens(generics))
+        }
+    };
+    let is_component = element.tag().kind() == crate::ast::TagKind::Component;
+
+    let slot = slot.map(|span| {
+        let slot = syn::Ident::new("slot", span);
+        quote! { #slot }
+    });
+    let source_loc = source_loc(element.tag())
+        .filter(|_| !is_component)
+        .map(|loc| quote! { data-source-loc=#loc });
+    let selectors = selectors_tokens(element, is_component);
+    let attrs: TokenStream = element
+        .attrs()
+        .iter()
+        .map(|attr| attr_tokens(attr, is_component))
+        .collect();
+    let bindings = element.children_args().and_then(let_binding_tokens);
+
+    let open = quote! { #name #generics #slot #source_loc #selectors #attrs #bindings };
+    let tokens = if let Some(children) = element.children() {
+        let children = children_tokens(children);
+        quote! { <#open> #children </#name> }
+    } else {
+        quote! { <#open /> }
+    };
+    dbg_wrapper_tokens(element, slot.is_some(), tokens)
+}
+
+/// Adds the effects of any `dbg:` directives on the element, which have no
+/// `view!` equivalent.
+///
+/// The element is made in its own `view!` inside a block child, after the
+/// effects. Slots have to be a direct child of their component, so `dbg:` is an
+/// error on them.
+fn dbg_wrapper_tokens(element: &Element, is_slot: bool, tokens: TokenStream) -> TokenStream {
+    let dbg_dirs = element.attrs().iter().filter_map(|attr| match attr {
+        Attr::Directive(dir) if dir.dir == "dbg" => Some(dir),
+        _ => None,
+    });
+    let effects: TokenStream = if is_slot {
+        for dir in dbg_dirs {
+            emit_error!(
+                dir.dir.span(),
+                "`dbg:` is not supported on slots in delegate mode"
+            );
+        }
+        return tokens;
+    } else {
+        dbg_dirs.map(dbg_directive_tokens).collect()
+    };
+    if effects.is_empty() {
+        tokens
+    } else {
+        quote! { { #effects ::leptos::view! { #tokens } } }
+    }
+}
+
+/// Converts the generics of a component to `<A, B>`, without a turbofish.
+///
+/// `leptos::view!` parses these like a type parameter list, so only single
+/// idents are allowed.
+fn generics_tokens(generics: syn::PathArguments) -> Option<TokenStream> {
+    let syn::PathArguments::AngleBracketed(mut generics) = generics else {
+        return None;
+    };
+    generics.colon2_token = None;
+    for arg in &generics.args {
+        let is_ident = matches!(
+            arg,
+            syn::GenericArgument::Type(syn::Type::Path(ty))
+                if ty.qself.is_none() && ty.path.get_ident().is_some()
+        );
+        if !is_ident {
+            emit_error!(
+                arg.span(), "only plain type names can be used as generics in delegate mode";
+                help = "add a type alias for this type"
+            );
+            return None;
+        }
+    }
+    Some(quote! { #generics })
+}
+
+/// Converts class and id selectors to `class:name=true` and `id="name"`.
+///
+/// Components don't take an `id` prop, so it is added as `attr:id` instead.
+fn selectors_tokens(element: &Element, is_component: bool) -> TokenStream {
+    emit_error_if_multiple_ids(element.selectors());
+    element
+        .selectors()
+        .iter()
+        .map(|selector| match selector {
+            SelectorShorthand::Class { class, dot_symbol } => {
+                let class_dir = syn::Ident::new("class", dot_symbol.span);
+                match class {
+                    KebabIdentOrStr::KebabIdent(ident) => {
+                        let ident = kebab_tokens(ident);
+                        quote! { #class_dir:#ident=true }
+                    }
+                    KebabIdentOrStr::Str(lit) => quote! { #class_dir=(#lit, true) },
+                }
+            }
+            SelectorShorthand::Id { id, pound_symbol } => {
+                let id_attr = syn::Ident::new("id", pound_symbol.span);
+                let id = id.to_lit_str();
+                if is_component {
+                    quote! { attr:#id_attr=#id }
+                } else {
+                    quote! { #id_attr=#id }
+                }
+            }
+        })
+        .collect()
+}
+
+fn attr_tokens(attr: &Attr, is_component: bool) -> TokenStream {
+    match attr {
+        Attr::Kv(kv) => {
+            let value = value_tokens(kv.value());
+            if is_component {
+                let key = kv.key().to_snake_ident().unraw();
+                quote! { #key=#value }
+            } else if kv.key().repr() == "ref" {
+                let node_ref = syn::Ident::new("node_ref", kv.key().span());
+                quote! { #node_ref=#value }
+            } else {
+                let key = kebab_tokens(kv.key());
+                quote! { #key=#value }
+            }
+        }
+        Attr::Directive(dir) => directive_tokens(dir, is_component),
+        Attr::Spread(spread) => {
+            let (dotdot, expr) = (spread.dotdot(), spread.expr());
+            quote_spanned! { spread.span()=> {#dotdot #expr} }
+        }
+    }
+}
+
+fn directive_tokens(directive: &Directive, is_component: bool) -> TokenStream {
+    let Directive {
+        dir,
+        key,
+        modifier,
+        optional,
+        value,
+    } = directive;
+    let name = dir.to_string();
+
+    if name != "on" {
+        emit_error_if_modifier(modifier.as_ref());
+    }
+
+    let value = match name.as_str() {
+        "class" | "prop" | "attr" => Some(directive.value_or_true()),
+        "clone" if !is_component => {
+            emit_error!(dir.span(), "`clone:` is not supported on elements");
+            return quote! {};
+        }
+        "testid" => {
+            return testid_to_attr_directive(directive)
+                .map(|attr| directive_tokens(&attr, is_component))
+                .unwrap_or_default();
+        }
+        // added around the element by `dbg_wrapper_tokens`
+        "dbg" => return quote! {},
+        "transition" => {
+            emit_error!(dir.span(), "`{}:` is not supported in delegate mode", dir);
+            return quote! {};
+        }
+        "style" | "on" | "use" | "bind" | "clone" => value.clone(),
+        _ => {
+            emit_error!(dir.span(), "unknown directive");
+            return quote! {};
+        }
+    };
+    let value = value.as_ref().map(|value| match value {
+        // `leptos::view!` makes literals static, which properties don't accept
+        Value::Lit(lit) if name == "prop" => quote! { {#lit} },
+        // the same closure as the builder, which only runs the handler if `Some`
+        _ if optional.is_some() => {
+            let handler = event_listener_handler(directive);
+            quote! { {#handler} }
+        }
+        _ => value_tokens(value),
+    });
+
+    let key = match key {
+        KebabIdentOrStr::KebabIdent(ident) => kebab_tokens(ident),
+        // names that aren't idents can use the tuple syntax instead
+        KebabIdentOrStr::Str(lit) if matches!(name.as_str(), "class" | "style") => {
+            return quote! { #dir=(#lit, #value) };
+        }
+        KebabIdentOrStr::Str(lit) => {
+            let Ok(ident) = lit.parse::<KebabIdent>() else {
+                emit_error!(
+                    lit.span(),
+                    "`{}:` only takes identifiers in delegate mode",
+                    dir
+                );
+                return quote! {};
+            };
+            kebab_tokens(&ident)
+        }
+    };
+    let value = value.map(|value| quote! { =#value });
+
+    // leptos only accepts `attr:` on components, elements take any attribute
+    if name == "attr" && !is_component {
+        return quote! { #key #value };
+    }
+    let modifier = modifier.as_ref().map(|modifier| quote! { :#modifier });
+    quote! { #dir:#key #modifier #value }
+}
+
+/// Converts closure children arguments `|x|` to a `let(x)` attribute.
+///
+/// Returns `None` if there are no arguments, like `||`.
+fn let_binding_tokens(args: &TokenStream) -> Option<TokenStream> {
+    let tokens = args.clone().into_iter().collect::<Vec<_>>();
+    let [TokenTree::Punct(open), inner @ .., TokenTree::Punct(_)] = tokens.as_slice() else {
+        return None;
+    };
+    if inner.is_empty() {
+        return None;
+    }
+    let let_token = syn::Ident::new("let", open.span());
+    let inner = inner.iter().cloned().collect::<TokenStream>();
+    Some(quote! { #let_token(#inner) })
+}
+
+/// Converts a value as it would be used after an `=`.
+///
+/// Brackets are closures, so they are wrapped in braces.
+fn value_tokens(value: &Value) -> TokenStream {
+    match value {
+        Value::Lit(_) | Value::Block { .. } => quote! { #value },
+        Value::Bracket { brackets, .. } => quote_spanned! { brackets.span.join()=> {#value} },
+    }
+}
+
+/// Converts a value used as a child.
+///
+/// Only string literals can be placed directly, all other values are blocks.
+fn child_value_tokens(value: &Value) -> TokenStream {
+    match value {
+        Value::Lit(syn::Lit::Str(lit)) => quote! { #lit },
+        Value::Lit(lit) => quote! { {#lit} },
+        _ => value_tokens(value),
+    }
+}
+
+/// Converts `!DOCTYPE html;` to `<!DOCTYPE html>`.
+///
+/// `leptos::view!` renders the source text of the doctype's value, so `html`
+/// is spanned to the original `html`.
+fn doctype_tokens(doctype: &Doctype) -> TokenStream {
+    doctype.emit_error_if_incomplete();
+    let html = syn::Ident::new("html", doctype.html_span());
+    quote! { <!DOCTYPE #html> }
+}
+
+/// Converts a [`KebabIdent`] to the ident and `-` tokens that it was made of,
+/// like how `leptos::view!` names are written.
+fn kebab_tokens(ident: &KebabIdent) -> TokenStream {
+    let tokens = ident
+        .repr()
+        .parse::<TokenStream>()
+        .expect("kebab ident should be valid tokens");
+    // only the first `-` has a span, the others use the previous section's span
+    let mut spans = ident.spans();
+    let mut span = spans.next().expect("kebab ident should not be empty");
+    tokens
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut token)| {
+            let is_dash = matches!(&token, TokenTree::Punct(p) if p.as_char() == '-');
+            if i > 0 && !is_dash {
+                span = spans.next().unwrap_or(span);
+            }
+            token.set_span(span);
+            token
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::view_macro_tokens;
+    use crate::ast::Children;
+
+    /// Compares the expansion, ignoring whitespace as the spacing of
+    /// punctuation like `</` is different between `quote!` and parsing.
+    fn expand_eq(input: &str, view: &str) {
+        let children: Children = syn::parse_str(input).unwrap();
+        let expansion = view_macro_tokens(&children).to_string();
+        let expected = format!("::leptos::view! {{ {view} }}");
+        let strip = |s: &str| s.split_whitespace().collect::<String>();
+        assert_eq!(strip(&expansion), strip(&expected));
+    }
+
+    #[test]
+    fn elements() {
+        expand_eq(
+            r#"div.card #main data-index=1 !hidden ref={r} { "a" span("b") br; }"#,
+            r#"<div class:card=true id="main" data-index=1 hidden=false node_ref={r}>
+                "a" <span>"b"</span> <br />
+            </div>"#,
+        );
+        expand_eq(
+            r#"my-element."a/b" { !DOCTYPE html; }"#,
+            r#"<my-element class=("a/b", true)> <!DOCTYPE html> </my-element>"#,
+        );
+    }
+
+    #[test]
+    fn values() {
+        expand_eq(
+            r#"p title=[t.get()] label=f["{}", 1] { {x} [y.get()] #[dedent] " z" }"#,
+            r#"<p title={move || {t.get()}} label={move || ::std::format!("{}", 1)}>
+                {x} {move || {y.get()}} "z"
+            </p>"#,
+        );
+    }
+
+    #[test]
+    fn directives() {
+        expand_eq(
+            r#"button class:active=[a.get()] style:color="red" on:click:undelegated={f}
+                use:tooltip attr:aria-label="x" prop:value={v} bind:checked={c};"#,
+            r#"<button class:active={move || {a.get()}} style:color="red" on:click:undelegated={f}
+                use:tooltip aria-label="x" prop:value={v} bind:checked={c} />"#,
+        );
+    }
+
+    #[test]
+    fn components() {
+        expand_eq(
+            "For each=[items()] key={|i| *i} clone:a |i| { span({i}) }",
+            "<For each={move || {items()}} key={|i| *i} clone:a let(i)>
+                <span>{i}</span>
+            </For>",
+        );
+        expand_eq(
+            r#"Tabs.wide #t attr:title="x" { slot:Tab label="a"; }"#,
+            r#"<Tabs class:wide=true attr:id="t" attr:title="x"> <Tab slot label="a" /> </Tabs>"#,
+        );
+    }
+}
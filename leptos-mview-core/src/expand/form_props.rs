@@ -0,0 +1,77 @@
+use std::cell::Cell;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+
+use super::utils::bind_value;
+use crate::ast::{attribute::kv::KvAttr, Tag, Value};
+
+thread_local! {
+    /// Whether `#![form_props]` was given to the macro currently expanding.
+    static FORM_PROPS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with form state attributes expanded as properties if `enabled`.
+pub fn with_form_props<T>(enabled: bool, f: impl FnOnce() -> T) -> T {
+    let prev = FORM_PROPS.replace(enabled);
+    let res = f();
+    FORM_PROPS.set(prev);
+    res
+}
+
+/// Whether the attribute holds the current state of a form control, and
+/// should also be set as a property.
+///
+/// Always `false` unless `#![form_props]` was given.
+pub fn is_form_prop(tag: &Tag, attr: &KvAttr) -> bool {
+    if !FORM_PROPS.get() {
+        return false;
+    }
+    let Tag::Html(ident) = tag else {
+        return false;
+    };
+    matches!(
+        (ident.unraw().to_string().as_str(), attr.key().repr()),
+        ("input", "checked" | "value") | ("option", "selected" | "value") | ("select", "value")
+    )
+}
+
+/// Sets a form state attribute as a property as well, so that it keeps
+/// updating after the user has changed the control.
+///
+/// Returns the `let` binding for the value, and the methods to call on the
+/// element.
+///
+/// ```ignore
+/// input type="checkbox" checked={checked};
+/// ```
+/// Expands to:
+/// ```ignore
+/// {
+///     let value = {checked};
+///     input().r#type("checkbox").checked(value.clone()).prop("checked", value)
+/// }
+/// ```
+///
+/// The attribute is still added for the initial state when rendered to HTML.
+/// A literal never changes, so it is only added as an attribute. `<select>`
+/// has no `value` attribute, so it only gets the property.
+pub fn form_prop_tokens(tag: &Tag, attr: &KvAttr) -> (TokenStream, TokenStream) {
+    let key = attr.key();
+    let value = attr.value();
+    let method = key.to_snake_ident();
+    if let Value::Lit(_) = value {
+        return (quote! {}, quote! { .#method(#value) });
+    }
+    let prop = syn::Ident::new("prop", key.span());
+    let name = key.repr();
+    if matches!(tag, Tag::Html(ident) if ident.unraw() == "select") {
+        return (quote! {}, quote! { .#prop(#name, #value) });
+    }
+    let (binding, var) = bind_value(value, "__mview_form_prop");
+    (
+        binding,
+        quote! { .#method(::core::clone::Clone::clone(&#var)).#prop(#name, #var) },
+    )
+}
@@ -0,0 +1,116 @@
+use std::cell::Cell;
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+
+use super::{has_macro_classes, xml_to_tokens};
+use crate::ast::{Attr, Element, NodeChild, Tag, Value};
+
+thread_local! {
+    /// Whether the contents of a hoisted element are currently being expanded,
+    /// so that they are not hoisted again.
+    static HOISTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Wraps an element with only static attributes and children in a
+/// `ViewTemplate`, which builds the DOM nodes into a `<template>` once and
+/// clones it every time the view is built afterwards.
+///
+/// Returns `None` if the element or any of its children are dynamic. Does
+/// nothing unless the `hoist` feature is enabled.
+pub fn hoisted_element_tokens(element: &Element) -> Option<TokenStream> {
+    if !cfg!(feature = "hoist") || HOISTING.get() {
+        return None;
+    }
+    // the template is parsed as HTML, so other elements have to be inside an
+    // `<svg>` or `<math>` to get the right namespace.
+    match element.tag() {
+        Tag::Html(_) => (),
+        Tag::Svg(ident) | Tag::Math(ident)
+            if matches!(ident.unraw().to_string().as_str(), "svg" | "math") => {}
+        _ => return None,
+    }
+    if !is_static(element) {
+        return None;
+    }
+
+    HOISTING.set(true);
+    let view = xml_to_tokens(element);
+    HOISTING.set(false);
+
+    Some(quote_spanned! { element.tag().span()=>
+        ::leptos::tachys::view::template::ViewTemplate::new(#view)
+    })
+}
+
+/// Whether the contents of a hoisted element are being expanded.
+///
+/// Other optimizations should not be used in this case, as everything in the
+/// template needs to implement `ToTemplate`.
+pub fn is_hoisting() -> bool { HOISTING.get() }
+
+/// Whether the element only has literal attributes and string or static
+/// element children.
+fn is_static(element: &Element) -> bool {
+    // the class names are only known after the class macro has run, and the
+    // attributes of groups after the group's macro
+    if matches!(element.tag(), Tag::Component(_))
+        || has_macro_classes(element)
+        || element.mixin().is_some()
+    {
+        return false;
+    }
+    let static_attrs = element.attrs().iter().all(|attr| {
+        let Attr::Kv(kv) = attr else {
+            return false;
+        };
+        let key = kv.key().repr();
+        // snake_case keys like `inner_html` are not attributes in the template
+        !key.contains('_') && key != "ref" && matches!(kv.value(), Value::Lit(_))
+    });
+    if !static_attrs {
+        return false;
+    }
+
+    element.children().is_none_or(|children| {
+        children.slot_children().next().is_none()
+            && children.node_children().all(|child| match child {
+                NodeChild::Value(Value::Lit(syn::Lit::Str(_))) | NodeChild::MarkedStr(_) => true,
+                NodeChild::Element(child) => is_static(child),
+                _ => false,
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_static;
+    use crate::ast::Element;
+
+    fn check(input: &str) -> bool {
+        let element: Element = syn::parse_str(input).unwrap();
+        is_static(&element)
+    }
+
+    #[test]
+    fn static_elements() {
+        assert!(check(
+            r#"footer.main #end data-x=1 { "hi " strong("there") br; }"#
+        ));
+        assert!(check(
+            r#"svg viewBox="0 0 24 24" { path d="M0 0h24v24H0z"; }"#
+        ));
+    }
+
+    #[test]
+    fn dynamic_elements() {
+        assert!(!check("div class={c};"));
+        assert!(!check("div { span([count()]) }"));
+        assert!(!check("div on:click={f};"));
+        assert!(!check("div ref={r};"));
+        assert!(!check(r#"div inner_html="<b>hi</b>";"#));
+        assert!(!check("div { Component; }"));
+        assert!(!check("div {..attrs};"));
+    }
+}
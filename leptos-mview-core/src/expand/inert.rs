@@ -0,0 +1,227 @@
+use std::cell::Cell;
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+use syn::ext::IdentExt;
+
+use super::{
+    has_macro_classes,
+    hoist::is_hoisting,
+    utils::{is_enumerated_attr, source_loc},
+    xml_to_tokens,
+};
+use crate::ast::{attribute::selector::SelectorShorthand, Attr, Element, NodeChild, Tag, Value};
+
+/// Elements that have no children or closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+thread_local! {
+    /// Whether elements are currently being expanded for type-checking only,
+    /// inside of an inert element.
+    static CHECKING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Pre-renders an element with only static attributes and children to an
+/// `InertElement`, which is made from a HTML string created at compile time.
+///
+/// The regular expansion is still placed in a closure that is never called, so
+/// that any mistakes like unknown attributes are still type errors.
+///
+/// Returns `None` if the element or any of its children are dynamic. Does
+/// nothing unless the `ssr-opt` feature is enabled.
+pub fn inert_element_tokens(element: &Element) -> Option<TokenStream> {
+    if !cfg!(feature = "ssr-opt") || CHECKING.get() || is_hoisting() {
+        return None;
+    }
+    // nothing to gain from an element with nothing in it
+    let is_empty = element.selectors().is_empty()
+        && element.attrs().is_empty()
+        && element
+            .children()
+            .is_none_or(|children| children.is_empty());
+    if is_empty {
+        return None;
+    }
+
+    let mut html = String::new();
+    render_element(element, &mut html)?;
+
+    CHECKING.set(true);
+    let check = xml_to_tokens(element);
+    CHECKING.set(false);
+
+    Some(quote_spanned! { element.tag().span()=>
+        {
+            let _ = || #check;
+            ::leptos::tachys::html::InertElement::new(#html)
+        }
+    })
+}
+
+/// Writes the HTML of a static element to `html`.
+///
+/// Returns `None` if anything about the element is not static.
+fn render_element(element: &Element, html: &mut String) -> Option<()> {
+    let Tag::Html(ident) = element.tag() else {
+        return None;
+    };
+    let tag = ident.unraw().to_string();
+    // the text in these is not escaped, so leave them to leptos
+    if matches!(tag.as_str(), "script" | "style" | "textarea") {
+        return None;
+    }
+    // the class names are only known after the class macro has run, and the
+    // attributes of groups after the group's macro
+    if has_macro_classes(element) || element.mixin().is_some() {
+        return None;
+    }
+
+    html.push('<');
+    html.push_str(&tag);
+    if let Some(loc) = source_loc(element.tag()) {
+        push_attr(html, "data-source-loc", Some(&loc));
+    }
+
+    let mut classes = Vec::new();
+    let mut id = None;
+    for selector in element.selectors().iter() {
+        match selector {
+            SelectorShorthand::Class { class, .. } => classes.push(class.to_unspanned_string()),
+            SelectorShorthand::Id { id: ident, .. } => {
+                if id.replace(ident.repr().to_string()).is_some() {
+                    return None;
+                }
+            }
+        }
+    }
+
+    for attr in element.attrs().iter() {
+        let Attr::Kv(kv) = attr else {
+            return None;
+        };
+        let key = kv.key().repr();
+        // leptos converts snake_case keys, and `ref` needs a `NodeRef`
+        if key.contains('_') || key == "ref" {
+            return None;
+        }
+        let Value::Lit(lit) = kv.value() else {
+            return None;
+        };
+        match (key, lit) {
+            // `class` is merged with the selectors, which are added after it
+            ("class", syn::Lit::Str(value)) => classes.insert(0, value.value()),
+            // leave it to leptos to decide which id to use
+            ("id", _) if id.is_some() => return None,
+            (_, syn::Lit::Str(value)) => push_attr(html, key, Some(&value.value())),
+            (_, syn::Lit::Bool(value)) if is_enumerated_attr(key) => {
+                push_attr(html, key, Some(if value.value { "true" } else { "false" }));
+            }
+            (_, syn::Lit::Bool(value)) => {
+                if value.value {
+                    push_attr(html, key, None);
+                }
+            }
+            _ => return None,
+        }
+    }
+    // leptos puts the classes after all other attributes
+    if let Some(id) = id {
+        push_attr(html, "id", Some(&id));
+    }
+    if !classes.is_empty() {
+        push_attr(html, "class", Some(&classes.join(" ")));
+    }
+    html.push('>');
+
+    let is_void = VOID_ELEMENTS.contains(&tag.as_str());
+    if let Some(children) = element.children() {
+        if is_void || children.slot_children().next().is_some() {
+            return None;
+        }
+        for child in children.node_children() {
+            match child {
+                // text is escaped like leptos does for `&str` children: `&`, `<` and
+                // `>` only, quotes are left as-is
+                NodeChild::Value(Value::Lit(syn::Lit::Str(text))) => {
+                    html.push_str(&html_escape::encode_text(&text.value()));
+                }
+                NodeChild::MarkedStr(marked) => {
+                    html.push_str(&html_escape::encode_text(&marked.try_value().ok()?));
+                }
+                NodeChild::Element(child) => render_element(child, html)?,
+                _ => return None,
+            }
+        }
+    }
+    if !is_void {
+        html.push_str("</");
+        html.push_str(&tag);
+        html.push('>');
+    }
+    Some(())
+}
+
+/// Writes ` key="value"`, or ` key` if there is no value.
+///
+/// The value is escaped like leptos does for attributes, which also escapes
+/// `"`.
+fn push_attr(html: &mut String, key: &str, value: Option<&str>) {
+    html.push(' ');
+    html.push_str(key);
+    if let Some(value) = value {
+        html.push_str("=\"");
+        html.push_str(&html_escape::encode_double_quoted_attribute(value));
+        html.push('"');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render_element;
+    use crate::ast::Element;
+
+    fn render(input: &str) -> Option<String> {
+        let element: Element = syn::parse_str(input).unwrap();
+        let mut html = String::new();
+        render_element(&element, &mut html).map(|()| html)
+    }
+
+    #[test]
+    fn static_elements() {
+        assert_eq!(
+            render(r#"div.b #main class="a" data-x="1" hidden { "hi " strong("there") br; }"#)
+                .unwrap(),
+            r#"<div data-x="1" hidden id="main" class="a b">hi <strong>there</strong><br></div>"#
+        );
+        assert_eq!(
+            render("input type=\"text\" !disabled;").unwrap(),
+            r#"<input type="text">"#
+        );
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(
+            render(r#"p title="&quot;<>" { "<b> & </b>" }"#).unwrap(),
+            r#"<p title="&amp;quot;&lt;&gt;">&lt;b&gt; &amp; &lt;/b&gt;</p>"#
+        );
+        // quotes only need escaping in attributes
+        assert_eq!(
+            render(r#"p title="a < b & \"c\"" { "a < b & \"c\"" }"#).unwrap(),
+            r#"<p title="a &lt; b &amp; &quot;c&quot;">a &lt; b &amp; "c"</p>"#
+        );
+    }
+
+    #[test]
+    fn dynamic_elements() {
+        assert!(render("div class={c};").is_none());
+        assert!(render("div { span([count()]) }").is_none());
+        assert!(render("div on:click={f};").is_none());
+        assert!(render("div { Component; }").is_none());
+        assert!(render("script { \"let a = 1;\" }").is_none());
+        assert!(render("svg.icon;").is_none());
+    }
+}
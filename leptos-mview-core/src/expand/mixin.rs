@@ -0,0 +1,72 @@
+//! Attribute groups (`@@clickable`) from `define_mview_attrs!`.
+//!
+//! An element with a group expands to a call to the group's macro, with the
+//! element's tokens (without the group) as the input. The macro passes its
+//! attributes and the element on to `__mixin!`, which either calls the next
+//! group's macro or, once all groups are resolved, adds the attributes to the
+//! element and expands it.
+
+use std::collections::HashSet;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Token;
+
+use crate::ast::{attribute::selector::SelectorShorthand, Attr, Attrs, Element};
+
+/// Expands an element with an attribute group to a call to the group's
+/// macro.
+///
+/// `groups` are the attributes of the groups that have been resolved
+/// already, each in brackets. Returns `None` if the element has no groups
+/// left.
+pub fn mixin_call_tokens(element: &Element, groups: &TokenStream) -> Option<TokenStream> {
+    let (mixin, tokens) = element.mixin()?;
+    let mut path = mixin.path().clone();
+    // an unknown group is reported at the `@@`
+    let last = path.segments.last_mut().expect("path should not be empty");
+    last.ident.set_span(mixin.at_span());
+    let bang = <Token![!]>::default();
+    let call = quote! { #path #bang { #groups #tokens } };
+    // `view!` only takes expressions in a block
+    Some(if cfg!(feature = "delegate") {
+        quote! { {#call} }
+    } else {
+        call
+    })
+}
+
+/// Adds the attributes of each group to the element, in the order the groups
+/// were written.
+///
+/// Attributes that are written on the element win over the groups, as do
+/// earlier groups over later ones. Spreads are always added.
+pub fn merge_mixin_attrs(element: &mut Element, groups: Vec<Attrs>) {
+    let mut seen: HashSet<String> = element.attrs().iter().filter_map(attr_key).collect();
+    if element
+        .selectors()
+        .iter()
+        .any(|selector| matches!(selector, SelectorShorthand::Id { .. }))
+    {
+        seen.insert("id".to_owned());
+    }
+
+    let mut merged = Attrs::default();
+    for attr in groups.into_iter().flat_map(|attrs| attrs.to_vec()) {
+        if attr_key(&attr).is_none_or(|key| seen.insert(key)) {
+            merged.push(attr);
+        }
+    }
+    merged.extend(std::mem::take(element.attrs_mut()));
+    *element.attrs_mut() = merged;
+}
+
+/// Returns the name that two conflicting attributes share, like `role` or
+/// `on:click`.
+fn attr_key(attr: &Attr) -> Option<String> {
+    match attr {
+        Attr::Kv(kv) => Some(kv.key().repr().to_owned()),
+        Attr::Directive(dir) => Some(format!("{}:{}", dir.dir(), dir.key().to_unspanned_string())),
+        Attr::Spread(_) => None,
+    }
+}
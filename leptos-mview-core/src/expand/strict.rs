@@ -0,0 +1,222 @@
+use std::cell::Cell;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+use crate::{
+    ast::{
+        attribute::{directive::Directive, kv::KvAttr},
+        Attr, Element, TagKind, Value,
+    },
+    error_ext::emit_error,
+    expand::utils::is_closure_value,
+    span,
+};
+
+thread_local! {
+    /// Whether `#![strict]` was given to the macro currently expanding.
+    static STRICT: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `f` with the attribute name lints enabled if `enabled`.
+///
+/// The lints are always enabled with the `"strict"` feature.
+pub fn with_strict<T>(enabled: bool, f: impl FnOnce() -> T) -> T {
+    let prev = STRICT.replace(enabled);
+    let res = f();
+    STRICT.set(prev);
+    res
+}
+
+/// Event names that are likely to be written as an inline handler attribute,
+/// like `onclick`.
+const EVENTS: &[&str] = &[
+    "abort",
+    "animationend",
+    "animationiteration",
+    "animationstart",
+    "auxclick",
+    "beforeinput",
+    "blur",
+    "change",
+    "click",
+    "close",
+    "contextmenu",
+    "copy",
+    "cut",
+    "dblclick",
+    "drag",
+    "dragend",
+    "dragenter",
+    "dragleave",
+    "dragover",
+    "dragstart",
+    "drop",
+    "error",
+    "focus",
+    "focusin",
+    "focusout",
+    "input",
+    "invalid",
+    "keydown",
+    "keypress",
+    "keyup",
+    "load",
+    "mousedown",
+    "mouseenter",
+    "mouseleave",
+    "mousemove",
+    "mouseout",
+    "mouseover",
+    "mouseup",
+    "paste",
+    "pointercancel",
+    "pointerdown",
+    "pointerenter",
+    "pointerleave",
+    "pointermove",
+    "pointerout",
+    "pointerover",
+    "pointerup",
+    "reset",
+    "resize",
+    "scroll",
+    "scrollend",
+    "select",
+    "submit",
+    "toggle",
+    "touchcancel",
+    "touchend",
+    "touchmove",
+    "touchstart",
+    "transitionend",
+    "wheel",
+];
+
+/// Returns the event of an inline handler attribute like `onclick`, or `None`
+/// if the key isn't `on` followed by a known event.
+pub fn inline_handler_event(key: &str) -> Option<&str> {
+    key.strip_prefix("on")
+        .filter(|event| EVENTS.contains(event))
+}
+
+/// Attributes with an underscore that are handled by the macro itself.
+const SNAKE_CASE_ALLOWED: &[&str] = &["inner_html", "unsafe_inner_html", "node_ref"];
+
+/// Checks the style of the element's attribute names, if `#![strict]` was
+/// given or the `"strict"` feature is enabled.
+///
+/// - `onclick=...` on an element is an error, as it is almost always meant to
+///   be an `on:click` handler.
+/// - `snake_case` keys on an element are warned about, as HTML attributes are
+///   kebab-case.
+/// - `kebab-case` keys on a component are warned about, as props are
+///   `snake_case` and are only converted by the macro.
+/// - `class:` and `style:` directives with a literal value are warned about, as
+///   the value can never change.
+///
+/// Returns the statements that emit the warnings, to be put next to the
+/// element. Nothing is checked on an element marked with `#[allow(strict)]`.
+pub fn strict_lint_tokens(element: &Element) -> TokenStream {
+    if !(cfg!(feature = "strict") || STRICT.get()) || element.allows_strict() {
+        return TokenStream::new();
+    }
+    let is_component = element.tag().kind() == TagKind::Component;
+    let mut warnings = TokenStream::new();
+    for attr in element.attrs().iter() {
+        match attr {
+            Attr::Kv(attr) if is_component => warnings.extend(component_key_warning(attr)),
+            Attr::Kv(attr) => warnings.extend(element_key_lint(attr)),
+            Attr::Directive(dir) => warnings.extend(constant_directive_warning(dir)),
+            Attr::Spread(_) => (),
+        }
+    }
+    warnings
+}
+
+/// Errors on inline handler keys and warns on `snake_case` keys of an
+/// element.
+fn element_key_lint(attr: &KvAttr) -> Option<TokenStream> {
+    let key = attr.key().repr();
+    let span = attr.key().span();
+    if let Some(event) = inline_handler_event(key) {
+        // closures are always an error, see `emit_error_if_closure_in_inline_handler`
+        if is_closure_value(attr.value()) {
+            return None;
+        }
+        emit_error!(
+            span, "`{}` is an inline JavaScript handler, not an event listener", key;
+            help = "use the `on:` directive: `on:{}={{move |_| ...}}`", event
+        );
+        return None;
+    }
+    if key.contains('_') && !SNAKE_CASE_ALLOWED.contains(&key) {
+        let kebab = key.trim_matches('_').replace('_', "-");
+        return Some(warning(
+            span,
+            &format!("HTML attributes are kebab-case: use `{kebab}` instead of `{key}`"),
+        ));
+    }
+    None
+}
+
+/// Warns on `kebab-case` keys of a component.
+fn component_key_warning(attr: &KvAttr) -> Option<TokenStream> {
+    let key = attr.key().repr();
+    key.contains('-').then(|| {
+        let snake = key.replace('-', "_");
+        warning(
+            attr.key().span(),
+            &format!("component props are snake_case: use `{snake}` instead of `{key}`"),
+        )
+    })
+}
+
+/// Warns on a `class:` or `style:` directive with a literal value, suggesting
+/// the static form that doesn't need to be updated.
+///
+/// `{true}` is treated the same as `true`. Brackets are never warned about, as
+/// they are written to be reactive.
+fn constant_directive_warning(dir: &Directive) -> Option<TokenStream> {
+    let value = dir.value()?;
+    let lit = match value {
+        Value::Lit(lit) => lit.clone(),
+        Value::Block { tokens, .. } => syn::parse2(tokens.clone()).ok()?,
+        Value::Bracket { .. } => return None,
+    };
+    let key = dir.key().to_unspanned_string();
+    let message = match (dir.dir().to_string().as_str(), lit) {
+        ("class", syn::Lit::Bool(b)) if b.value => {
+            format!("`class:{key}` is always added: use the selector shorthand `.{key}` instead")
+        }
+        ("class", syn::Lit::Bool(_)) => {
+            format!("`class:{key}` is never added: remove it")
+        }
+        ("style", syn::Lit::Str(style)) => format!(
+            "`style:{key}` never changes: put it in the `style` attribute instead, like \
+             `style=\"{key}: {}\"`",
+            style.value()
+        ),
+        _ => return None,
+    };
+    Some(warning(
+        span::join(dir.dir().span(), value.span()),
+        &message,
+    ))
+}
+
+/// Emits `message` as a deprecation warning at `span`, as proc macros can't
+/// emit warnings on stable.
+fn warning(span: Span, message: &str) -> TokenStream {
+    let marker = syn::Ident::new("strict", span);
+    let note = format!("{message} (`#[allow(strict)]` on the element to silence this)");
+    // each warning is in its own block, so that the markers don't clash
+    quote_spanned! { span=>
+        {
+            #[allow(non_camel_case_types)]
+            #[deprecated(note = #note)]
+            struct #marker;
+            let _ = #marker;
+        }
+    }
+}
@@ -1,6 +1,5 @@
 use proc_macro2::{Span, TokenStream};
-use proc_macro_error2::emit_error;
-use quote::{quote, quote_spanned};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, spanned::Spanned};
 
 use crate::{
@@ -11,9 +10,15 @@ use crate::{
             selector::{SelectorShorthand, SelectorShorthands},
             spread_attrs::SpreadAttr,
         },
-        KebabIdentOrStr, NodeChild, TagKind, Value,
+        Attr, Element, KebabIdentOrStr, NodeChild, Tag, TagKind, Value,
     },
-    expand::{children_fragment_tokens, emit_error_if_modifier, utils},
+    error_ext::emit_error,
+    expand::{
+        check_ref_value, children_fragment_tokens, class_macro, emit_error_if_modifier,
+        emit_error_if_multiple_ids, hoisted_element_tokens, inert_element_tokens,
+        inline_handler_event, macro_class_name, utils,
+    },
+    span,
 };
 
 ////////////////////////////////////////////////////////////////
@@ -33,6 +38,7 @@ pub(super) fn use_directive_fn_value(u: &Directive) -> (syn::Ident, TokenStream)
         dir: use_token,
         key,
         modifier,
+        optional: _,
         value,
     } = u;
     assert_eq!(use_token, "use", "directive should be `use:`");
@@ -40,7 +46,14 @@ pub(super) fn use_directive_fn_value(u: &Directive) -> (syn::Ident, TokenStream)
     emit_error_if_modifier(modifier.as_ref());
 
     let value = value.as_ref().map_or_else(
-        || quote_spanned! {directive_fn.span()=> ().into() },
+        // the conversion may be `() -> ()`, don't lint in user code
+        || {
+            quote_spanned! {directive_fn.span()=> {
+                #[allow(clippy::useless_conversion, clippy::let_unit_value)]
+                let param = ().into();
+                param
+            }}
+        },
         |val| quote! { ::std::convert::Into::into(#val) },
     );
     (directive_fn, value)
@@ -51,6 +64,7 @@ pub(super) fn event_listener_event_path(dir: &Directive) -> TokenStream {
         dir,
         key,
         modifier,
+        optional: _,
         value: _,
     } = dir;
     assert_eq!(dir, "on", "directive should be `on:`");
@@ -82,6 +96,155 @@ pub(super) fn event_listener_event_path(dir: &Directive) -> TokenStream {
     }
 }
 
+/// Gets the handler of an `on:` directive.
+///
+/// If the value is optional (`on:click=?{handler}`), the handler is an
+/// `Option` of either a `Callback` or a closure, and it is wrapped in a closure
+/// that only runs the handler if it is `Some`.
+pub(super) fn event_listener_handler(dir: &Directive) -> TokenStream {
+    let value = &dir.value;
+    let Some(question) = dir.optional else {
+        return quote! { #value };
+    };
+
+    // `Callback` only implements `Callable`, so a local trait is needed to run
+    // both it and closures.
+    let handler_trait = syn::Ident::new("__MviewEventHandler", Span::mixed_site());
+    let handler = syn::Ident::new("handler", Span::mixed_site());
+    let ev = syn::Ident::new("ev", Span::mixed_site());
+    quote_spanned! { question.span=>
+        {
+            trait #handler_trait<E> {
+                fn run(&mut self, ev: E);
+            }
+            impl<E: 'static> #handler_trait<E> for ::leptos::prelude::Callback<E> {
+                fn run(&mut self, ev: E) { ::leptos::prelude::Callable::run(self, ev) }
+            }
+            impl<E, F: FnMut(E)> #handler_trait<E> for F {
+                fn run(&mut self, ev: E) { self(ev) }
+            }
+
+            let mut #handler = #value;
+            move |#ev| {
+                if let ::std::option::Option::Some(#handler) = &mut #handler {
+                    #handler_trait::run(#handler, #ev);
+                }
+            }
+        }
+    }
+}
+
+/// Converts a `dbg:label={value}` to an effect that logs the value whenever
+/// it changes.
+///
+/// Signals and closures (including brackets) are called inside the effect so
+/// that they are tracked, other values are logged once. Only included in debug
+/// builds.
+///
+/// **Panics** if the provided directive is not `dbg:`.
+pub(super) fn dbg_directive_tokens(dir: &Directive) -> TokenStream {
+    assert_eq!(dir.dir, "dbg", "directive should be `dbg:`");
+    emit_error_if_modifier(dir.modifier.as_ref());
+
+    let label = dir.key.to_unspanned_string();
+    let Some(value) = &dir.value else {
+        emit_error!(
+            dir.dir.span(), "`dbg:` requires a value";
+            help = "add a value to log, like `dbg:count={count}`"
+        );
+        return TokenStream::new();
+    };
+
+    // autoref specialization: signals are preferred over closures, which are
+    // preferred over any other `Debug` value.
+    let wrapper = syn::Ident::new("__MviewDbg", Span::mixed_site());
+    let signal_trait = syn::Ident::new("__MviewDbgSignal", Span::mixed_site());
+    let fn_trait = syn::Ident::new("__MviewDbgFn", Span::mixed_site());
+    let value_trait = syn::Ident::new("__MviewDbgValue", Span::mixed_site());
+    let value_var = syn::Ident::new("value", Span::mixed_site());
+    quote_spanned! { dir.dir.span()=>
+        #[cfg(debug_assertions)]
+        {
+            struct #wrapper<T>(T);
+            trait #signal_trait {
+                fn __mview_dbg(&self) -> ::std::string::String;
+            }
+            impl<S> #signal_trait for &&#wrapper<S>
+            where
+                S: ::leptos::prelude::Get,
+                S::Value: ::std::fmt::Debug,
+            {
+                fn __mview_dbg(&self) -> ::std::string::String {
+                    ::std::format!("{:?}", ::leptos::prelude::Get::get(&self.0))
+                }
+            }
+            trait #fn_trait {
+                fn __mview_dbg(&self) -> ::std::string::String;
+            }
+            impl<F, T> #fn_trait for &#wrapper<F>
+            where
+                F: ::std::ops::Fn() -> T,
+                T: ::std::fmt::Debug,
+            {
+                fn __mview_dbg(&self) -> ::std::string::String {
+                    ::std::format!("{:?}", (self.0)())
+                }
+            }
+            trait #value_trait {
+                fn __mview_dbg(&self) -> ::std::string::String;
+            }
+            impl<T: ::std::fmt::Debug> #value_trait for #wrapper<T> {
+                fn __mview_dbg(&self) -> ::std::string::String {
+                    ::std::format!("{:?}", self.0)
+                }
+            }
+
+            let #value_var = #wrapper(#value);
+            ::leptos::prelude::Effect::new(move |_| {
+                ::leptos::logging::log!(
+                    "[{}:{}] {} = {}",
+                    ::std::file!(),
+                    ::std::line!(),
+                    #label,
+                    (&&&#value_var).__mview_dbg()
+                );
+            });
+        }
+    }
+}
+
+/// Converts a `testid:name` to an `attr:data-testid="name"` directive.
+///
+/// Returns `None` if the `testid` feature is disabled, so that nothing is
+/// added to the element.
+///
+/// **Panics** if the provided directive is not `testid:`.
+pub(super) fn testid_to_attr_directive(dir: &Directive) -> Option<Directive> {
+    assert_eq!(dir.dir, "testid", "directive should be `testid:`");
+    emit_error_if_modifier(dir.modifier.as_ref());
+    if let Some(value) = &dir.value {
+        emit_error!(
+            value.span(), "`testid:` does not take a value";
+            help = "the test id is the name after `testid:`, like `testid:submit-button`"
+        );
+    }
+
+    if !cfg!(feature = "testid") {
+        return None;
+    }
+    let test_id = dir.key.to_unspanned_string();
+    Some(Directive {
+        dir: syn::Ident::new("attr", dir.dir.span()),
+        key: KebabIdentOrStr::Str(syn::LitStr::new("data-testid", Span::call_site())),
+        modifier: None,
+        optional: None,
+        value: Some(Value::Lit(syn::Lit::Str(syn::LitStr::new(
+            &test_id,
+            dir.key.to_lit_str().span(),
+        )))),
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum AttributeKind {
     /// "class"
@@ -90,7 +253,9 @@ enum AttributeKind {
     Style,
     /// An attribute with a `-`, like data-*
     ///
-    /// Excludes `aria-*` attributes.
+    /// Excludes `aria-*` attributes. Also includes the declarative shadow DOM
+    /// attributes on `template`, like `shadowrootmode`, and inline handlers
+    /// like `onclick`, which Leptos has no methods for.
     Custom,
     /// An attribute that should be added by a method so that it is checked.
     OtherChecked,
@@ -108,7 +273,10 @@ impl From<&str> for AttributeKind {
             Self::Class
         } else if value == "style" {
             Self::Style
-        } else if value.contains('-') && !value.starts_with("aria-") {
+        } else if (value.contains('-') && !value.starts_with("aria-"))
+            || value.starts_with("shadowroot")
+            || inline_handler_event(value).is_some()
+        {
             Self::Custom
         } else {
             Self::OtherChecked
@@ -122,33 +290,251 @@ impl From<&str> for AttributeKind {
 
 /// Converts element class/id selector shorthands into a series of `.classes`
 /// and `.id` calls.
-pub(super) fn xml_selectors_tokens(selectors: &SelectorShorthands) -> TokenStream {
+///
+/// The classes are left out if `include_classes` is false, as they have been
+/// merged by [`xml_static_classes_tokens`].
+pub(super) fn xml_selectors_tokens(
+    selectors: &SelectorShorthands,
+    include_classes: bool,
+) -> TokenStream {
+    emit_error_if_multiple_ids(selectors);
     let (classes, ids): (Vec<_>, Vec<_>) = selectors
         .iter()
+        .filter(|sel| include_classes || !matches!(sel, SelectorShorthand::Class { .. }))
         .partition(|sel| matches!(sel, SelectorShorthand::Class { .. }));
 
     let class_methods = classes.iter().map(|class| {
         let method = syn::Ident::new("class", class.prefix().span());
-        let class_name = class.ident().to_str_colored();
+        let class_name = match class {
+            SelectorShorthand::Class { class, dot_symbol } => {
+                macro_class_name(&class.to_unspanned_string(), dot_symbol.span)
+            }
+            SelectorShorthand::Id { .. } => None,
+        }
+        .unwrap_or_else(|| class.to_str_colored());
         quote! { .#method((#class_name, true)) }
     });
 
     let id_methods = ids.iter().map(|id| {
         let method = syn::Ident::new("id", id.prefix().span());
-        let id_name = id.ident().to_str_colored();
+        let id_name = id.to_str_colored();
         quote! { .#method(#id_name) }
     });
 
     quote! { #(#class_methods)* #(#id_methods)* }
 }
 
+/// Merges the class selectors and `class="..."` attributes into a single
+/// `.class("...")` call, if all of them are string literals.
+///
+/// Returns `None` if there are no classes, a dynamic `class` attribute, any
+/// `class:` directives, or if class names are passed to a class macro. Each
+/// class is then added separately.
+///
+/// # Example
+/// ```ignore
+/// div.card.primary class="x y";
+/// ```
+/// Expands to:
+/// ```ignore
+/// div().class("x y card primary")
+/// ```
+pub(super) fn xml_static_classes_tokens(element: &Element) -> Option<TokenStream> {
+    if class_macro().is_some() {
+        return None;
+    }
+    let mut classes = Vec::new();
+    let mut method_span = None;
+    for attr in element.attrs().iter() {
+        match attr {
+            Attr::Kv(kv) if kv.key().repr() == "class" => {
+                let Value::Lit(syn::Lit::Str(class)) = kv.value() else {
+                    return None;
+                };
+                method_span.get_or_insert(kv.key().span());
+                classes.push(class.value());
+            }
+            Attr::Directive(dir)
+                if dir.dir == "class"
+                    || (dir.dir == "attr" && dir.key.to_lit_str().value() == "class") =>
+            {
+                return None;
+            }
+            _ => (),
+        }
+    }
+
+    let mut dummy_items = Vec::new();
+    for selector in element.selectors().iter() {
+        let SelectorShorthand::Class { class, .. } = selector else {
+            continue;
+        };
+        method_span.get_or_insert(selector.prefix().span());
+        if let KebabIdentOrStr::KebabIdent(ident) = class {
+            dummy_items.extend(span::color_all(ident.spans()));
+        }
+        classes.push(class.to_unspanned_string());
+    }
+
+    let method = syn::Ident::new("class", method_span?);
+    let classes = classes.join(" ");
+    Some(quote! { .#method({ #(#dummy_items)* #classes }) })
+}
+
+/// Whether the attribute is a `data={pairs}` map of `data-*` attributes.
+///
+/// `<object>` has its own `data` attribute, and literals are always a plain
+/// attribute.
+pub(super) fn is_data_attrs(tag: &Tag, attr: &KvAttr) -> bool {
+    attr.key().repr() == "data"
+        && !matches!(attr.value(), Value::Lit(_))
+        && !matches!(tag, Tag::Html(ident) if ident.unraw() == "object")
+}
+
+/// Converts `data={pairs}` to a `data-{key}` attribute for each `(key, value)`
+/// pair, where `pairs` is anything that can be iterated over.
+///
+/// If `pairs` is a closure returning the pairs, the attributes are updated
+/// whenever it changes: attributes that are no longer returned are removed.
+///
+/// ```ignore
+/// div data={HashMap::from([("id", 1)])};
+/// ```
+/// Expands to:
+/// ```ignore
+/// div().add_any_attr(vec![custom_attribute("data-id", "1").into_any_attr()])
+/// ```
+pub(super) fn data_attrs_tokens(attr: &KvAttr) -> TokenStream {
+    let value = attr.value();
+    let add_any_attr = syn::Ident::new("add_any_attr", attr.key().span());
+    quote_spanned! { value.span()=>
+        .#add_any_attr({
+            // autoref specialization: closures are preferred over any other value.
+            #[allow(unused_imports)]
+            use ::leptos_mview::__private::{DataFn as _, DataValue as _};
+            (&&::leptos_mview::__private::Data::new(#value)).__mview_data()
+        })
+    }
+}
+
+/// Whether the directive is a `style:` with a string literal value, which can
+/// be merged into the `style` attribute by [`xml_static_styles_tokens`].
+pub(super) fn is_static_style_directive(dir: &Directive) -> bool {
+    dir.dir == "style"
+        && dir.modifier.is_none()
+        && matches!(dir.value, Some(Value::Lit(syn::Lit::Str(_))))
+}
+
+/// Merges a string `style="..."` attribute and the `style:` directives with
+/// string values into a single `.style("...")` call.
+///
+/// Returns `None` if there is no string `style` attribute or no directive to
+/// merge into it, or if the `style` attribute is dynamic. The remaining
+/// `style:` directives are added after the merged attribute, so they take
+/// precedence over it.
+///
+/// # Example
+/// ```ignore
+/// div style="color: red" style:margin="4px" style:width=[w()];
+/// ```
+/// Expands to:
+/// ```ignore
+/// div().style("color: red;margin:4px").style(("width", move || w()))
+/// ```
+pub(super) fn xml_static_styles_tokens(element: &Element) -> Option<TokenStream> {
+    let mut style = None;
+    let mut pairs = Vec::new();
+    for attr in element.attrs().iter() {
+        match attr {
+            Attr::Kv(kv) if kv.key().repr() == "style" => {
+                let Value::Lit(syn::Lit::Str(value)) = kv.value() else {
+                    return None;
+                };
+                let style = style.get_or_insert((kv.key().span(), String::new()));
+                push_style_declarations(&mut style.1, &value.value());
+            }
+            Attr::Directive(dir)
+                if dir.dir == "attr" && dir.key.to_lit_str().value() == "style" =>
+            {
+                return None;
+            }
+            Attr::Directive(dir) if is_static_style_directive(dir) => {
+                let Some(Value::Lit(syn::Lit::Str(value))) = &dir.value else {
+                    unreachable!("checked by `is_static_style_directive`")
+                };
+                pairs.push(format!(
+                    "{}:{}",
+                    dir.key.to_unspanned_string(),
+                    value.value()
+                ));
+            }
+            _ => (),
+        }
+    }
+
+    let (span, mut style) = style?;
+    if pairs.is_empty() {
+        return None;
+    }
+    for pair in pairs {
+        push_style_declarations(&mut style, &pair);
+    }
+    let method = syn::Ident::new("style", span);
+    Some(quote! { .#method(#style) })
+}
+
+/// Appends CSS declarations to a style attribute, separating them with a `;`.
+fn push_style_declarations(style: &mut String, declarations: &str) {
+    let declarations = declarations.trim();
+    if declarations.is_empty() {
+        return;
+    }
+    if !style.is_empty() && !style.ends_with(';') {
+        style.push(';');
+    }
+    style.push_str(declarations);
+}
+
 pub(super) fn xml_kv_attribute_tokens(attr: &KvAttr, element_tag: TagKind) -> TokenStream {
     let key = attr.key();
     let value = attr.value();
     // special cases
-    if key.repr() == "ref" {
+    if matches!(key.repr(), "ref" | "node_ref" | "_ref" | "ref_") {
+        if key.repr() != "ref" {
+            emit_error!(
+                key.span(), "unknown attribute `{}`", key.repr();
+                note = "use `ref` to bind a `NodeRef` to an element"
+            );
+        }
+        if !check_ref_value(attr) {
+            return quote! {};
+        }
         let node_ref = syn::Ident::new("node_ref", key.span());
         quote! { .#node_ref(#value) }
+    } else if matches!(key.repr(), "inner_html" | "unsafe_inner_html") {
+        // dynamic html is only accepted with the `unsafe_` prefix, as it is
+        // inserted without any escaping.
+        if key.repr() == "inner_html" && !matches!(value, Value::Lit(syn::Lit::Str(_))) {
+            emit_error!(
+                value.span(), "`inner_html` only accepts a string literal";
+                note = "the html is inserted as-is, so a dynamic value could \
+                    allow cross-site scripting (XSS) if it contains user input";
+                help = "if the html is trusted or sanitized, use `unsafe_inner_html` instead"
+            );
+        }
+        let inner_html = syn::Ident::new("inner_html", key.span());
+        quote! { .#inner_html(#value) }
+    } else if let (Some(_), "class", Value::Lit(syn::Lit::Str(classes))) =
+        (class_macro(), key.repr(), value)
+    {
+        // each class name is passed to the class macro separately
+        let method = syn::Ident::new("class", key.span());
+        let classes_str = classes.value();
+        let class_methods = classes_str.split_whitespace().map(|class| {
+            let class = macro_class_name(class, classes.span()).expect("class macro is set");
+            quote! { .#method((#class, true)) }
+        });
+        quote! { #(#class_methods)* }
     } else {
         // https://github.com/leptos-rs/leptos/blob/main/leptos_macro/src/view/mod.rs#L960
         // Use unchecked attributes if:
@@ -157,6 +543,12 @@ pub(super) fn xml_kv_attribute_tokens(attr: &KvAttr, element_tag: TagKind) -> To
         // - or it's a custom or data attribute (has `-` except for `aria-`)
         let attr_kind = AttributeKind::from(key.repr());
         let is_web_or_svg = matches!(element_tag, TagKind::Svg | TagKind::WebComponent);
+        // these take "true" or "false" instead of being present or absent
+        let value = if utils::is_enumerated_attr(key.repr()) {
+            utils::enumerated_value_tokens(value)
+        } else {
+            value.to_token_stream()
+        };
 
         if (is_web_or_svg || attr_kind.is_custom()) && !attr_kind.is_class_or_style() {
             // unchecked attribute
@@ -172,28 +564,82 @@ pub(super) fn xml_kv_attribute_tokens(attr: &KvAttr, element_tag: TagKind) -> To
     }
 }
 
-pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
+/// Converts `value` on a `textarea` to the property and child that set its
+/// contents, as a `value` attribute is ignored by the browser.
+///
+/// Returns the `let` binding for the value, the method setting the property,
+/// and the child method.
+///
+/// ```ignore
+/// textarea value={text};
+/// ```
+/// Expands to:
+/// ```ignore
+/// {
+///     let value = {text};
+///     textarea().prop("value", value.clone()).child(value)
+/// }
+/// ```
+///
+/// The property keeps a reactive value in sync after the user has typed in
+/// the textarea, and the child sets the initial contents when rendered to
+/// HTML. A literal never changes, so it is only added as a child.
+pub(super) fn textarea_value_tokens(attr: &KvAttr) -> (TokenStream, TokenStream, TokenStream) {
+    let value = attr.value();
+    let child_method = syn::Ident::new("child", attr.key().span());
+    if let Value::Lit(_) = value {
+        return (quote! {}, quote! {}, quote! { .#child_method(#value) });
+    }
+    let (binding, var) = utils::bind_value(value, "__mview_textarea_value");
+    let prop = syn::Ident::new("prop", attr.key().span());
+    (
+        binding,
+        quote! { .#prop("value", ::core::clone::Clone::clone(&#var)) },
+        quote! { .#child_method(#var) },
+    )
+}
+
+pub(super) fn xml_directive_tokens(directive: &Directive, element_tag: TagKind) -> TokenStream {
     let Directive {
         dir,
         key,
         modifier,
+        optional: _,
         value,
     } = directive;
 
     match dir.to_string().as_str() {
-        "class" | "style" => {
+        "class" => {
             let key = key.to_lit_str();
+            let key = macro_class_name(&key.value(), key.span()).unwrap_or_else(|| quote! { #key });
             emit_error_if_modifier(modifier.as_ref());
+            let value = directive.value_or_true();
             quote! { .#dir((#key, #value)) }
         }
-        "prop" => {
+        "style" => {
+            let priority = important_style_directive(directive)
+                .map(|priority| quote! { .add_any_attr(#priority) });
             let key = key.to_lit_str();
             emit_error_if_modifier(modifier.as_ref());
+            quote! { .#dir((#key, #value)) #priority }
+        }
+        "prop" => {
+            // custom elements name their properties in camelCase, a string key is
+            // used as it is
+            let key = match key {
+                KebabIdentOrStr::KebabIdent(ident) if element_tag == TagKind::WebComponent => {
+                    syn::LitStr::new(&utils::kebab_to_lower_camel(ident.repr()), ident.span())
+                }
+                _ => key.to_lit_str(),
+            };
+            emit_error_if_modifier(modifier.as_ref());
+            let value = directive.value_or_true();
             quote! { .#dir(#key, #value) }
         }
         "on" => {
             let event_path = event_listener_event_path(directive);
-            quote! { .#dir(#event_path, #value) }
+            let handler = event_listener_handler(directive);
+            quote! { .#dir(#event_path, #handler) }
         }
         "use" => {
             let (fn_name, value) = use_directive_fn_value(directive);
@@ -202,7 +648,24 @@ pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
                 .#directive(#fn_name, #value)
             }
         }
-        "attr" | "clone" => {
+        "attr" => {
+            // always a plain attribute, even for `class`, `style`, `ref`, etc.
+            emit_error_if_modifier(modifier.as_ref());
+            let key = key.to_unspanned_string();
+            let value = utils::attr_directive_value(directive);
+            quote! {
+                .#dir(#key, ::leptos::prelude::IntoAttributeValue::into_attribute_value(#value))
+            }
+        }
+        "testid" => testid_to_attr_directive(directive)
+            .map(|attr| xml_directive_tokens(&attr, element_tag))
+            .unwrap_or_default(),
+        "transition" => {
+            let attrs = transition_tokens(directive);
+            let add_any_attr = syn::Ident::new("add_any_attr", dir.span());
+            quote! { .#add_any_attr(#attrs) }
+        }
+        "clone" => {
             emit_error!(dir.span(), "`{}:` is not supported on elements", dir);
             quote! {}
         }
@@ -226,6 +689,97 @@ pub(super) fn xml_directive_tokens(directive: &Directive) -> TokenStream {
     }
 }
 
+/// Sets a literal `style:` value ending in `!important` with its priority in
+/// the browser.
+///
+/// Leptos passes the whole value to `setProperty`, which ignores it as
+/// `!important` isn't part of the value, so this adds a directive that sets it
+/// again with the priority once the element is built. The style itself still
+/// renders as `color:red !important;` on the server.
+///
+/// Returns `None` if the directive is not a string ending in `!important`.
+fn important_style_directive(directive: &Directive) -> Option<TokenStream> {
+    let Some(Value::Lit(syn::Lit::Str(lit))) = &directive.value else {
+        return None;
+    };
+    let full = lit.value();
+    let value = full.trim_end().strip_suffix("!important")?.trim_end();
+    let key = directive.key.to_unspanned_string();
+    Some(quote! {
+        ::leptos::tachys::html::directive::directive(
+            |el: ::leptos::tachys::renderer::types::Element| {
+                let el = ::leptos::wasm_bindgen::JsCast::unchecked_ref::<
+                    ::leptos::web_sys::HtmlElement
+                >(&el);
+                let _ = el.style().set_property_with_priority(#key, #value, "important");
+            },
+            (),
+        )
+    })
+}
+
+/// Converts a `transition:name={when}` to the attributes that show and hide
+/// the element with the `name-enter` and `name-leave` classes.
+///
+/// The attributes are made by `leptos_mview::__private::transition`, see
+/// there for how the classes are added and removed. `when` can be a signal or
+/// a closure returning a `bool`.
+pub(super) fn transition_tokens(dir: &Directive) -> TokenStream {
+    emit_error_if_modifier(dir.modifier.as_ref());
+    let Some(value) = &dir.value else {
+        emit_error!(
+            dir.dir.span(), "`transition:` requires a value";
+            help = "add a signal or closure that shows the element, like `transition:fade={visible}`"
+        );
+        return quote! { () };
+    };
+    let name = dir.key.to_unspanned_string();
+    let (enter, leave) = (format!("{name}-enter"), format!("{name}-leave"));
+    quote_spanned! { dir.dir.span()=>
+        ::leptos_mview::__private::transition(#enter, #leave, {
+            // autoref specialization: signals are preferred over closures.
+            #[allow(unused_imports)]
+            use ::leptos_mview::__private::{TransitionWhenFn as _, TransitionWhenSignal as _};
+            (&&::leptos_mview::__private::TransitionWhen::new(#value)).__mview_when()
+        })
+    }
+}
+
+/// Moves the children of a `template` into its `content` in the browser.
+///
+/// Like in HTML, the children of a `template` shouldn't be part of the page,
+/// but Leptos mounts them on the element itself. Directives run before the
+/// children are mounted, so they are moved in a microtask. On the server, the
+/// children are rendered inside the `<template>` as usual, which makes it a
+/// declarative shadow root with a `shadowrootmode` attribute.
+///
+/// Returns `None` if the element is not a `template` with children.
+pub(super) fn template_content_tokens(element: &Element) -> Option<TokenStream> {
+    let Tag::Html(ident) = element.tag() else {
+        return None;
+    };
+    if ident.unraw() != "template" || element.children().is_none_or(|c| c.is_empty()) {
+        return None;
+    }
+    let add_any_attr = syn::Ident::new("add_any_attr", ident.span());
+    Some(quote! {
+        .#add_any_attr(::leptos::tachys::html::directive::directive(
+            |el: ::leptos::tachys::renderer::types::Element| {
+                ::leptos::prelude::queue_microtask(move || {
+                    let template = ::leptos::wasm_bindgen::JsCast::unchecked_into::<
+                        ::leptos::web_sys::HtmlTemplateElement
+                    >(el);
+                    let content = template.content();
+                    while let ::std::option::Option::Some(child) = template.first_child() {
+                        let _ = content.append_child(&child);
+                    }
+                });
+            },
+            (),
+        ))
+    })
+}
+
 pub(super) fn xml_spread_tokens(attr: &SpreadAttr) -> TokenStream {
     let (dotdot, expr) = (attr.dotdot(), attr.expr());
     let attrs = syn::Ident::new("add_any_attr", dotdot.span());
@@ -250,9 +804,17 @@ pub(super) fn xml_child_methods_tokens<'a>(
     let mut ts = TokenStream::new();
     for child in children {
         let child_method = syn::Ident::new("child", child.span());
-        ts.extend(quote! {
-            .#child_method(#child)
-        });
+        let inert = match child {
+            NodeChild::Element(element) => {
+                inert_element_tokens(element).or_else(|| hoisted_element_tokens(element))
+            }
+            _ => None,
+        };
+        if let Some(inert) = inert {
+            ts.extend(quote! { .#child_method(#inert) });
+        } else {
+            ts.extend(quote! { .#child_method(#child) });
+        }
     }
     ts
 }
@@ -261,8 +823,12 @@ pub(super) fn xml_child_methods_tokens<'a>(
 // ------------------- component only ------------------- //
 ////////////////////////////////////////////////////////////
 
+/// Expands to a call to the prop's builder method, like `.some_prop(value)`.
+///
+/// The method is spanned to the start of the key, so that hovering or
+/// renaming the key in the view finds the prop.
 pub(super) fn component_kv_attribute_tokens(attr: &KvAttr) -> TokenStream {
-    let (key, value) = (attr.key().to_snake_ident(), attr.value());
+    let (key, value) = (attr.key().to_snake_ident_first_span(), attr.value());
     quote_spanned! { attr.span()=> .#key(#value) }
 }
 
@@ -287,14 +853,7 @@ pub(super) fn component_clone_tokens(dir: &Directive) -> TokenStream {
 ///     // any clones
 ///     let clone = clone.clone();
 ///     // the children themself
-///     leptos::ToChildren::to_children(move || {
-///         leptos::Fragment::lazy(|| {
-///             [
-///                 child1.into_view(),
-///                 child2.into_view(),
-///             ].to_vec()
-///         })
-///     })
+///     ::leptos::children::ToChildren::to_children(move || (child1, child2))
 /// })
 /// ```
 ///
@@ -304,17 +863,13 @@ pub(super) fn component_clone_tokens(dir: &Directive) -> TokenStream {
 ///     // any clones
 ///     let clone = clone.clone();
 ///     // the children
-///     move |args| leptos::Fragment::lazy(|| {
-///         [
-///             child1.into_view(),
-///             child2.into_view(),
-///         ].to_vec()
-///     })
+///     move |args| (child1, child2)
 /// })
 /// ```
 pub(super) fn component_children_tokens<'a>(
     children: impl Iterator<Item = &'a NodeChild>,
     args: Option<&TokenStream>,
+    block_span: Span,
     clones: &TokenStream,
 ) -> TokenStream {
     let mut children = children.peekable();
@@ -323,10 +878,13 @@ pub(super) fn component_children_tokens<'a>(
         // not sure why `child.span()` is calling `syn::spanned::Spanned` instead
         .map_or_else(Span::call_site, |child| (*child).span());
 
-    // span call site if there are no args so that the children don't get all the
-    // `std` `vec!` etc docs.
-    let children_fragment =
-        children_fragment_tokens(children, args.map_or(Span::call_site(), Spanned::span));
+    // span the fragment to the children block so that errors about the children
+    // (e.g. one of them not being a view) point at the user's `{ ... }` instead
+    // of the whole macro.
+    // each child can't be checked on its own, as components can take children
+    // that aren't views (like the router's `RouteChildren`), so the whole tuple
+    // is the narrowest span available.
+    let children_fragment = children_fragment_tokens(children, block_span);
 
     // children with arguments take a `Fn(T) -> impl IntoView`
     // normal children (`Children`, `ChildrenFn`, ...) take
@@ -338,12 +896,16 @@ pub(super) fn component_children_tokens<'a>(
         // this span is required for slots that take `Callback<T, View>` but have been
         // given a regular `ChildrenFn` instead.
         let closure = quote_spanned!(child_span=> move || #children_fragment);
-        quote! {
+        quote_spanned! { block_span=>
             ::leptos::children::ToChildren::to_children(#closure)
         }
     };
 
-    let children_method = quote_spanned!(child_span=> children);
+    let children_method = if args.is_some() {
+        quote_spanned!(block_span=> children)
+    } else {
+        quote_spanned!(child_span=> children)
+    };
 
     quote! {
         .#children_method({
@@ -387,11 +949,19 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
         "class" | "style" => {
             // avoid making it string coloured
             let key = directive.key.to_unspanned_string();
-            let value = directive.value.clone().unwrap_or_else(Value::new_true);
+            let key = match macro_class_name(&key, directive.key.to_lit_str().span()) {
+                Some(class) if dir == "class" => class,
+                _ => quote! { #key },
+            };
+            let value = directive.value_or_true();
             // to avoid spanning the directive to the module
             let dir_unspanned = syn::Ident::new(&dir.to_string(), Span::call_site());
-            quote! {
+            let path = quote! {
                 ::leptos::tachys::html::#dir_unspanned::#dir((#key, #value))
+            };
+            match important_style_directive(directive) {
+                Some(priority) => quote! { (#path, #priority) },
+                None => path,
             }
         }
         "attr" => {
@@ -399,7 +969,7 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
             match attr_kind {
                 AttributeKind::Class | AttributeKind::Style => {
                     let class_or_style = directive.key.to_ident_or_emit();
-                    let value = directive.value.clone().unwrap_or_else(Value::new_true);
+                    let value = directive.value_or_true();
                     // to avoid spanning to the module name
                     let class_or_style_unspanned =
                         syn::Ident::new(&class_or_style.unraw().to_string(), Span::call_site());
@@ -409,14 +979,14 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
                 }
                 AttributeKind::Custom => {
                     let attr_name = directive.key.to_unspanned_string();
-                    let value = directive.value.clone().unwrap_or_else(Value::new_true);
+                    let value = utils::attr_directive_value(directive);
                     quote! {
                         ::leptos::tachys::html::attribute::custom::custom_attribute(#attr_name, #value)
                     }
                 }
                 AttributeKind::OtherChecked => {
                     let attr_name = directive.key.to_ident_or_emit();
-                    let value = directive.value.clone().unwrap_or_else(Value::new_true);
+                    let value = utils::attr_directive_value(directive);
                     quote! {
                         ::leptos::tachys::html::attribute::#attr_name(#value)
                     }
@@ -424,17 +994,19 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
             }
         }
         "prop" => {
-            let prop = directive.key.to_ident_or_emit();
-            let value = directive.value.clone().unwrap_or_else(Value::new_true);
+            emit_error_if_modifier(directive.modifier.as_ref());
+            // avoid making it string coloured
+            let key = directive.key.to_unspanned_string();
+            let value = directive.value_or_true();
             quote! {
-                ::leptos::tachys::html::property::#prop(#value)
+                ::leptos::tachys::html::property::#dir(#key, #value)
             }
         }
         "on" => {
             let event_path = event_listener_event_path(directive);
-            let value = &directive.value;
+            let handler = event_listener_handler(directive);
             quote! {
-                ::leptos::tachys::html::event::on(#event_path, #value)
+                ::leptos::tachys::html::event::on(#event_path, #handler)
             }
         }
         "use" => {
@@ -447,6 +1019,7 @@ pub(super) fn directive_to_any_attr_path(directive: &Directive) -> Option<TokenS
                 )
             }
         }
+        "transition" => transition_tokens(directive),
         _ => return None,
     };
 
@@ -0,0 +1,195 @@
+use std::borrow::Cow;
+
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::ext::IdentExt;
+
+use super::utils::{convert_enumerated_bracket, is_enumerated_attr};
+use crate::ast::{Attr, Attrs, NodeChild, Tag, TagKind, Value};
+
+/// Children with their bracketed closures traced, from [`trace_children`].
+pub struct TracedChildren<'a>(Vec<(&'a NodeChild, Option<NodeChild>)>);
+
+impl TracedChildren<'_> {
+    /// Returns the traced version of each child, or the original child if it
+    /// is unchanged.
+    pub fn iter(&self) -> impl Iterator<Item = &NodeChild> {
+        self.0
+            .iter()
+            .map(|(child, traced)| traced.as_ref().unwrap_or(*child))
+    }
+}
+
+/// Wraps every bracketed closure in the attributes with a log that is called
+/// whenever the closure re-runs.
+///
+/// Does nothing unless the `trace` feature is enabled.
+pub fn trace_attrs<'a>(attrs: &'a Attrs, tag: &Tag) -> Cow<'a, Attrs> {
+    let is_traced = |attr: &Attr| match attr {
+        Attr::Kv(kv) => is_traced_value(kv.value()),
+        // `dbg:` already logs its value
+        Attr::Directive(dir) => dir.dir != "dbg" && dir.value.as_ref().is_some_and(is_traced_value),
+        Attr::Spread(_) => false,
+    };
+    if !cfg!(feature = "trace") || !attrs.iter().any(is_traced) {
+        return Cow::Borrowed(attrs);
+    }
+
+    // enumerated attributes are converted before tracing, see
+    // `convert_enumerated_bracket`
+    let is_component = tag.kind() == TagKind::Component;
+    let tag = tag_name(tag);
+    let mut attrs = attrs.clone();
+    for attr in attrs.iter_mut().filter(|a| is_traced(a)) {
+        match attr {
+            Attr::Kv(kv) => {
+                let name = kv.key().repr().to_string();
+                if !is_component && is_enumerated_attr(&name) {
+                    convert_enumerated_bracket(kv.value_mut());
+                }
+                trace_value(kv.value_mut(), &format!("<{tag}> {name}"));
+            }
+            Attr::Directive(dir) => {
+                let key = dir.key.to_unspanned_string();
+                let name = format!("{}:{key}", dir.dir);
+                if let Some(value) = &mut dir.value {
+                    // same as `attr_directive_value`
+                    if dir.dir == "attr" && key.starts_with("aria-") {
+                        convert_enumerated_bracket(value);
+                    }
+                    trace_value(value, &format!("<{tag}> {name}"));
+                }
+            }
+            Attr::Spread(_) => (),
+        }
+    }
+    Cow::Owned(attrs)
+}
+
+/// Wraps every bracketed closure child with a log that is called whenever the
+/// closure re-runs.
+///
+/// `tag` is the parent element, or `None` if the children are at the top
+/// level. Does nothing unless the `trace` feature is enabled.
+pub fn trace_children<'a>(
+    children: impl Iterator<Item = &'a NodeChild>,
+    tag: Option<&Tag>,
+) -> TracedChildren<'a> {
+    let location = tag.map_or_else(
+        || "child".to_string(),
+        |tag| format!("<{}> child", tag_name(tag)),
+    );
+    TracedChildren(
+        children
+            .map(|child| (child, trace_child(child, &location)))
+            .collect(),
+    )
+}
+
+/// Returns the traced version of a single child, or `None` if it is unchanged.
+///
+/// Does nothing unless the `trace` feature is enabled.
+pub fn trace_root_child(child: &NodeChild) -> Option<NodeChild> { trace_child(child, "child") }
+
+fn trace_child(child: &NodeChild, location: &str) -> Option<NodeChild> {
+    match child {
+        NodeChild::Value(value) if cfg!(feature = "trace") && is_traced_value(value) => {
+            let mut value = value.clone();
+            trace_value(&mut value, location);
+            Some(NodeChild::Value(value))
+        }
+        _ => None,
+    }
+}
+
+/// Whether the value is a closure with no arguments made by the macro, like
+/// `[count() * 2]` or `f["{}", count()]`.
+fn is_traced_value(value: &Value) -> bool {
+    matches!(value, Value::Bracket { prefixes, .. } if prefixes.as_ref().is_none_or(|p| p == "f"))
+        && !value.is_closure_bracket()
+}
+
+/// Replaces a bracketed closure with a block that makes a closure logging
+/// `location` and the source line before calling the original.
+fn trace_value(value: &mut Value, location: &str) {
+    let Value::Bracket { brackets, .. } = value else {
+        return;
+    };
+    let span = brackets.span.join();
+    let braces = syn::token::Brace {
+        span: brackets.span,
+    };
+    let traced = syn::Ident::new("__mview_traced", Span::mixed_site());
+    let tokens = quote_spanned! { span=>
+        let #traced = #value;
+        move || {
+            ::leptos::logging::log!(
+                "[mview] {} ran at {}:{}",
+                #location,
+                ::std::file!(),
+                ::std::line!()
+            );
+            #traced()
+        }
+    };
+    *value = Value::Block { tokens, braces };
+}
+
+fn tag_name(tag: &Tag) -> String {
+    match tag {
+        Tag::Html(ident) | Tag::Svg(ident) | Tag::Math(ident) => ident.unraw().to_string(),
+        Tag::WebComponent(ident) => ident.repr().to_string(),
+        Tag::Component(path) => path.segments.last().map_or_else(
+            || quote!(#path).to_string(),
+            |segment| segment.ident.to_string(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::{trace_attrs, trace_children};
+    use crate::ast::{Attr, Element};
+
+    fn attr_value(attr: &Attr) -> String {
+        match attr {
+            Attr::Kv(kv) => kv.value().to_token_stream().to_string(),
+            Attr::Directive(dir) => dir.value.to_token_stream().to_string(),
+            Attr::Spread(spread) => spread.expr().to_string(),
+        }
+    }
+
+    #[test]
+    fn traces_brackets() {
+        let input = "div class=[active()] id={id} style:color=f[\"{}\", c()] { [count()] {text} }";
+        let element: Element = syn::parse_str(input).unwrap();
+        let attrs = trace_attrs(element.attrs(), element.tag());
+        let children = element.children().unwrap();
+        let children = trace_children(children.node_children(), Some(element.tag()));
+        let children = children
+            .iter()
+            .map(|child| child.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+
+        if cfg!(feature = "trace") {
+            assert!(attr_value(&attrs[0]).contains("\"<div> class\""));
+            assert!(attr_value(&attrs[2]).contains("\"<div> style:color\""));
+            assert!(children[0].contains("\"<div> child\""));
+        } else {
+            assert!(!attr_value(&attrs[0]).contains("log"));
+            assert!(!children[0].contains("log"));
+        }
+        // blocks are never traced
+        assert!(!attr_value(&attrs[1]).contains("log"));
+        assert!(!children[1].contains("log"));
+    }
+
+    #[test]
+    fn unchanged_without_brackets() {
+        let element: Element = syn::parse_str("div class={c} on:click={f};").unwrap();
+        let attrs = trace_attrs(element.attrs(), element.tag());
+        assert!(matches!(attrs, std::borrow::Cow::Borrowed(_)));
+    }
+}
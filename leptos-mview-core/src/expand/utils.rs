@@ -1,6 +1,22 @@
-use proc_macro_error2::{abort, emit_error};
+use std::borrow::Cow;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::{ext::IdentExt, parse_quote, spanned::Spanned};
 
+use crate::{
+    ast::{
+        attribute::{
+            directive::Directive,
+            kv::KvAttr,
+            selector::{SelectorShorthand, SelectorShorthands},
+        },
+        Attr, Attrs, Tag, Value,
+    },
+    error_ext::{abort, emit_error},
+    span,
+};
+
 #[allow(clippy::doc_markdown)]
 // just doing a manual implementation as theres only one need for this (slots).
 // Use the `paste` crate if more are needed in the future.
@@ -42,6 +58,24 @@ pub fn snake_case_to_upper_camel(ident: syn::Ident) -> syn::Ident {
     syn::Ident::new_raw(&new, ident.span())
 }
 
+/// Converts a kebab-case name like `help-text` to lowerCamelCase (`helpText`),
+/// which is how DOM properties are named.
+pub fn kebab_to_lower_camel(name: &str) -> String {
+    let mut new = String::with_capacity(name.len());
+    let mut next_char_is_word_start = false;
+    for char in name.chars() {
+        match (char, next_char_is_word_start) {
+            ('-', _) => next_char_is_word_start = true,
+            (c, true) => {
+                next_char_is_word_start = false;
+                new.extend(c.to_uppercase());
+            }
+            (c, false) => new.push(c),
+        }
+    }
+    new
+}
+
 pub fn emit_error_if_modifier(m: Option<&syn::Ident>) {
     if let Some(modifier) = m {
         emit_error!(
@@ -51,6 +85,266 @@ pub fn emit_error_if_modifier(m: Option<&syn::Ident>) {
     }
 }
 
+/// Emits an error on every id selector after the first, as an element can only
+/// have one id.
+pub fn emit_error_if_multiple_ids(selectors: &SelectorShorthands) {
+    let extra_ids = selectors
+        .iter()
+        .filter(|sel| matches!(sel, SelectorShorthand::Id { .. }))
+        .skip(1);
+    for id in extra_ids {
+        emit_error!(id.span(), "an element can only have one id");
+    }
+}
+
+/// Checks that the value given to a `ref` attribute is a block, emitting an
+/// error and returning `false` if it is not.
+///
+/// `ref` takes a `NodeRef`, so literals (including the implicit `true` from
+/// writing just `ref`) and bracketed closures are always wrong.
+pub fn check_ref_value(attr: &KvAttr) -> bool {
+    let span = match attr.value() {
+        Value::Block { .. } => return true,
+        // `ref` with no value is parsed as `ref=true`
+        Value::Lit(syn::Lit::Bool(b)) if b.value => attr.key().span(),
+        value => value.span(),
+    };
+    emit_error!(span, "ref takes a NodeRef in braces: `ref={node_ref}`");
+    false
+}
+
+/// Whether the directive adds a plain HTML attribute (`attr:` or `testid:`),
+/// which is added along with the other attributes in source order.
+pub fn is_plain_attr_directive(dir: &Directive) -> bool { dir.dir == "attr" || dir.dir == "testid" }
+
+/// Keeps attribute values evaluated in source order when the expansion calls
+/// the builder methods in a different order.
+///
+/// `rank` gives the position of each attribute's method call in the expansion
+/// (e.g. attributes before directives). If the source order already matches,
+/// nothing is changed. Otherwise, every value that may have side effects is
+/// moved out (returned as the first element, see [`bind_reordered_values`])
+/// and replaced by a variable.
+///
+/// Literals and closures are left in place: creating them has no side effects,
+/// and closures need the method's signature to infer their argument types.
+/// `dbg:` values are also left alone, as they are removed in release builds.
+pub fn hoist_reordered_values(
+    attrs: &Attrs,
+    rank: impl Fn(&Attr) -> u8,
+) -> (ReorderedValues, Cow<'_, Attrs>) {
+    let is_dbg = |attr: &Attr| matches!(attr, Attr::Directive(dir) if dir.dir == "dbg");
+    let ranks: Vec<u8> = attrs.iter().filter(|a| !is_dbg(a)).map(&rank).collect();
+    let in_order = ranks.windows(2).all(|w| w[0] <= w[1]);
+    if in_order {
+        return (ReorderedValues::default(), Cow::Borrowed(attrs));
+    }
+
+    let mut attrs = attrs.clone();
+    let mut values = ReorderedValues::default();
+    let mut hoist = |expr: &mut TokenStream, span: Span| {
+        // mixed-site so the variable can't clash with anything the user wrote
+        let var = format_ident!(
+            "__mview_value_{}",
+            values.vars.len(),
+            span = Span::mixed_site().located_at(span)
+        );
+        let value = std::mem::replace(expr, quote! { #var });
+        values.vars.push(var);
+        values.values.push(value);
+    };
+
+    for attr in attrs.iter_mut().filter(|a| !is_dbg(a)) {
+        let value = match attr {
+            Attr::Kv(kv) => Some(kv.value_mut()),
+            Attr::Directive(dir) => dir.value.as_mut(),
+            Attr::Spread(spread) => {
+                let span = spread.span();
+                hoist(spread.expr_mut(), span);
+                None
+            }
+        };
+        if let Some(Value::Block { tokens, .. }) = value {
+            if !is_closure(tokens) {
+                let span = tokens.span();
+                hoist(tokens, span);
+            }
+        }
+    }
+
+    (values, Cow::Owned(attrs))
+}
+
+/// Values moved out by [`hoist_reordered_values`], with the variables that
+/// replaced them.
+#[derive(Default)]
+pub struct ReorderedValues {
+    vars: Vec<syn::Ident>,
+    values: Vec<TokenStream>,
+}
+
+/// Evaluates the values from [`hoist_reordered_values`] in order, then the
+/// expansion with their variables in scope.
+///
+/// The values are matched on instead of put in `let` bindings, so that
+/// temporaries like the `String` in `{&format!("a{}", x)}` live for the whole
+/// expansion, as they would if the value was used in place.
+pub fn bind_reordered_values(values: ReorderedValues, expansion: TokenStream) -> TokenStream {
+    let ReorderedValues { vars, values } = values;
+    if vars.is_empty() {
+        expansion
+    } else {
+        quote! {
+            match (#({ #values },)*) {
+                (#(#vars,)*) => #expansion,
+            }
+        }
+    }
+}
+
+/// Moves a value into a `let` binding, so that it can be used more than once.
+///
+/// Returns the binding and the variable holding the value.
+pub fn bind_value(value: &Value, name: &str) -> (TokenStream, syn::Ident) {
+    // mixed-site so the variable can't clash with anything the user wrote
+    let var = syn::Ident::new(name, Span::mixed_site().located_at(value.span()));
+    let binding = quote! {
+        #[allow(unused_braces)]
+        let #var = #value;
+    };
+    (binding, var)
+}
+
+/// Puts `let` bindings in front of the expansion, if there are any.
+pub fn wrap_bindings(bindings: &TokenStream, expansion: TokenStream) -> TokenStream {
+    if bindings.is_empty() {
+        expansion
+    } else {
+        quote! { { #bindings #expansion } }
+    }
+}
+
+/// Whether the tokens start a closure or async block, like `move || ...`.
+pub fn is_closure(tokens: &TokenStream) -> bool {
+    match tokens.clone().into_iter().next() {
+        Some(TokenTree::Ident(ident)) => ident == "move" || ident == "async",
+        Some(TokenTree::Punct(punct)) => punct.as_char() == '|',
+        _ => false,
+    }
+}
+
+/// Whether the value is a block holding a closure, like `{move |_| ...}`.
+pub fn is_closure_value(value: &Value) -> bool {
+    matches!(value, Value::Block { tokens, .. } if is_closure(tokens))
+}
+
+/// Whether the attribute takes the strings `"true"` and `"false"` instead of
+/// being present or absent, like `draggable` or `aria-hidden`.
+pub fn is_enumerated_attr(key: &str) -> bool {
+    matches!(key, "draggable" | "spellcheck" | "contenteditable") || key.starts_with("aria-")
+}
+
+/// Converts a bool value of an [enumerated attribute](is_enumerated_attr) to
+/// `"true"` or `"false"`, as Leptos would add or remove the attribute instead.
+///
+/// Literals are converted directly. Other values (and the result of closures)
+/// are converted if they are a `bool` or `Option<bool>`, anything else is left
+/// as it is. A block holding a `bool` signal becomes a derived signal of the
+/// string, so it stays reactive.
+pub fn enumerated_value_tokens(value: &Value) -> TokenStream {
+    let convert = enumerated_conversion_tokens;
+    match value {
+        Value::Lit(syn::Lit::Bool(b)) => {
+            syn::LitStr::new(if b.value { "true" } else { "false" }, b.span()).into_token_stream()
+        }
+        Value::Lit(_)
+        | Value::Bracket {
+            prefixes: Some(_), ..
+        } => value.to_token_stream(),
+        Value::Bracket {
+            tokens, brackets, ..
+        } if !value.is_closure_bracket() => {
+            let body = convert(&quote! { {#tokens} });
+            quote_spanned! { brackets.span.join()=> move || #body }
+        }
+        Value::Block { tokens, .. } | Value::Bracket { tokens, .. } => {
+            // convert the result of closures without arguments, like `move || ...`
+            let closure = |input: syn::parse::ParseStream| {
+                let capture: Option<syn::Token![move]> = input.parse()?;
+                let pipes: syn::Token![||] = input.parse()?;
+                let body: TokenStream = input.parse()?;
+                Ok((capture, pipes, body))
+            };
+            match syn::parse::Parser::parse2(closure, tokens.clone()) {
+                Ok((capture, pipes, body)) => {
+                    let body = convert(&quote! { {#body} });
+                    quote! { #capture #pipes #body }
+                }
+                Err(_) => enumerated_signal_tokens(value),
+            }
+        }
+    }
+}
+
+/// Converts the result of a bracketed closure of an enumerated attribute in
+/// place, so that `[hidden()]` becomes `["true" or "false"]`.
+///
+/// Used before the closure is wrapped by the `trace` feature, as
+/// [`enumerated_value_tokens`] can't see into the wrapper afterwards.
+pub fn convert_enumerated_bracket(value: &mut Value) {
+    if let Value::Bracket {
+        tokens,
+        prefixes: None,
+        ..
+    } = value
+    {
+        *tokens = enumerated_conversion_tokens(&quote! { {#tokens} });
+    }
+}
+
+/// Converts `expr` to `"true"` or `"false"` if it is a `bool` or
+/// `Option<bool>`, leaving anything else as it is.
+fn enumerated_conversion_tokens(expr: &dyn ToTokens) -> TokenStream {
+    quote_spanned! { expr.span()=>
+        {
+            #[allow(unused_imports)]
+            use ::leptos_mview::__private::{EnumeratedBool as _, EnumeratedOther as _};
+            (&::leptos_mview::__private::Enumerated::new(#expr)).__mview_enumerated()
+        }
+    }
+}
+
+/// Returns the value of an `attr:` directive, converting bools for ARIA
+/// attributes like `attr:aria-busy=false`.
+///
+/// Only `aria-*` keys are converted, as `attr:` is otherwise passed to Leptos
+/// as it is (`attr:contenteditable=true` stays present-or-absent).
+pub fn attr_directive_value(dir: &Directive) -> TokenStream {
+    let value = dir.value_or_true();
+    if dir.key.to_lit_str().value().starts_with("aria-") {
+        enumerated_value_tokens(&value)
+    } else {
+        value.into_token_stream()
+    }
+}
+
+/// Converts a block value of an enumerated attribute like
+/// [`enumerated_value_tokens`], with `bool` signals converted as well.
+///
+/// `EnumeratedSignal` is implemented on the same type as `EnumeratedBool`,
+/// which is never implemented for a signal.
+fn enumerated_signal_tokens(value: &Value) -> TokenStream {
+    quote_spanned! { value.span()=>
+        {
+            #[allow(unused_imports)]
+            use ::leptos_mview::__private::{
+                EnumeratedBool as _, EnumeratedOther as _, EnumeratedSignal as _,
+            };
+            (&::leptos_mview::__private::Enumerated::new(#value)).__mview_enumerated()
+        }
+    }
+}
+
 /// Converts a [`syn::Path`] (which could include things like `Vec<i32>`) to
 /// always use the turbofish (like `Vec::<i32>`).
 pub fn turbofishify(mut path: syn::Path) -> syn::Path {
@@ -69,11 +363,23 @@ pub fn turbofishify(mut path: syn::Path) -> syn::Path {
     path
 }
 
+/// The `file:line` for the `data-source-loc` attribute of an element, from
+/// where its tag was written.
+///
+/// Returns `None` if the `source-loc` feature is disabled or the location is
+/// not available.
+pub fn source_loc(tag: &Tag) -> Option<String> {
+    if !cfg!(feature = "source-loc") {
+        return None;
+    }
+    span::source_location(tag.span())
+}
+
 #[cfg(test)]
 mod tests {
     use quote::{quote, ToTokens};
 
-    use super::turbofishify;
+    use super::{kebab_to_lower_camel, turbofishify};
 
     #[test]
     fn add_turbofish() {
@@ -94,4 +400,12 @@ mod tests {
             path.to_token_stream().to_string().replace(' ', "")
         );
     }
+
+    #[test]
+    fn kebab_to_camel() {
+        assert_eq!(kebab_to_lower_camel("help-text"), "helpText");
+        assert_eq!(kebab_to_lower_camel("value-as-number"), "valueAsNumber");
+        assert_eq!(kebab_to_lower_camel("checked"), "checked");
+        assert_eq!(kebab_to_lower_camel("selectedIndex"), "selectedIndex");
+    }
 }
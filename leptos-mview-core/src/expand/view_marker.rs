@@ -0,0 +1,23 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// The `.with_view_marker(...)` call that `leptos::view!` adds to its
+/// `View`, so that devtools and hot reloading can tell which macro call a
+/// view came from.
+///
+/// Returns `None` unless the `nightly` feature is enabled in a debug build, the
+/// same as `leptos::view!`. Release builds get nothing extra.
+pub fn view_marker() -> Option<TokenStream> {
+    if !(cfg!(debug_assertions) && cfg!(feature = "nightly")) {
+        return None;
+    }
+    let marker = view_marker_id(Span::call_site());
+    Some(quote! { .with_view_marker(#marker) })
+}
+
+/// The id that `leptos_hot_reload` gives to a macro call: the file path with
+/// every separator replaced by `-`, followed by the line of the call.
+fn view_marker_id(span: Span) -> String {
+    let file = span.file().replace(['/', '\\'], "-");
+    format!("{file}-{}", span.start().line)
+}
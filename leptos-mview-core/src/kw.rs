@@ -5,3 +5,5 @@ syn::custom_keyword!(on);
 syn::custom_keyword!(prop);
 syn::custom_keyword!(clone);
 syn::custom_keyword!(slot);
+syn::custom_keyword!(children);
+syn::custom_keyword!(blocking);
@@ -2,33 +2,359 @@
 #![allow(
     clippy::option_if_let_else,
     clippy::or_fun_call,
-    clippy::module_name_repetitions
+    clippy::module_name_repetitions,
+    clippy::must_use_candidate
 )]
 
-mod ast;
+pub mod ast;
+pub mod convert;
 mod error_ext;
 mod expand;
 mod kw;
+mod locate;
+mod mixin;
 mod parse;
 mod span;
+pub mod visit;
+
+use std::path::PathBuf;
 
 use ast::{Child, Children};
-use expand::root_children_tokens;
-use proc_macro2::{Span, TokenStream};
-use proc_macro_error2::abort;
+use error_ext::abort;
+use expand::{
+    prelude_trait_imports, record_classes, root_children_tokens, trace_root_child,
+    view_macro_tokens, view_marker,
+};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
 use quote::quote;
 use syn::spanned::Spanned;
 
+/// The inner attributes at the start of the macro input, like `#![debug]`.
+///
+/// Each field is the span of the attribute's name, if it was given.
+#[derive(Default)]
+pub struct InnerAttrs {
+    /// `#![debug]`: show the expansion as a warning.
+    pub debug: Option<Span>,
+    /// `#![into_view]`: convert the view to an `AnyView`.
+    pub into_view: Option<Span>,
+    /// `#![form_props]`: also set `checked`, `value` and `selected` on form
+    /// controls as properties.
+    pub form_props: Option<Span>,
+    /// `#![strict]`: lint the style of attribute names.
+    pub strict: Option<Span>,
+}
+
+impl InnerAttrs {
+    /// Runs the expansion `f` with the attributes that change how elements
+    /// are expanded, like `#![form_props]`.
+    ///
+    /// # Examples
+    /// ```
+    /// use leptos_mview_core::{expand, take_inner_attrs};
+    ///
+    /// let (attrs, input) = take_inner_attrs(quote::quote! {
+    ///     #![form_props] input value={text};
+    /// });
+    /// let expanded = attrs.scoped(|| expand(input)).unwrap();
+    /// # if !cfg!(feature = "delegate") {
+    /// assert!(expanded.to_string().contains("prop"));
+    /// # }
+    /// ```
+    pub fn scoped<T>(&self, f: impl FnOnce() -> T) -> T {
+        expand::with_form_props(self.form_props.is_some(), || {
+            expand::with_strict(self.strict.is_some(), f)
+        })
+    }
+
+    /// Applies the attributes that wrap the expansion.
+    ///
+    /// `#![debug]` is left to the macro, as it needs to show the result of
+    /// this.
+    #[must_use]
+    pub fn apply(&self, expanded: TokenStream) -> TokenStream {
+        match self.into_view {
+            Some(span) => quote::quote_spanned! { span=>
+                ::leptos::prelude::IntoAny::into_any(#expanded)
+            },
+            None => expanded,
+        }
+    }
+}
+
+/// Removes the known inner attributes (`#![debug]`, `#![into_view]`,
+/// `#![form_props]` and `#![strict]`) from the start of the macro input.
+///
+/// The attributes can be given in any order. Parsing stops at the first token
+/// that isn't a known attribute, so anything else is left to the parser.
+#[must_use]
+pub fn take_inner_attrs(input: TokenStream) -> (InnerAttrs, TokenStream) {
+    let mut attrs = InnerAttrs::default();
+    let mut rest = input;
+    loop {
+        let mut tokens = rest.clone().into_iter();
+        let name = match (tokens.next(), tokens.next(), tokens.next()) {
+            (
+                Some(TokenTree::Punct(pound)),
+                Some(TokenTree::Punct(bang)),
+                Some(TokenTree::Group(group)),
+            ) if pound.as_char() == '#'
+                && bang.as_char() == '!'
+                && group.delimiter() == Delimiter::Bracket =>
+            {
+                let mut inner = group.stream().into_iter();
+                match (inner.next(), inner.next()) {
+                    (Some(TokenTree::Ident(ident)), None) => ident,
+                    _ => return (attrs, rest),
+                }
+            }
+            _ => return (attrs, rest),
+        };
+        let field = if name == "debug" {
+            &mut attrs.debug
+        } else if name == "into_view" {
+            &mut attrs.into_view
+        } else if name == "form_props" {
+            &mut attrs.form_props
+        } else if name == "strict" {
+            &mut attrs.strict
+        } else {
+            return (attrs, rest);
+        };
+        *field = Some(name.span());
+        rest = tokens.collect();
+    }
+}
+
+/// Expands the contents of an `mview!` call, without the surrounding braces.
+///
+/// Unlike the macro, this can be called outside of a proc macro, so it can be
+/// used to snapshot test expansions or check the errors of an input.
+///
+/// # Errors
+/// Returns every error found in the input. Only the messages are kept, the
+/// notes and help messages shown by the macro are not included.
+///
+/// # Examples
+/// ```
+/// let expanded = leptos_mview_core::expand(quote::quote! { div.x { "hi" } }).unwrap();
+/// assert!(expanded.to_string().contains("div"));
+///
+/// let errors = leptos_mview_core::expand(quote::quote! { div class=; }).unwrap_err();
+/// assert_eq!(errors[0].to_string(), "expected value after =");
+/// ```
+pub fn expand(input: TokenStream) -> Result<TokenStream, Vec<syn::Error>> {
+    error_ext::collect_errors(|| expand_children(input))
+}
+
+/// Expands the contents of an `mview!` call, reporting any errors through
+/// `proc_macro_error2`.
+///
+/// This must be called inside of a `#[proc_macro_error]` function. Use
+/// [`expand`] everywhere else.
 #[must_use]
 pub fn mview_impl(input: TokenStream) -> TokenStream {
-    // return () in case of any errors, to avoid "unexpected end of macro
-    // invocation" e.g. when assigning `let res = mview! { ... };`
-    proc_macro_error2::set_dummy(quote! { () });
+    emit_expansion(|| expand_children(input), |_| None)
+}
+
+/// Expands a `define_mview_attrs!` call to the group's `macro_rules`.
+///
+/// This must be called inside of a `#[proc_macro_error]` function.
+#[must_use]
+pub fn define_attrs_impl(input: TokenStream) -> TokenStream {
+    let (output, collected) = error_ext::collect_diagnostics(|| mixin::define_attrs(input));
+    for error in collected {
+        error.diagnostic.emit();
+    }
+    output.unwrap_or_default()
+}
+
+/// Expands an element with the attributes of its groups, for the macros
+/// generated by `define_mview_attrs!`.
+///
+/// This must be called inside of a `#[proc_macro_error]` function.
+#[must_use]
+pub fn mixin_impl(input: TokenStream) -> TokenStream {
+    emit_expansion(|| mixin::expand_mixin(input), |_| None)
+}
+
+/// Runs the expansion `f` like [`mview_impl`], adding the note from `note` to
+/// each error.
+fn emit_expansion(
+    f: impl FnOnce() -> syn::Result<TokenStream>,
+    note: impl Fn(&syn::Error) -> Option<String>,
+) -> TokenStream {
+    ast::reset_max_nesting_depth();
+    let (output, collected) = error_ext::collect_diagnostics(f);
+    if collected.is_empty() {
+        return output.unwrap_or_default();
+    }
+    // `proc_macro_error2` replaces the output with the dummy if there are any
+    // errors. if every error was recovered from, the children that did parse
+    // are still expanded, so the rest of the view keeps type checking.
+    // otherwise, return () to avoid "unexpected end of macro invocation" e.g.
+    // when assigning `let res = mview! { ... };`
+    let output = output.unwrap_or_else(|| quote! { () });
+    proc_macro_error2::set_dummy(output.clone());
+    for error in collected {
+        match note(&error.error) {
+            Some(note) => error.diagnostic.note(note).emit(),
+            None => error.diagnostic.emit(),
+        }
+    }
+    output
+}
+
+/// A view read from a file, for `mview_file!`.
+pub struct ViewFile {
+    /// The path as it was written in the macro.
+    path: syn::LitStr,
+    /// The path relative to the crate root.
+    full_path: PathBuf,
+    /// The contents of the file.
+    pub tokens: TokenStream,
+}
+
+impl ViewFile {
+    /// Reads the file at the string literal path in `input`, relative to the
+    /// root of the crate being compiled (`CARGO_MANIFEST_DIR`).
+    ///
+    /// This must be called inside of a `#[proc_macro_error]` function, as it
+    /// aborts if the file can't be read.
+    #[must_use]
+    pub fn read(input: TokenStream) -> Self {
+        // avoid "unexpected end of macro invocation" if the file can't be read
+        proc_macro_error2::set_dummy(quote! { () });
+        let path = match syn::parse2::<syn::LitStr>(input) {
+            Ok(path) => path,
+            Err(e) => abort!(
+                e.span(), "expected a path to a view file";
+                help = r#"e.g. `mview_file!("templates/footer.mview")`"#
+            ),
+        };
+        let root = std::env::var_os("CARGO_MANIFEST_DIR").map_or_else(PathBuf::new, PathBuf::from);
+        let full_path = root.join(path.value());
+        let contents = std::fs::read_to_string(&full_path).unwrap_or_else(|e| {
+            abort!(
+                path.span(),
+                "failed to read `{}`: {}",
+                full_path.display(),
+                e
+            )
+        });
+        // any lexing errors are also reported by the compiler, with the line
+        let tokens = contents
+            .parse()
+            .unwrap_or_else(|e| abort!(path.span(), "failed to parse `{}`: {}", path.value(), e));
+        Self {
+            path,
+            full_path,
+            tokens,
+        }
+    }
+
+    /// Expands `input` like [`mview_impl`], noting the file in every error.
+    ///
+    /// The tokens read from the file have no location of their own, so errors
+    /// point at the whole macro call.
+    ///
+    /// This must be called inside of a `#[proc_macro_error]` function.
+    #[must_use]
+    pub fn expand(&self, input: TokenStream) -> TokenStream {
+        let note = format!("in `{}`", self.path.value());
+        let output = emit_expansion(|| expand_children(input), |_| Some(note.clone()));
+        // cargo only rebuilds when an included file changes
+        let full_path = self.full_path.to_string_lossy();
+        quote! {
+            {
+                const _: &[u8] = ::core::include_bytes!(#full_path);
+                #output
+            }
+        }
+    }
+}
+
+/// A view parsed from a string literal, for `mview_str!`.
+pub struct ViewStr {
+    /// The text of the string.
+    text: String,
+    /// The tokens lexed from the string, all spanned to the string literal.
+    pub tokens: TokenStream,
+}
+
+impl ViewStr {
+    /// Lexes the contents of the string literal in `input`.
+    ///
+    /// This must be called inside of a `#[proc_macro_error]` function, as it
+    /// aborts if the input isn't a string literal or can't be lexed.
+    #[must_use]
+    pub fn parse(input: TokenStream) -> Self {
+        // avoid "unexpected end of macro invocation" if the string is invalid
+        proc_macro_error2::set_dummy(quote! { () });
+        let lit = match syn::parse2::<syn::LitStr>(input.clone()) {
+            Ok(lit) => lit,
+            Err(e) => {
+                // macros in the input are not expanded before the proc macro runs
+                if let Ok(mac) = syn::parse2::<syn::Macro>(input) {
+                    abort!(
+                        mac.span(), "expected a string literal, macros can't be expanded here";
+                        help = "use `mview_file!` to include a view from a file"
+                    );
+                }
+                abort!(e.span(), "expected a string literal")
+            }
+        };
+        let text = lit.value();
+        let tokens = text
+            .parse::<TokenStream>()
+            .unwrap_or_else(|e| abort!(lit.span(), "failed to parse the string: {}", e))
+            .into_iter()
+            .map(|token| span::respan(token, lit.span()))
+            .collect();
+        Self { text, tokens }
+    }
+
+    /// Expands `input` like [`mview_impl`], noting where each error is in the
+    /// string.
+    ///
+    /// `input` should be [`Self::tokens`], without any inner attributes.
+    ///
+    /// This must be called inside of a `#[proc_macro_error]` function.
+    #[must_use]
+    pub fn expand(&self, input: TokenStream) -> TokenStream {
+        let tokens = input.clone();
+        emit_expansion(
+            || expand_children(input),
+            |error| {
+                let (line, column) = locate::locate_error(&tokens, &self.text, &error.to_string())?;
+                Some(format!("at line {line}, column {column} of the string"))
+            },
+        )
+    }
+}
+
+fn expand_children(input: TokenStream) -> syn::Result<TokenStream> {
+    let children = syn::parse2::<Children>(input)?;
+    let imports = prelude_trait_imports();
+    record_classes(&children);
+
+    if cfg!(feature = "delegate") {
+        if let Some(slot) = children.slot_children().next() {
+            abort!(
+                slot.tag().span(),
+                "slots should be inside a parent that supports slots"
+            );
+        }
+        let view = view_macro_tokens(&children);
+        return Ok(quote! {
+            { #imports #view }
+        });
+    }
 
-    let children = match syn::parse2::<Children>(input) {
-        Ok(tree) => tree,
-        Err(e) => return e.to_compile_error(),
-    };
+    // an empty view renders nothing, the same as `view! {}`
+    if children.is_empty() {
+        return Ok(quote! { ::leptos::prelude::View::new(()) });
+    }
 
     // If there's a single top level component, can just expand like
     // div().attr(...).child(...)...
@@ -36,9 +362,14 @@ pub fn mview_impl(input: TokenStream) -> TokenStream {
     if children.len() == 1 {
         let child = children.into_vec().remove(0);
         match child {
-            Child::Node(node) => quote! {
-                { #[allow(unused_braces)] #node }
-            },
+            Child::Node(node) => {
+                // unlike `view!`, a single node isn't marked, so that its type
+                // is the same in debug and release builds
+                let node = trace_root_child(&node).unwrap_or(node);
+                Ok(quote! {
+                    { #imports #[allow(unused_braces)] #node }
+                })
+            }
             Child::Slot(slot, _) => abort!(
                 slot.span(),
                 "slots should be inside a parent that supports slots"
@@ -51,14 +382,649 @@ pub fn mview_impl(input: TokenStream) -> TokenStream {
                 slot.tag().span(),
                 "slots should be inside a parent that supports slots"
             );
-        };
+        }
 
         let fragment = root_children_tokens(children.node_children(), Span::call_site());
-        quote! {
+        let marker = view_marker();
+        Ok(quote! {
             {
+                #imports
                 #[allow(unused_braces)]
-                #fragment
+                #fragment #marker
             }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::expand;
+
+    /// How an element with the tag `name` starts in the expansion, which is a
+    /// `view!` call in delegate mode.
+    fn element_start(name: &str) -> String {
+        if cfg!(feature = "delegate") {
+            format!("< {name}")
+        } else {
+            format!("r#{name} ()")
+        }
+    }
+
+    fn error_messages(input: proc_macro2::TokenStream) -> Vec<String> {
+        expand(input)
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn expands_outside_macro() {
+        let expanded = expand(quote! { span.a("hi") }).unwrap().to_string();
+        assert!(expanded.contains(&element_start("span")));
+        assert!(expanded.contains("\"hi\""));
+    }
+
+    #[test]
+    fn collects_errors() {
+        assert_eq!(
+            error_messages(quote! { div class=; button on:click={}; }),
+            [
+                "expected value after =",
+                "expected an expression inside the braces"
+            ]
+        );
+        // parse errors
+        assert_eq!(error_messages(quote! { % }), [
+            "invalid child: expected literal, block, bracket or element"
+        ]);
+        // aborting errors
+        assert_eq!(error_messages(quote! { slot:Item; }), [
+            "slots should be inside a parent that supports slots"
+        ]);
+    }
+
+    #[test]
+    fn one_error_per_invalid_child() {
+        for input in [
+            quote! { div { span("a") 1; span({b}) } },
+            quote! { div { span("a") a + b span({b}) } },
+            quote! { div { span("a") % %; span({b}) } },
+        ] {
+            let (_, collected) =
+                crate::error_ext::collect_diagnostics(|| super::expand_children(input.clone()));
+            assert_eq!(collected.len(), 1, "{input}");
+        }
+    }
+
+    #[test]
+    fn recovers_from_invalid_children() {
+        let (output, collected) = crate::error_ext::collect_diagnostics(|| {
+            super::expand_children(quote! {
+                span("a")
+                % %
+                div @"x" class={c} { "skipped" }
+                em { "b" % strong("c") }
+                input value={v};
+            })
+        });
+        let errors = collected
+            .iter()
+            .map(|c| c.error.to_string())
+            .collect::<Vec<_>>();
+        let invalid_child = "invalid child: expected literal, block, bracket or element";
+        // stray tokens next to each other are reported once
+        assert_eq!(errors, [
+            invalid_child,
+            "invalid id; ids must be a kebab-case identifier",
+            invalid_child
+        ]);
+        let output = output.unwrap().to_string();
+        assert!(!output.contains("skipped"));
+        for tag in ["span", "em", "strong", "input"] {
+            assert!(output.contains(&element_start(tag)), "{output}");
+        }
+
+        // errors that leave placeholders in the view aren't recovered from
+        let (output, _) = crate::error_ext::collect_diagnostics(|| {
+            super::expand_children(quote! { div class=; })
+        });
+        assert!(output.is_none());
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn textarea_value_without_children() {
+        assert_eq!(error_messages(quote! { textarea value={text} { "hi" } }), [
+            "`value` sets the contents of the textarea, so it can't have children"
+        ]);
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn form_props_only_when_enabled() {
+        let (attrs, input) = crate::take_inner_attrs(quote! {
+            #![form_props] input checked={c} value="v"; div title={t};
+        });
+        let expanded = attrs.scoped(|| expand(input.clone())).unwrap().to_string();
+        assert_eq!(expanded.matches(". prop (").count(), 1);
+        assert!(expanded.contains(". prop (\"checked\""));
+        assert!(!expand(input).unwrap().to_string().contains("prop"));
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn closures_in_inline_handlers() {
+        let errors = error_messages(quote! {
+            button onclick={move |_| count.update(|c| *c += 1)} oninput={|ev| log(ev)};
+            button onclick="history.back()" onclick2={|| ()} title={|| ()};
+        });
+        let mut expected = vec![
+            "`onclick` would set an HTML attribute; use the `on:click` directive to attach a Rust \
+             event handler",
+            "`oninput` would set an HTML attribute; use the `on:input` directive to attach a Rust \
+             event handler",
+        ];
+        // JavaScript strings are only rejected by strict mode
+        if cfg!(feature = "strict") {
+            expected.push("`onclick` is an inline JavaScript handler, not an event listener");
+        }
+        assert_eq!(errors, expected);
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn strict_inline_handlers() {
+        let (attrs, input) = crate::take_inner_attrs(quote! {
+            #![strict]
+            button onclick="go()" oninput={f} onboarding="x";
+            #[allow(strict)] button onclick="go()";
+        });
+        let errors: Vec<_> = attrs
+            .scoped(|| expand(input.clone()))
+            .unwrap_err()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(errors, [
+            "`onclick` is an inline JavaScript handler, not an event listener",
+            "`oninput` is an inline JavaScript handler, not an event listener",
+        ]);
+        // the `strict` feature always checks
+        assert_eq!(expand(input).is_ok(), !cfg!(feature = "strict"));
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn strict_warnings() {
+        let (attrs, input) = crate::take_inner_attrs(quote! {
+            #![strict]
+            div data_id="1" inner_html="x" aria-label="a" {
+                Card max_width=1 max-width=2;
+                #[allow(strict)] span data_id="2";
+            }
+        });
+        let expanded = attrs.scoped(|| expand(input)).unwrap().to_string();
+        assert_eq!(expanded.matches("deprecated").count(), 2);
+        assert!(expanded.contains("use `data-id` instead of `data_id`"));
+        assert!(expanded.contains("use `max_width` instead of `max-width`"));
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn strict_constant_class_and_style() {
+        let (attrs, input) = crate::take_inner_attrs(quote! {
+            #![strict]
+            div class:a=true class:b={false} class:c=[true] class:d=[on()] style:color="red"
+                style:height={h} on:click=[true];
+        });
+        let expanded = attrs.scoped(|| expand(input)).unwrap().to_string();
+        // brackets like `class:c=[true]` are always left alone
+        assert_eq!(expanded.matches("deprecated").count(), 3);
+        assert!(expanded.contains("use the selector shorthand `.a` instead"));
+        assert!(expanded.contains("`class:b` is never added"));
+        assert!(!expanded.contains("`.c`"));
+        assert!(expanded.contains(r#"like `style=\"color: red\"`"#));
+    }
+
+    #[test]
+    fn slash_with_children() {
+        assert_eq!(error_messages(quote! { div / { "a" } }), [
+            "`/` ends an element without children, but a children block follows"
+        ]);
+    }
+
+    #[test]
+    fn component_paths_keep_tag_spans() {
+        use proc_macro2::{TokenStream, TokenTree};
+
+        /// Returns the columns of every `name` ident in the tokens.
+        fn ident_columns(tokens: TokenStream, name: &str, columns: &mut Vec<usize>) {
+            for tt in tokens {
+                match tt {
+                    TokenTree::Ident(ident) if ident == name => {
+                        columns.push(ident.span().start().column);
+                    }
+                    TokenTree::Group(group) => ident_columns(group.stream(), name, columns),
+                    _ => (),
+                }
+            }
+        }
+
+        for (input, name, column) in [
+            ("Counter initial=1;", "Counter", 0),
+            ("ui::Counter initial=1;", "Counter", 4),
+            ("Tabs { slot:Tab label=\"a\"; }", "Tab", 12),
+        ] {
+            let expanded = expand(input.parse().unwrap()).unwrap();
+            let mut columns = Vec::new();
+            ident_columns(expanded, name, &mut columns);
+            assert!(!columns.is_empty(), "{input}");
+            assert!(columns.iter().all(|&c| c == column), "{input}: {columns:?}");
+        }
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn prop_methods_keep_key_spans() {
+        use proc_macro2::{Ident, TokenStream, TokenTree};
+
+        fn find_ident(tokens: TokenStream, name: &str) -> Option<Ident> {
+            tokens.into_iter().find_map(|tt| match tt {
+                TokenTree::Ident(ident) if ident == name => Some(ident),
+                TokenTree::Group(group) => find_ident(group.stream(), name),
+                _ => None,
+            })
+        }
+
+        for input in ["Comp some-attribute=5;", "Comp {some-attribute};"] {
+            let expanded = expand(input.parse().unwrap()).unwrap();
+            let method = find_ident(expanded, "r#some_attribute").unwrap();
+            // only `some`, so that it is the same span as an input token
+            let (start, end) = (method.span().start().column, method.span().end().column);
+            let key_start = input.find("some").unwrap();
+            assert_eq!((start, end), (key_start, key_start + 4), "{input}");
+        }
+    }
+
+    #[test]
+    fn shorthand_value_spans() {
+        use proc_macro2::{TokenStream, TokenTree};
+
+        fn ident_spans(tokens: TokenStream, name: &str, spans: &mut Vec<(usize, usize)>) {
+            for tt in tokens {
+                match tt {
+                    TokenTree::Ident(ident) if ident == name => {
+                        spans.push((ident.span().start().column, ident.span().end().column));
+                    }
+                    TokenTree::Group(group) => ident_spans(group.stream(), name, spans),
+                    _ => (),
+                }
+            }
+        }
+
+        for input in ["Comp {some-attribute};", "div {some-attribute};"] {
+            let expanded = expand(input.parse().unwrap()).unwrap();
+            let mut spans = Vec::new();
+            ident_spans(expanded, "r#some_attribute", &mut spans);
+            let braces = (input.find('{').unwrap(), input.find('}').unwrap() + 1);
+            // the value comes last, after any prop method
+            assert_eq!(spans.last(), Some(&braces), "{input}");
+        }
+
+        // without a `-`, the value is the same span as the ident in the braces
+        for input in [
+            "Comp {value};",
+            "div {value};",
+            "input prop:{value};",
+            "div class:{value};",
+            "div style:{value};",
+        ] {
+            let expanded = expand(input.parse().unwrap()).unwrap();
+            let mut spans = Vec::new();
+            ident_spans(expanded, "r#value", &mut spans);
+            let ident = input.find("value").unwrap();
+            assert_eq!(spans.last(), Some(&(ident, ident + 5)), "{input}");
+        }
+    }
+
+    #[test]
+    fn joins_spans_with_nightly() {
+        let input = "div #x #y-z;".parse().unwrap();
+        let columns = |errors: Vec<syn::Error>| {
+            errors
+                .iter()
+                .map(|e| (e.span().start().column, e.span().end().column))
+                .collect::<Vec<_>>()
+        };
+        let expected = if cfg!(feature = "nightly") {
+            // `#y-z`
+            [(7, 11)]
+        } else {
+            // `#`
+            [(7, 8)]
+        };
+        assert_eq!(columns(expand(input).unwrap_err()), expected);
+    }
+
+    #[test]
+    fn view_markers_with_nightly() {
+        let marked = |input| {
+            expand(input)
+                .unwrap()
+                .to_string()
+                .contains("with_view_marker")
+        };
+        let nightly = cfg!(all(debug_assertions, feature = "nightly"));
+        assert_eq!(marked(quote! { div; span; }), nightly);
+        // a single node keeps its type, so it isn't marked
+        assert!(!marked(quote! { div; }));
+        assert!(!marked(quote! { Comp; }));
+        assert!(!marked(quote! { {value} }));
+        assert!(!marked(quote! { "text" }));
+    }
+
+    #[test]
+    fn attr_groups_call_the_first_group() {
+        let expanded = expand(quote! { .x @@ui::a key="v" @@b { "hi" } })
+            .unwrap()
+            .to_string();
+        // the rest of the element, including other groups, goes to the first
+        // group's macro
+        assert!(expanded.contains(r#"ui :: a ! { div . x key = "v" @ @ b { "hi" } }"#));
+
+        let errors = error_messages(quote! { div { slot:Item @@a; } });
+        assert!(errors.contains(&"attribute groups can't be used on slots".to_owned()));
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn slot_children_on_elements() {
+        let errors = error_messages(quote! { sl-card { slot:div("footer") } });
+        assert_eq!(errors, ["slots are only supported on components"]);
+        // a plain `slot` attribute is fine
+        assert!(expand(quote! { sl-card { div slot="footer"; slot name="x"; } }).is_ok());
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn dedent_inside_pre() {
+        let errors = error_messages(quote! {
+            pre { code { span { #[dedent] "a" } } }
+            textarea { #[dedent] "b" }
+            div { #[dedent] "c" }
+        });
+        assert_eq!(errors, [
+            "`#[dedent]` cannot be used inside `pre`",
+            "`#[dedent]` cannot be used inside `textarea`"
+        ]);
+        // a nested `pre` only reports its own children once
+        let errors = error_messages(quote! { pre { pre { #[dedent] "a" } } });
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn source_locs_need_span_locations() {
+        // outside of a proc macro the file is unknown, so nothing is added even
+        // with the `source-loc` feature
+        let expanded = expand("div { span; }".parse().unwrap()).unwrap();
+        assert!(!expanded.to_string().contains("data-source-loc"));
+    }
+
+    #[test]
+    fn unterminated_element_points_at_end() {
+        let input: proc_macro2::TokenStream = r#"div { "a" input.x type="text" }"#.parse().unwrap();
+        let errors = expand(input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "unterminated element");
+        // the `"text"` at the end, where the `;` goes
+        assert_eq!(errors[0].span().start().column, 23);
+    }
+
+    #[test]
+    fn stray_dot() {
+        let input: proc_macro2::TokenStream = r#"div.a title="t" . ;"#.parse().unwrap();
+        let errors = expand(input).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "expected a class name after `.`");
+        assert_eq!(errors[0].span().start().column, 16);
+    }
+
+    #[test]
+    fn await_child() {
+        let expanded = expand(quote! { await blocking {fut} |x| { {x} } })
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains(":: leptos :: prelude :: Await"));
+        if cfg!(feature = "delegate") {
+            assert!(expanded.contains("future = { fut } blocking = true let (x)"));
+        } else {
+            assert!(expanded.contains(". r#future ({ fut })"));
+            assert!(expanded.contains(". r#blocking (true)"));
+        }
+        assert_eq!(error_messages(quote! { await {fut} p; }), [
+            "expected closure arguments for the resolved value, like `|data| { ... }`"
+        ]);
+        let unbraced = "expected the future in braces: `await {fut} |x| { .. }`";
+        assert_eq!(error_messages(quote! { p { await fut |x| { {x} } } }), [
+            unbraced
+        ]);
+        assert_eq!(
+            error_messages(quote! { await blocking load.get() |x| { {x} } }),
+            [unbraced]
+        );
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn custom_element_props_are_camel_case() {
+        let expanded = expand(quote! {
+            sl-input prop:help-text={x} prop:"data-raw"={y};
+            input prop:value-as-number={z};
+        })
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains(r#"prop ("helpText" , { x })"#));
+        assert!(expanded.contains(r#"prop ("data-raw" , { y })"#));
+        // only custom elements are converted
+        assert!(expanded.contains(r#"prop ("value-as-number" , { z })"#));
+    }
+
+    // delegate mode expands to a `view!` call instead
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn prop_values_are_passed_on() {
+        // properties only exist on the DOM node, where leptos keeps them up to
+        // date with a signal or closure, so these must not be read here
+        let expanded = expand(quote! {
+            input prop:value={value} prop:checked=[value.get().is_empty()];
+            Input prop:value={value} prop:"checked"=[done()];
+        })
+        .unwrap()
+        .to_string();
+        assert!(
+            expanded.contains(r#". prop ("value" , { value })"#),
+            "{expanded}"
+        );
+        assert!(
+            expanded.contains(r#". prop ("checked" , move || { value . get () . is_empty () })"#),
+            "{expanded}"
+        );
+        assert!(
+            expanded.contains(r#"property :: prop ("value" , { value })"#),
+            "{expanded}"
+        );
+        assert!(
+            expanded.contains(r#"property :: prop ("checked" , move || { done () })"#),
+            "{expanded}"
+        );
+    }
+
+    // delegate mode expands to a `view!` call instead
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn component_use_directives() {
+        // directives only run in the browser, once they have been added to the
+        // component's root element by `add_any_attr`
+        let expanded = expand(quote! { Comp use:no_arg_dir; Comp use:arg_dir=300; })
+            .unwrap()
+            .to_string();
+        let add_directive =
+            ". add_any_attr ((:: leptos :: tachys :: html :: directive :: directive";
+        assert!(
+            expanded.contains(&format!(
+                "{add_directive} (r#no_arg_dir , {{ # [allow (clippy :: useless_conversion , \
+                 clippy :: let_unit_value)] let param = () . into () ; param }}) ,))"
+            )),
+            "{expanded}"
+        );
+        assert!(
+            expanded.contains(&format!(
+                "{add_directive} (r#arg_dir , :: std :: convert :: Into :: into (300)) ,))"
+            )),
+            "{expanded}"
+        );
+    }
+
+    #[test]
+    fn async_block_values() {
+        let expanded = expand(quote! { Comp future=async move { load().await } other=async {}; })
+            .unwrap()
+            .to_string();
+        if cfg!(feature = "delegate") {
+            assert!(expanded.contains("future = { async move { load () . await } }"));
+            assert!(expanded.contains("other = { async { } }"));
+        } else {
+            assert!(expanded.contains("r#future ({ async move { load () . await } })"));
+            assert!(expanded.contains("r#other ({ async { } })"));
+        }
+        assert_eq!(error_messages(quote! { Comp future=async; }), [
+            "expected value after ="
+        ]);
+    }
+
+    #[test]
+    fn control_flow_values() {
+        let expanded = expand(quote! {
+            div class=if dark { "dark" } else if dim { "dim" } else { "light" }
+                title=match s { 0 => "none", _ => "some" };
+        })
+        .unwrap()
+        .to_string();
+        assert!(
+            expanded.contains(r#"{ if dark { "dark" } else if dim { "dim" } else { "light" } }"#)
+        );
+        assert!(expanded.contains(r#"{ match s { 0 => "none" , _ => "some" } }"#));
+        assert_eq!(error_messages(quote! { div class=if dark; }), [
+            "expected value after ="
+        ]);
+    }
+
+    #[test]
+    fn closure_values() {
+        let expanded = expand(quote! {
+            button on:click=move |_| count.update(|c| *c += 1) class="btn";
+        })
+        .unwrap()
+        .to_string();
+        assert!(expanded.contains("{ move | _ | count . update (| c | * c += 1) }"));
+        assert!(expanded.contains("\"btn\""));
+        // `/` and `>` end the closure before the terminator or child shorthand
+        let expanded = expand(quote! { button on:click=move |_| go() / })
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("{ move | _ | go () }"), "{expanded}");
+        let expanded = expand(quote! { div on:click=|_| go() > span("x") })
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("{ | _ | go () }"), "{expanded}");
+        assert!(expanded.contains("\"x\""), "{expanded}");
+        // but are still operators before a number or parens
+        let expanded = expand(quote! { input on:input=move |_| set(a / 2 > (b - 1)) / })
+            .unwrap()
+            .to_string();
+        assert!(expanded.contains("set (a / 2 > (b - 1))"), "{expanded}");
+        let expanded = expand(quote! { input on:input=move |_| a / 2 > (b - 1) / })
+            .unwrap()
+            .to_string();
+        assert!(
+            expanded.contains("{ move | _ | a / 2 > (b - 1) }"),
+            "{expanded}"
+        );
+        // the closure is left unparsed, so it is taken as the children's arguments
+        assert_eq!(error_messages(quote! { button on:click=|_| return; }), [
+            "expected value after =",
+            "expected children block after closure arguments"
+        ]);
+    }
+
+    #[test]
+    fn kebab_directive_shorthand() {
+        for input in [
+            quote! { div class:{is-red} style:{font-size}; },
+            quote! { Comp class:{is-red} style:{font-size}; },
+        ] {
+            let expanded = expand(input).unwrap().to_string();
+            let (is_red, font_size) = if cfg!(feature = "delegate") {
+                (
+                    "class : is - red = { r#is_red }",
+                    "style : font - size = { r#font_size }",
+                )
+            } else {
+                (
+                    "(\"is-red\" , { r#is_red })",
+                    "(\"font-size\" , { r#font_size })",
+                )
+            };
+            assert!(expanded.contains(is_red), "{expanded}");
+            assert!(expanded.contains(font_size), "{expanded}");
+        }
+    }
+
+    // only checked when not delegating to `view!`
+    #[cfg(not(feature = "delegate"))]
+    #[test]
+    fn dynamic_inner_html_needs_opt_in() {
+        assert_eq!(error_messages(quote! { div inner_html={html}; }), [
+            "`inner_html` only accepts a string literal"
+        ]);
+        assert!(expand(quote! { div inner_html="<b>hi</b>"; }).is_ok());
+        assert!(expand(quote! { div unsafe_inner_html={html}; }).is_ok());
+    }
+
+    #[cfg(feature = "class-macro")]
+    #[test]
+    fn class_macro() {
+        let expand_with_css = |input| {
+            crate::expand::with_class_macro(syn::parse_quote!(css), || expand(input))
+                .unwrap()
+                .to_string()
+        };
+        let expanded = expand_with_css(quote! {
+            div.card class="a b" class:active={x} class={dynamic} attr:class="raw";
+        });
+        for class in ["card", "a", "b", "active"] {
+            assert!(
+                expanded.contains(&format!("css ! (\"{class}\")")),
+                "{expanded}"
+            );
         }
+        assert!(expanded.contains("{ dynamic }"));
+        assert!(!expanded.contains("css ! (\"raw\")"));
+        // classes on components are wrapped too
+        let expanded = expand_with_css(quote! { Comp.card; });
+        assert!(expanded.contains("css ! (\"card\")"));
     }
 }
@@ -0,0 +1,165 @@
+//! Finding where an error is in the text of a `mview_str!` string.
+//!
+//! The tokens lexed from a string all have the same span, so the error itself
+//! can't say where it is. Instead, the input is expanded again with fewer and
+//! fewer tokens, to find the first token that causes the error. That token is
+//! then found in the text.
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+
+/// Returns the line and column (both starting at 1) in `text` of the error
+/// with `message`, where `tokens` were lexed from `text`.
+///
+/// This is a best guess: the error is assumed to be caused by the first token
+/// that makes it appear, and that token is found by searching through the
+/// text in order.
+pub fn locate_error(tokens: &TokenStream, text: &str, message: &str) -> Option<(usize, usize)> {
+    let has_error = |input: TokenStream| {
+        crate::expand(input).is_err_and(|errors| errors.iter().any(|e| e.to_string() == message))
+    };
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+    let path = error_token_path(&tokens, &|prefix| prefix, &has_error)?;
+    let offset = token_offset(&tokens, &path, text, &mut 0)?;
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before[before.rfind('\n').map_or(0, |i| i + 1)..]
+        .chars()
+        .count()
+        + 1;
+    Some((line, column))
+}
+
+/// Returns the index of the first token that causes the error, followed by
+/// the index inside of it if the token is a group.
+///
+/// `wrap` puts a prefix of `tokens` back into the tokens of the parent groups.
+fn error_token_path(
+    tokens: &[TokenTree],
+    wrap: &dyn Fn(TokenStream) -> TokenStream,
+    has_error: &dyn Fn(TokenStream) -> bool,
+) -> Option<Vec<usize>> {
+    let len =
+        (0..=tokens.len()).find(|&len| has_error(wrap(tokens[..len].iter().cloned().collect())))?;
+    let index = len.checked_sub(1)?;
+    let mut path = vec![index];
+    if let TokenTree::Group(group) = &tokens[index] {
+        let inner = group.stream().into_iter().collect::<Vec<_>>();
+        let wrap_inner = |prefix| {
+            let mut truncated = Group::new(group.delimiter(), prefix);
+            truncated.set_span(group.span());
+            let mut outer = tokens[..index].iter().cloned().collect::<TokenStream>();
+            outer.extend([TokenTree::Group(truncated)]);
+            wrap(outer)
+        };
+        // if the empty group already has the error, it's about the group itself
+        path.extend(error_token_path(&inner, &wrap_inner, has_error).unwrap_or_default());
+    }
+    Some(path)
+}
+
+/// Returns the byte offset in `text` of the token at `path`, searching from
+/// `pos` onwards.
+fn token_offset(
+    tokens: &[TokenTree],
+    path: &[usize],
+    text: &str,
+    pos: &mut usize,
+) -> Option<usize> {
+    let (&index, rest) = path.split_first()?;
+    for token in &tokens[..index] {
+        skip_token(token, text, pos)?;
+    }
+    match (&tokens[index], rest.is_empty()) {
+        (TokenTree::Group(group), false) => {
+            if let Some((open, _)) = delimiters(group.delimiter()) {
+                find(open, text, pos)?;
+            }
+            let inner = group.stream().into_iter().collect::<Vec<_>>();
+            token_offset(&inner, rest, text, pos)
+        }
+        (token, _) => {
+            let start = match token {
+                TokenTree::Group(group) => delimiters(group.delimiter()).map(|(open, _)| open),
+                _ => None,
+            }
+            .map_or_else(|| token.to_string(), str::to_owned);
+            find(&start, text, pos)
+        }
+    }
+}
+
+/// Moves `pos` past the token in `text`.
+fn skip_token(token: &TokenTree, text: &str, pos: &mut usize) -> Option<()> {
+    match token {
+        TokenTree::Group(group) => {
+            let delimiters = delimiters(group.delimiter());
+            if let Some((open, _)) = delimiters {
+                find(open, text, pos)?;
+            }
+            for token in group.stream() {
+                skip_token(&token, text, pos)?;
+            }
+            if let Some((_, close)) = delimiters {
+                find(close, text, pos)?;
+            }
+        }
+        token => {
+            find(&token.to_string(), text, pos)?;
+        }
+    }
+    Some(())
+}
+
+/// Returns the start of the next `needle` in `text` after `pos`, and moves
+/// `pos` to the end of it.
+fn find(needle: &str, text: &str, pos: &mut usize) -> Option<usize> {
+    let start = *pos + text[*pos..].find(needle)?;
+    *pos = start + needle.len();
+    Some(start)
+}
+
+const fn delimiters(delimiter: Delimiter) -> Option<(&'static str, &'static str)> {
+    match delimiter {
+        Delimiter::Parenthesis => Some(("(", ")")),
+        Delimiter::Brace => Some(("{", "}")),
+        Delimiter::Bracket => Some(("[", "]")),
+        Delimiter::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::locate_error;
+
+    fn locate(text: &str, message: &str) -> Option<(usize, usize)> {
+        locate_error(&text.parse().unwrap(), text, message)
+    }
+
+    #[test]
+    fn locates_errors() {
+        assert_eq!(
+            locate("div class=;", "expected value after ="),
+            Some((1, 10))
+        );
+        assert_eq!(
+            locate(
+                "div {\n    \"a\"\n    span class=;\n}",
+                "expected value after ="
+            ),
+            Some((3, 15))
+        );
+        // inside of nested groups
+        assert_eq!(
+            locate(
+                "main { div(p { button on:click={}; }) }",
+                "expected an expression inside the braces"
+            ),
+            Some((1, 32))
+        );
+    }
+
+    #[test]
+    fn unknown_errors_are_not_located() {
+        assert_eq!(locate("div;", "not an error"), None);
+    }
+}
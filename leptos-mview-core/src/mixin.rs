@@ -0,0 +1,136 @@
+//! Parsing and expanding `define_mview_attrs!` and `__mixin!`.
+//!
+//! See `expand/mixin.rs` for how the two fit together.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+
+use crate::{
+    ast::{Attrs, Child, Children, Element, NodeChild},
+    expand::{merge_mixin_attrs, mixin_call_tokens, prelude_trait_imports, view_macro_tokens},
+    parse,
+};
+
+/// The input to `define_mview_attrs!`: a name and a block of attributes.
+///
+/// ```text
+/// pub(crate) clickable { role="button" tabindex=0 }
+/// ```
+struct AttrGroup {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: syn::Ident,
+    contents: TokenStream,
+}
+
+impl Parse for AttrGroup {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let (_, contents) = parse::braced_tokens(input)?;
+        if !input.is_empty() {
+            return Err(input.error("expected only one group of attributes"));
+        }
+        Ok(Self {
+            attrs,
+            vis,
+            name,
+            contents,
+        })
+    }
+}
+
+/// Only attributes are allowed in a group: any selectors or children are
+/// reported.
+fn parse_group_attrs(input: ParseStream) -> syn::Result<Attrs> {
+    let attrs = Attrs::parse(input)?;
+    if !input.is_empty() {
+        return Err(input.error("expected an attribute"));
+    }
+    Ok(attrs)
+}
+
+/// Expands `define_mview_attrs!` to a `macro_rules` that passes the group's
+/// attributes on to `__mixin!`.
+pub fn define_attrs(input: TokenStream) -> syn::Result<TokenStream> {
+    let AttrGroup {
+        attrs,
+        vis,
+        name,
+        contents,
+    } = syn::parse2(input)?;
+    syn::parse::Parser::parse2(parse_group_attrs, contents.clone())?;
+
+    // a `macro_rules` can only be used by path once it is re-exported
+    let export = match vis {
+        syn::Visibility::Inherited => None,
+        vis => Some(quote! { #vis use #name; }),
+    };
+    Ok(quote! {
+        #(#attrs)*
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            ($($element:tt)*) => {
+                ::leptos_mview::__mixin! { [#contents] $($element)* }
+            };
+        }
+        #export
+    })
+}
+
+/// The input to `__mixin!`: the attributes of each resolved group, latest
+/// first, then the element.
+struct MixinInput {
+    groups: Vec<TokenStream>,
+    element: Element,
+}
+
+impl Parse for MixinInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut groups = Vec::new();
+        while input.peek(syn::token::Bracket) {
+            let (_, group) = parse::bracketed_tokens(input)?;
+            groups.push(group);
+        }
+        let element = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("expected a single element"));
+        }
+        Ok(Self { groups, element })
+    }
+}
+
+/// Expands `__mixin!`, either calling the element's next group or expanding
+/// the element with all of the groups' attributes.
+pub fn expand_mixin(input: TokenStream) -> syn::Result<TokenStream> {
+    let MixinInput {
+        groups,
+        mut element,
+    } = syn::parse2(input)?;
+
+    let resolved: TokenStream = groups.iter().map(|group| quote! { [#group] }).collect();
+    if let Some(call) = mixin_call_tokens(&element, &resolved) {
+        return Ok(call);
+    }
+
+    let groups = groups
+        .into_iter()
+        .rev()
+        .map(|group| syn::parse::Parser::parse2(parse_group_attrs, group))
+        .collect::<syn::Result<_>>()?;
+    merge_mixin_attrs(&mut element, groups);
+
+    let imports = prelude_trait_imports();
+    if cfg!(feature = "delegate") {
+        let children = Children::new(vec![Child::Node(NodeChild::Element(element))]);
+        let view = view_macro_tokens(&children);
+        return Ok(quote! {
+            { #imports #view }
+        });
+    }
+    Ok(quote! {
+        { #imports #[allow(unused_braces)] #element }
+    })
+}
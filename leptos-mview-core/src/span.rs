@@ -1,14 +1,50 @@
 //! Mini helper functions for working with spans.
 
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
 use quote::quote;
 
 /// Tries to join two spans together, returning just the first span if
 /// unable to join.
 ///
-/// The spans are unable to join if the user is not on nightly or the spans
-/// are in different files.
-pub fn join(s1: Span, s2: Span) -> Span { s1.join(s2).unwrap_or(s1) }
+/// Spans are only joined with the `nightly` feature, as joining needs a
+/// nightly compiler. They are also unable to join if the spans are in
+/// different files.
+pub fn join(s1: Span, s2: Span) -> Span {
+    if cfg!(feature = "nightly") {
+        s1.join(s2).unwrap_or(s1)
+    } else {
+        s1
+    }
+}
+
+/// Returns how many lines `after` starts below where `before` ends, or `None`
+/// if the line numbers are not available.
+///
+/// Line numbers are only available from Rust 1.88.
+pub fn lines_between(before: Span, after: Span) -> Option<usize> {
+    let (end, start) = (before.end().line, after.start().line);
+    (end != 0 && start != 0).then(|| start.saturating_sub(end))
+}
+
+/// Returns the `file:line` that `span` starts at, or `None` if the location is
+/// not available.
+///
+/// Locations are only available inside of a proc macro from Rust 1.88.
+pub fn source_location(span: Span) -> Option<String> {
+    let (file, line) = (span.file(), span.start().line);
+    // unknown files are described like `<token stream>`
+    (line != 0 && !file.starts_with('<')).then(|| format!("{file}:{line}"))
+}
+
+/// Sets the span of `token` and every token inside of it to `span`.
+pub fn respan(mut token: TokenTree, span: Span) -> TokenTree {
+    if let TokenTree::Group(group) = &token {
+        let stream = group.stream().into_iter().map(|token| respan(token, span));
+        token = TokenTree::Group(Group::new(group.delimiter(), stream.collect()));
+    }
+    token.set_span(span);
+    token
+}
 
 /// Gives each span of `spans` the color of a variable.
 ///
@@ -18,6 +54,9 @@ pub fn join(s1: Span, s2: Span) -> Span { s1.join(s2).unwrap_or(s1) }
 pub fn color_all(spans: impl IntoIterator<Item = Span>) -> impl Iterator<Item = TokenStream> {
     spans.into_iter().map(|span| {
         let ident = syn::Ident::new("__x", span);
-        quote! { let #ident = (); }
+        quote! {
+            #[allow(clippy::no_effect_underscore_binding)]
+            let #ident = ();
+        }
     })
 }
@@ -0,0 +1,300 @@
+//! Traversal of the [`ast`](crate::ast).
+//!
+//! Implement [`Visitor`] (or [`VisitMut`] to change the tree) and override the
+//! methods for the nodes you are interested in. Each method defaults to the
+//! matching `walk_*` function, which visits all of the node's children. Call
+//! the `walk_*` function from an overridden method to keep going deeper.
+//!
+//! # Examples
+//! ```
+//! use leptos_mview_core::{
+//!     ast::{self, attribute::directive::Directive},
+//!     visit::{self, Visitor},
+//! };
+//!
+//! /// Finds `style:` directives with a hex color.
+//! struct HexColors(usize);
+//!
+//! impl<'ast> Visitor<'ast> for HexColors {
+//!     fn visit_directive(&mut self, dir: &'ast Directive) {
+//!         if dir.dir() == "style" && dir.value().is_some_and(|v| v.to_string().contains('#')) {
+//!             self.0 += 1;
+//!         }
+//!         visit::walk_directive(self, dir);
+//!     }
+//! }
+//!
+//! let tree = ast::parse(quote::quote! {
+//!     div style:color="#fff" { span style:color="white"; }
+//! })
+//! .unwrap();
+//! let mut hex = HexColors(0);
+//! hex.visit_children(&tree);
+//! assert_eq!(hex.0, 1);
+//! ```
+
+use crate::ast::{
+    attribute::{
+        directive::Directive, kv::KvAttr, selector::SelectorShorthand, spread_attrs::SpreadAttr,
+    },
+    Attr, Child, Children, Doctype, Element, MarkedStr, NodeChild, Tag, Value,
+};
+
+/// Read-only traversal of the AST.
+///
+/// See the [module documentation](self) for more details.
+pub trait Visitor<'ast> {
+    fn visit_children(&mut self, children: &'ast Children) { walk_children(self, children); }
+
+    fn visit_child(&mut self, child: &'ast Child) { walk_child(self, child); }
+
+    fn visit_node_child(&mut self, node: &'ast NodeChild) { walk_node_child(self, node); }
+
+    /// Visits an element after `slot:`. Defaults to
+    /// [`visit_element`](Self::visit_element).
+    fn visit_slot(&mut self, slot: &'ast Element) { self.visit_element(slot); }
+
+    fn visit_element(&mut self, element: &'ast Element) { walk_element(self, element); }
+
+    fn visit_tag(&mut self, _tag: &'ast Tag) {}
+
+    fn visit_selector(&mut self, _selector: &'ast SelectorShorthand) {}
+
+    fn visit_attr(&mut self, attr: &'ast Attr) { walk_attr(self, attr); }
+
+    fn visit_kv_attr(&mut self, kv: &'ast KvAttr) { walk_kv_attr(self, kv); }
+
+    fn visit_directive(&mut self, dir: &'ast Directive) { walk_directive(self, dir); }
+
+    fn visit_spread_attr(&mut self, _spread: &'ast SpreadAttr) {}
+
+    fn visit_value(&mut self, _value: &'ast Value) {}
+
+    fn visit_doctype(&mut self, _doctype: &'ast Doctype) {}
+
+    fn visit_marked_str(&mut self, _marked: &'ast MarkedStr) {}
+}
+
+pub fn walk_children<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, children: &'ast Children) {
+    for child in children.iter() {
+        v.visit_child(child);
+    }
+}
+
+pub fn walk_child<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, child: &'ast Child) {
+    match child {
+        Child::Node(node) => v.visit_node_child(node),
+        Child::Slot(_, slot) => v.visit_slot(slot),
+    }
+}
+
+pub fn walk_node_child<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, node: &'ast NodeChild) {
+    match node {
+        NodeChild::Value(value) => v.visit_value(value),
+        NodeChild::Element(element) => v.visit_element(element),
+        NodeChild::Doctype(doctype) => v.visit_doctype(doctype),
+        NodeChild::MarkedStr(marked) => v.visit_marked_str(marked),
+    }
+}
+
+/// Visits the tag, selectors, attributes and children of the element, in that
+/// order.
+pub fn walk_element<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, element: &'ast Element) {
+    v.visit_tag(element.tag());
+    for selector in element.selectors().iter() {
+        v.visit_selector(selector);
+    }
+    for attr in element.attrs().iter() {
+        v.visit_attr(attr);
+    }
+    if let Some(children) = element.children() {
+        v.visit_children(children);
+    }
+}
+
+pub fn walk_attr<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, attr: &'ast Attr) {
+    match attr {
+        Attr::Kv(kv) => v.visit_kv_attr(kv),
+        Attr::Directive(dir) => v.visit_directive(dir),
+        Attr::Spread(spread) => v.visit_spread_attr(spread),
+    }
+}
+
+pub fn walk_kv_attr<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, kv: &'ast KvAttr) {
+    v.visit_value(kv.value());
+}
+
+pub fn walk_directive<'ast, V: Visitor<'ast> + ?Sized>(v: &mut V, dir: &'ast Directive) {
+    if let Some(value) = dir.value() {
+        v.visit_value(value);
+    }
+}
+
+/// Mutable traversal of the AST, for rewriting a tree.
+///
+/// See the [module documentation](self) for more details.
+pub trait VisitMut {
+    fn visit_children_mut(&mut self, children: &mut Children) { walk_children_mut(self, children); }
+
+    fn visit_child_mut(&mut self, child: &mut Child) { walk_child_mut(self, child); }
+
+    fn visit_node_child_mut(&mut self, node: &mut NodeChild) { walk_node_child_mut(self, node); }
+
+    /// Visits an element after `slot:`. Defaults to
+    /// [`visit_element_mut`](Self::visit_element_mut).
+    fn visit_slot_mut(&mut self, slot: &mut Element) { self.visit_element_mut(slot); }
+
+    fn visit_element_mut(&mut self, element: &mut Element) { walk_element_mut(self, element); }
+
+    fn visit_tag_mut(&mut self, _tag: &mut Tag) {}
+
+    fn visit_selector_mut(&mut self, _selector: &mut SelectorShorthand) {}
+
+    fn visit_attr_mut(&mut self, attr: &mut Attr) { walk_attr_mut(self, attr); }
+
+    fn visit_kv_attr_mut(&mut self, kv: &mut KvAttr) { walk_kv_attr_mut(self, kv); }
+
+    fn visit_directive_mut(&mut self, dir: &mut Directive) { walk_directive_mut(self, dir); }
+
+    fn visit_spread_attr_mut(&mut self, _spread: &mut SpreadAttr) {}
+
+    fn visit_value_mut(&mut self, _value: &mut Value) {}
+
+    fn visit_doctype_mut(&mut self, _doctype: &mut Doctype) {}
+
+    fn visit_marked_str_mut(&mut self, _marked: &mut MarkedStr) {}
+}
+
+pub fn walk_children_mut<V: VisitMut + ?Sized>(v: &mut V, children: &mut Children) {
+    for child in children.iter_mut() {
+        v.visit_child_mut(child);
+    }
+}
+
+pub fn walk_child_mut<V: VisitMut + ?Sized>(v: &mut V, child: &mut Child) {
+    match child {
+        Child::Node(node) => v.visit_node_child_mut(node),
+        Child::Slot(_, slot) => v.visit_slot_mut(slot),
+    }
+}
+
+pub fn walk_node_child_mut<V: VisitMut + ?Sized>(v: &mut V, node: &mut NodeChild) {
+    match node {
+        NodeChild::Value(value) => v.visit_value_mut(value),
+        NodeChild::Element(element) => v.visit_element_mut(element),
+        NodeChild::Doctype(doctype) => v.visit_doctype_mut(doctype),
+        NodeChild::MarkedStr(marked) => v.visit_marked_str_mut(marked),
+    }
+}
+
+/// Visits the tag, selectors, attributes and children of the element, in that
+/// order.
+pub fn walk_element_mut<V: VisitMut + ?Sized>(v: &mut V, element: &mut Element) {
+    v.visit_tag_mut(element.tag_mut());
+    for selector in element.selectors_mut().iter_mut() {
+        v.visit_selector_mut(selector);
+    }
+    for attr in element.attrs_mut().iter_mut() {
+        v.visit_attr_mut(attr);
+    }
+    if let Some(children) = element.children_mut() {
+        v.visit_children_mut(children);
+    }
+}
+
+pub fn walk_attr_mut<V: VisitMut + ?Sized>(v: &mut V, attr: &mut Attr) {
+    match attr {
+        Attr::Kv(kv) => v.visit_kv_attr_mut(kv),
+        Attr::Directive(dir) => v.visit_directive_mut(dir),
+        Attr::Spread(spread) => v.visit_spread_attr_mut(spread),
+    }
+}
+
+pub fn walk_kv_attr_mut<V: VisitMut + ?Sized>(v: &mut V, kv: &mut KvAttr) {
+    v.visit_value_mut(kv.value_mut());
+}
+
+pub fn walk_directive_mut<V: VisitMut + ?Sized>(v: &mut V, dir: &mut Directive) {
+    if let Some(value) = dir.value_mut() {
+        v.visit_value_mut(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::{walk_element, VisitMut, Visitor};
+    use crate::ast::{self, Element, Value};
+
+    #[derive(Default)]
+    struct Counts {
+        elements: usize,
+        slots: usize,
+        attrs: usize,
+        values: usize,
+    }
+
+    impl<'ast> Visitor<'ast> for Counts {
+        fn visit_element(&mut self, element: &'ast Element) {
+            self.elements += 1;
+            walk_element(self, element);
+        }
+
+        fn visit_slot(&mut self, slot: &'ast Element) {
+            self.slots += 1;
+            walk_element(self, slot);
+        }
+
+        fn visit_attr(&mut self, attr: &'ast ast::Attr) {
+            self.attrs += 1;
+            super::walk_attr(self, attr);
+        }
+
+        fn visit_value(&mut self, _value: &'ast Value) { self.values += 1; }
+    }
+
+    #[test]
+    fn visits_everything() {
+        let tree = ast::parse(quote! {
+            div.a class:b={b} data-x=1 {
+                "text"
+                List { slot:Item name="a"; }
+                span on:click={f} {..attrs};
+            }
+            [count()]
+        })
+        .unwrap();
+        let mut counts = Counts::default();
+        counts.visit_children(&tree);
+        assert_eq!(counts.elements, 3);
+        assert_eq!(counts.slots, 1);
+        assert_eq!(counts.attrs, 5);
+        // b, 1, "text", "a", f, count()
+        assert_eq!(counts.values, 6);
+    }
+
+    /// Replaces every literal with `"x"`.
+    struct ReplaceLits;
+
+    impl VisitMut for ReplaceLits {
+        fn visit_value_mut(&mut self, value: &mut Value) {
+            if let Value::Lit(lit) = value {
+                *lit = syn::parse_quote!("x");
+            }
+        }
+    }
+
+    #[test]
+    fn visit_mut() {
+        let mut tree = ast::parse(quote! {
+            div data-x=1 { "a" span title="b" { {c} } }
+        })
+        .unwrap();
+        ReplaceLits.visit_children_mut(&mut tree);
+        assert_eq!(
+            tree.to_string(),
+            r#"div data-x="x" { "x" span title="x" { {c} } }"#
+        );
+    }
+}
@@ -73,5 +73,194 @@ use proc_macro_error2::proc_macro_error;
 #[proc_macro]
 #[rustfmt::skip]
 pub fn mview(input: TokenStream) -> TokenStream {
-    leptos_mview_core::mview_impl(input.into()).into()
+    let (attrs, input) = leptos_mview_core::take_inner_attrs(input.into());
+    let expanded = attrs.apply(attrs.scoped(|| leptos_mview_core::mview_impl(input)));
+    with_debug_warning(attrs.debug, &expanded, expanded.clone()).into()
 }
+
+/// Expands the view in a file, like [`mview!`] with the file's contents.
+///
+/// The path is relative to the crate root, and the crate is rebuilt whenever
+/// the file changes.
+///
+/// # Examples
+///
+/// ```ignore
+/// mview_file!("templates/footer.mview")
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn mview_file(input: TokenStream) -> TokenStream {
+    let file = leptos_mview_core::ViewFile::read(input.into());
+    let (attrs, input) = leptos_mview_core::take_inner_attrs(file.tokens.clone());
+    let expanded = attrs.apply(attrs.scoped(|| file.expand(input)));
+    with_debug_warning(attrs.debug, &expanded, expanded.clone()).into()
+}
+
+/// Expands the view in a string literal, like [`mview!`] with the string's
+/// contents.
+///
+/// This is meant for markup generated by build scripts. The string can't
+/// capture any variables, and macros like `include_str!` can't be used, as
+/// they are not expanded before this macro runs.
+///
+/// # Examples
+///
+/// ```
+/// # use leptos_mview_macro::mview_str; use leptos::prelude::*;
+/// let view = mview_str!("div.card { \"hi\" }");
+/// assert!(view.to_html().contains(r#"<div class="card">hi</div>"#));
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn mview_str(input: TokenStream) -> TokenStream {
+    let view = leptos_mview_core::ViewStr::parse(input.into());
+    let (attrs, input) = leptos_mview_core::take_inner_attrs(view.tokens.clone());
+    let expanded = attrs.apply(attrs.scoped(|| view.expand(input)));
+    with_debug_warning(attrs.debug, &expanded, expanded.clone()).into()
+}
+
+/// Defines a group of attributes that can be added to elements with
+/// `@@name`.
+///
+/// Attributes written on the element win over the ones from the group. The
+/// group is a `macro_rules`, so it is scoped like one: give it a visibility
+/// like `pub(crate)` to use it by path in other modules.
+///
+/// # Examples
+///
+/// ```ignore
+/// define_mview_attrs!(clickable { role="button" tabindex=0 });
+///
+/// mview! { span @@clickable tabindex=-1 { "x" } }
+/// ```
+#[proc_macro_error]
+#[proc_macro]
+pub fn define_mview_attrs(input: TokenStream) -> TokenStream {
+    leptos_mview_core::define_attrs_impl(input.into()).into()
+}
+
+/// Expands an element with the attributes of its groups. Used by the macros
+/// from [`define_mview_attrs!`].
+#[doc(hidden)]
+#[proc_macro_error]
+#[proc_macro]
+pub fn __mixin(input: TokenStream) -> TokenStream {
+    leptos_mview_core::mixin_impl(input.into()).into()
+}
+
+/// Expands to a `&'static str` of the pretty-printed [`mview!`] expansion.
+///
+/// This is intended for snapshot testing the generated code, without needing
+/// `cargo expand`. Requires the `expand-str` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use leptos_mview_macro::mview_expand_str;
+/// let expanded: &str = mview_expand_str! { div.x { "hi" } };
+/// assert!(expanded.contains("::leptos::tachys::html::element::r#div()"));
+/// ```
+#[cfg(feature = "expand-str")]
+#[proc_macro_error]
+#[proc_macro]
+pub fn mview_expand_str(input: TokenStream) -> TokenStream {
+    let (attrs, input) = leptos_mview_core::take_inner_attrs(input.into());
+    let expanded = attrs.apply(attrs.scoped(|| leptos_mview_core::mview_impl(input)));
+    let pretty = proc_macro2::TokenStream::from(proc_macro2::TokenTree::Literal(
+        proc_macro2::Literal::string(&pretty_print(expanded.clone())),
+    ));
+    with_debug_warning(attrs.debug, &expanded, pretty).into()
+}
+
+/// Renders a view to a `String` of HTML, for asserting the output of a view
+/// in tests. Requires the `to-html` feature.
+///
+/// Takes the same input as [`mview!`]. The view is rendered inside of a new
+/// reactive owner, so signals and effects work without any setup. The
+/// `<!--hot-reload|...-->` comments that mark views in debug builds with the
+/// `nightly` feature, and the `data-source-loc` attributes of the
+/// `source-loc` feature, are left out.
+///
+/// # Examples
+///
+/// ```ignore
+/// let html: String = mview_to_html! { div.card { "hi" } };
+/// assert_eq!(html, r#"<div class="card">hi</div>"#);
+/// ```
+#[cfg(feature = "to-html")]
+#[proc_macro_error]
+#[proc_macro]
+pub fn mview_to_html(input: TokenStream) -> TokenStream {
+    let (attrs, input) = leptos_mview_core::take_inner_attrs(input.into());
+    let expanded = attrs.apply(attrs.scoped(|| leptos_mview_core::mview_impl(input)));
+    // mixed site so that the view can't refer to these
+    let owner = proc_macro2::Ident::new("owner", proc_macro2::Span::mixed_site());
+    let html = proc_macro2::Ident::new("html", proc_macro2::Span::mixed_site());
+    let without_source_locs = cfg!(feature = "source-loc").then(
+        || quote::quote! { let #html = ::leptos_mview::__private::without_source_locs(#html); },
+    );
+    let rendered = quote::quote! {
+        {
+            let #owner = ::leptos::prelude::Owner::new();
+            let #html = #owner.with(|| {
+                ::leptos::prelude::RenderHtml::to_html(
+                    ::leptos::prelude::IntoView::into_view(#expanded)
+                )
+            });
+            #without_source_locs
+            ::leptos_mview::__private::without_view_markers(#html)
+        }
+    };
+    with_debug_warning(attrs.debug, &expanded, rendered).into()
+}
+
+/// Adds a warning with the pretty-printed `expanded` code to `output`, if
+/// there was a `#![debug]` attribute.
+///
+/// Proc macros can't emit warnings on stable, so this uses a deprecated item
+/// instead, which shows the note without failing the build.
+fn with_debug_warning(
+    debug: Option<proc_macro2::Span>,
+    expanded: &proc_macro2::TokenStream,
+    output: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let Some(span) = debug else {
+        return output;
+    };
+    let note = format!("mview! expansion:\n{}", pretty_print(expanded.clone()));
+    let item = proc_macro2::Ident::new("MviewExpansion", span);
+    quote::quote! {
+        {
+            #[deprecated(note = #note)]
+            struct #item;
+            let _ = #item;
+            #output
+        }
+    }
+}
+
+/// Formats an expression with `prettyplease`.
+///
+/// Falls back to the plain token string if the expression can't be parsed.
+#[cfg(feature = "debug")]
+fn pretty_print(tokens: proc_macro2::TokenStream) -> String {
+    let Ok(expr) = syn::parse2::<syn::Expr>(tokens.clone()) else {
+        return tokens.to_string();
+    };
+    // prettyplease only formats whole files, so put the expression in a
+    // function and remove it afterwards.
+    let file: syn::File = syn::parse_quote! { fn expansion() { #expr } };
+    prettyplease::unparse(&file)
+        .lines()
+        .skip(1)
+        .take_while(|line| *line != "}")
+        .map(|line| line.strip_prefix("    ").unwrap_or(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Without the `debug` feature, the expansion is shown unformatted so that
+/// the pretty-printer isn't compiled in.
+#[cfg(not(feature = "debug"))]
+fn pretty_print(tokens: proc_macro2::TokenStream) -> String { tokens.to_string() }
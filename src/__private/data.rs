@@ -0,0 +1,98 @@
+//! The runtime of the `data={pairs}` attribute.
+
+use core::{cell::Cell, fmt::Display};
+
+use leptos::{
+    prelude::{untrack, Effect},
+    tachys::{
+        html::{
+            attribute::{
+                any_attribute::{AnyAttribute, IntoAnyAttribute},
+                custom::custom_attribute,
+            },
+            directive::directive,
+        },
+        renderer::types::Element,
+    },
+};
+
+/// Converts each `(key, value)` to a `data-{key}` attribute.
+fn data_attrs<I, K, V>(pairs: I) -> Vec<AnyAttribute>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Display,
+    V: Display,
+{
+    pairs
+        .into_iter()
+        .map(|(key, value)| {
+            custom_attribute(format!("data-{key}"), value.to_string()).into_any_attr()
+        })
+        .collect()
+}
+
+/// Converts the value of a `data={pairs}` attribute to its attributes.
+///
+/// Used as `(&&Data::new(pairs)).__mview_data()`: closures are preferred over
+/// any other value, and keep the attributes up to date.
+pub struct Data<T>(Cell<Option<T>>);
+
+impl<T> Data<T> {
+    pub const fn new(value: T) -> Self { Self(Cell::new(Some(value))) }
+
+    fn take(&self) -> T { self.0.take().expect("data attributes are only taken once") }
+}
+
+pub trait DataFn {
+    fn __mview_data(self) -> Vec<AnyAttribute>;
+}
+
+impl<F, I, K, V> DataFn for &&Data<F>
+where
+    F: Fn() -> I + Clone + Send + 'static,
+    I: IntoIterator<Item = (K, V)>,
+    K: Display,
+    V: Display,
+{
+    fn __mview_data(self) -> Vec<AnyAttribute> {
+        let pairs = self.take();
+        // the first values are rendered on the server as well
+        let mut attrs = data_attrs(untrack(&pairs));
+        // attributes whose keys are no longer returned are removed
+        let update = directive(
+            move |el: Element| {
+                let pairs = pairs.clone();
+                Effect::new(move |prev: Option<Vec<String>>| {
+                    let mut names = Vec::new();
+                    for (key, value) in pairs() {
+                        let name = format!("data-{key}");
+                        let _ = el.set_attribute(&name, &value.to_string());
+                        names.push(name);
+                    }
+                    for name in prev.into_iter().flatten() {
+                        if !names.contains(&name) {
+                            let _ = el.remove_attribute(&name);
+                        }
+                    }
+                    names
+                });
+            },
+            (),
+        );
+        attrs.push(update.into_any_attr());
+        attrs
+    }
+}
+
+pub trait DataValue {
+    fn __mview_data(self) -> Vec<AnyAttribute>;
+}
+
+impl<I, K, V> DataValue for &Data<I>
+where
+    I: IntoIterator<Item = (K, V)>,
+    K: Display,
+    V: Display,
+{
+    fn __mview_data(self) -> Vec<AnyAttribute> { data_attrs(self.take()) }
+}
@@ -0,0 +1,144 @@
+//! The runtime of the `transition:name={when}` directive.
+
+use core::cell::Cell;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use leptos::{
+    prelude::{
+        on_cleanup, set_timeout_with_handle, window, Effect, Get, GetUntracked, Signal,
+        TimeoutHandle,
+    },
+    tachys::{
+        html::{attribute::Attribute, directive::directive, style::style},
+        renderer::types::Element,
+    },
+    wasm_bindgen::JsCast,
+    web_sys::HtmlElement,
+};
+
+/// The attributes that show and hide an element with the `enter` and `leave`
+/// classes as `when` changes.
+///
+/// The element is rendered with `display: none` if `when` is initially false,
+/// which is all that happens on the server. In the browser, a directive adds
+/// the class when `when` changes, and removes it (setting `display: none`
+/// when leaving) once the class's transition or animation has finished. The
+/// timer is cleared if the element is disposed first.
+pub fn transition(enter: &'static str, leave: &'static str, when: Signal<bool>) -> impl Attribute {
+    let hidden = !when.get_untracked();
+    (
+        style(hidden.then_some(("display", "none"))),
+        directive(
+            move |el: Element| {
+                let el = el.unchecked_into::<HtmlElement>();
+                let timer = Arc::new(Mutex::new(None::<TimeoutHandle>));
+                let clear = {
+                    let timer = Arc::clone(&timer);
+                    move || {
+                        let handle = timer.lock().ok().and_then(|mut timer| timer.take());
+                        if let Some(handle) = handle {
+                            handle.clear();
+                        }
+                    }
+                };
+                on_cleanup(clear.clone());
+                Effect::new(move |prev: Option<bool>| {
+                    let show = when.get();
+                    // the first run matches what was rendered
+                    if prev.is_none_or(|prev| prev == show) {
+                        return show;
+                    }
+                    clear();
+                    let (add, remove) = if show { (enter, leave) } else { (leave, enter) };
+                    let classes = el.class_list();
+                    let _ = classes.remove_1(remove);
+                    let _ = classes.add_1(add);
+                    if show {
+                        let _ = el.style().remove_property("display");
+                    }
+                    let done = {
+                        let el = el.clone();
+                        move || {
+                            let _ = el.class_list().remove_1(add);
+                            if !show {
+                                let _ = el.style().set_property("display", "none");
+                            }
+                        }
+                    };
+                    let handle =
+                        set_timeout_with_handle(done, Duration::from_millis(duration_ms(&el)));
+                    if let Ok(mut timer) = timer.lock() {
+                        *timer = handle.ok();
+                    }
+                    show
+                });
+            },
+            (),
+        ),
+    )
+}
+
+/// Returns the longest transition or animation duration of an element, in
+/// milliseconds.
+fn duration_ms(el: &HtmlElement) -> u64 {
+    let Some(style) = window().get_computed_style(el).ok().flatten() else {
+        return 0;
+    };
+    let ms = |duration: &str| {
+        let duration = duration.trim();
+        match duration.strip_suffix("ms") {
+            Some(ms) => ms.parse::<f64>().ok(),
+            None => duration
+                .strip_suffix('s')
+                .and_then(|s| s.parse::<f64>().ok())
+                .map(|s| s * 1000.0),
+        }
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let longest = ["transition-duration", "animation-duration"]
+        .into_iter()
+        .filter_map(|property| style.get_property_value(property).ok())
+        .flat_map(|durations| durations.split(',').filter_map(ms).collect::<Vec<_>>())
+        .fold(0.0, f64::max) as u64;
+    longest
+}
+
+/// Converts the value of a `transition:` directive to a `Signal<bool>`.
+///
+/// Used as `(&&TransitionWhen::new(value)).__mview_when()`: signals are
+/// preferred over closures.
+pub struct TransitionWhen<T>(Cell<Option<T>>);
+
+impl<T> TransitionWhen<T> {
+    pub const fn new(value: T) -> Self { Self(Cell::new(Some(value))) }
+
+    fn take(&self) -> T { self.0.take().expect("value is only taken once") }
+}
+
+pub trait TransitionWhenSignal {
+    fn __mview_when(&self) -> Signal<bool>;
+}
+
+impl<S> TransitionWhenSignal for &&TransitionWhen<S>
+where
+    S: Get<Value = bool> + Send + Sync + 'static,
+{
+    fn __mview_when(&self) -> Signal<bool> {
+        let signal = self.take();
+        Signal::derive(move || signal.get())
+    }
+}
+
+pub trait TransitionWhenFn {
+    fn __mview_when(&self) -> Signal<bool>;
+}
+
+impl<F> TransitionWhenFn for &TransitionWhen<F>
+where
+    F: Fn() -> bool + Send + Sync + 'static,
+{
+    fn __mview_when(&self) -> Signal<bool> { Signal::derive(self.take()) }
+}
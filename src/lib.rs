@@ -107,7 +107,7 @@ The `view!` macros in Leptos is often the largest part of a component, and can g
 
 # Compatibility
 
-This macro will be compatible with the latest stable release of Leptos. The macro references Leptos items using `::leptos::...`, no items are re-exported from this crate. Therefore, this crate will likely work with any Leptos version if no view-related items are changed.
+This macro will be compatible with the latest stable release of Leptos. The macro references Leptos items using `::leptos::...`, no items are re-exported from this crate. The traits that the expansion calls methods from are imported inside the expansion, so `mview!` works without `use leptos::prelude::*` in scope. Therefore, this crate will likely work with any Leptos version if no view-related items are changed.
 
 The below are the versions with which I have tested it to be working. It is likely that the macro works with more versions of Leptos.
 
@@ -118,7 +118,75 @@ The below are the versions with which I have tested it to be working. It is like
 | `0.3`                  | `0.6`                       |
 | `0.4`                  | `0.7`                       |
 
-This crate also has a feature `"nightly"` that enables better proc-macro diagnostics (simply enables the nightly feature in proc-macro-error2. Necessary while [this pr](https://github.com/GnomedDev/proc-macro-error-2/pull/5) is not yet merged).
+This crate also has a feature `"nightly"` that enables better proc-macro diagnostics (simply enables the nightly feature in proc-macro-error2. Necessary while [this pr](https://github.com/GnomedDev/proc-macro-error-2/pull/5) is not yet merged). It also joins spans, so that errors underline the whole construct (like a kebab-case ident `data-index`, a `key=value` attribute or a list of selectors) instead of only its first token. This needs a nightly compiler. In debug builds, it also marks each view with several top-level nodes with the file and line of the macro call, the same as `view!` does with the `nightly` feature of leptos, so that devtools and hot reloading can tell where a view came from. A single element or component is not marked, so that it has the same type in every build. Release builds are unchanged.
+
+The `"trace"` feature logs every time a closure made from brackets (like `class=[active()]` or a `[count()]` child) re-runs, using `leptos::logging::log!` with the element, attribute and source line. For example, `[mview] <span> class ran at src/app.rs:12`. This shows which parts of a view are updating. It has no effect on the macro output when disabled.
+
+The `"testid"` feature enables the `testid:name` directive, which adds a `data-testid="name"` attribute for end-to-end tests to select. Without the feature, `testid:` adds nothing, so test ids can be left in and only enabled for test builds. It works on both elements and components:
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+mview! {
+    form {
+        input type="email" testid:email-input;
+        button type="submit" testid:submit-button { "Submit" }
+    }
+}
+# ;
+```
+
+The `"source-loc"` feature adds a `data-source-loc="file.rs:123"` attribute to every HTML, SVG and MathML element with the file and line its tag was written on, for tools that open the source of a clicked element. Components are skipped, as their root element gets its own location. Nothing is added without the feature, or when the compiler doesn't provide span locations (before Rust 1.88).
+
+The `"expand-str"` feature adds a `mview_expand_str!` macro, which takes the same input as `mview!` but expands to a `&'static str` of the pretty-printed generated code. This can be used to snapshot test views (e.g. with `insta`) without needing `cargo expand`.
+
+The `"to-html"` feature adds a `mview_to_html!` macro for tests, which takes the same input as `mview!` but renders the view to a `String` of HTML. The view is rendered inside of a new reactive owner, so it can be used in a `#[test]` without any setup, and the `<!--hot-reload|...-->` comments from the `"nightly"` feature and the `data-source-loc` attributes from the `"source-loc"` feature are left out.
+```
+# use leptos_mview::mview_to_html;
+let html = mview_to_html! { div.card { "hi" } };
+assert_eq!(html, r#"<div class="card">hi</div>"#);
+```
+
+Large views can be kept in their own files with `mview_file!("templates/footer.mview")`, which expands the contents of the file like `mview!` would. The path is relative to the crate root, and the crate is rebuilt whenever the file changes. The tokens read from a file have no location of their own, so errors point at the whole `mview_file!` call, with a note of which file they came from.
+
+Generated markup (e.g. from a build script) can be expanded from a string literal with `mview_str!("div.card { \"hi\" }")`. The string can't use any variables, and macros like `include_str!` can't be used inside of it, as they are not expanded before `mview_str!` runs. Errors point at the whole string, with a note of roughly where in the string they are.
+
+To see what a single `mview!` call expands to, put `#![debug]` at the start of it (`mview! { #![debug] div { ... } }`). The expansion is shown as a warning when compiling, without failing the build. It is pretty-printed with the `"debug"` feature (also enabled by `"expand-str"`), and shown as a single line otherwise, so that normal builds don't pull in the pretty-printer. It is shown as a deprecation warning, as proc macros can't emit their own warnings on stable. The attribute has no other effect, but remember to remove it afterwards.
+
+The type of an `mview!` call depends on its contents, so views with different contents can't be stored together or returned from different branches. Put `#![into_view]` at the start of the call (`mview! { #![into_view] div { ... } }`) to convert the whole view to an `AnyView`, so every call has the same type. This is the type-erased view in Leptos 0.7, as `View<T>` is generic over its contents. It can be combined with `#![debug]` in any order. An empty `mview! {}` is an empty view that renders nothing, so `if show { mview! { #![into_view] p("shown") } } else { mview! { #![into_view] } }` works too.
+
+The `checked`, `value` and `selected` attributes on form controls only set their *initial* state: once the user has changed the control, updating the attribute does nothing. Put `#![form_props]` at the start of the call to also set these on `input`, `option` and `select` elements as properties (like `prop:checked`), so that reactive values keep the control in sync. The attribute is still added, so the initial state is rendered on the server (`select` only gets the `value` property, as it has no such attribute). Literal values never change, so they are left as attributes.
+```
+# use leptos::prelude::*;
+# use leptos_mview::mview;
+let checked = RwSignal::new(false);
+mview! {
+    #![form_props]
+    input type="checkbox" checked={checked};
+}
+# ;
+```
+
+Put `#![strict]` at the start of the call (or enable the `"strict"` feature for every call) to check the style of attribute names. An `onclick=...`-style key on an element (`on` followed by an event name) is an error, as it adds an inline JavaScript attribute instead of an event listener: use `on:click` instead. `snake_case` keys on elements (like `http_equiv`) are warned about, as HTML attributes are kebab-case, and so are `kebab-case` keys on components, as props are `snake_case` and `max-width` is only converted to the `max_width` prop by the macro. `class:` and `style:` directives with a literal value (or a block of one, but not a bracket) are warned about too, as they never change but are still updated like a reactive value: `class:active=true` can be the selector shorthand `.active`, `class:active=false` can be removed, and `style:color="red"` can go in the `style` attribute. The warnings point at the key and are shown as deprecation warnings, like `#![debug]`. Put `#[allow(strict)]` before an element to skip its attributes. Directives like `attr:` are never checked.
+```
+# use leptos_mview::mview;
+mview! {
+    #![strict]
+    meta http-equiv="refresh" content="5";
+    button on:click={move |_| ()} { "Reload" }
+    #[allow(strict)] meta http_equiv="refresh";
+}
+# ;
+```
+
+The `"ssr-opt"` feature pre-renders static parts of a view into HTML strings at compile time, like Leptos' `view!` does. Any element nested in an HTML element that only has string attributes and string/element children (like `p.intro("Hello " strong("world"))`) becomes a single `InertElement`, instead of building each element at runtime. Text and attribute values are escaped at compile time, in the same way that Leptos escapes them at runtime: `&`, `<` and `>` in text, and `"` as well in attributes. Static strings outside of pre-rendered elements are still escaped by Leptos. The feature must be enabled for both the server and client builds so that hydration lines up.
+
+The `"hoist"` feature caches static subtrees on the client. Any element nested in another element that only has literal attributes and string/element children (including inline `svg` icons) is wrapped in a Leptos `ViewTemplate`, so its DOM nodes are built into a `<template>` once and cloned every time the view is built afterwards. The output is the same as without the feature. If `"ssr-opt"` is also enabled, elements that can be pre-rendered are still turned into an `InertElement` instead.
+
+The `"delegate"` feature expands `mview!` to an equivalent `leptos::view! { <div ...> ... </div> }` call instead of building the view itself, so that Leptos' own macro does the codegen (including its SSR optimizations and hot-reloading). Bracket values become `move ||` closures, selectors become `class:name=true` and `id`, `ref` becomes `node_ref`, closure children `|x| { ... }` become `let(x)`, and slots become `<Name slot>`. Optional handlers `on:event=?{...}` become a closure that only runs the handler if it is `Some`, and an element with `dbg:` directives is put in its own `view!` after the logging effects. A few things have no `view!` equivalent and are errors in this mode: `transition:` directives, `dbg:` on slots, and component generics that are not plain type names (use a type alias instead). The `"trace"`, `"ssr-opt"` and `"hoist"` features have no effect with `"delegate"`. The checks that are made while building the view are skipped too, as the attributes are passed on to `view!` as they are: `#![strict]`, `#![form_props]`, closures in `onclick`-style keys, dynamic `inner_html`, a `textarea` with both `value` and children, and `slot:` children on elements.
+
+The `"class-macro"` feature passes every static class name through a macro, to map them to the generated names from CSS modules or scoped styles. Set the `MVIEW_CLASS_MACRO` environment variable to the macro's path when building (for example in the `[env]` section of `.cargo/config.toml`), and class selectors, the names in a string `class="..."` and `class:` keys are each wrapped in it: `div.card class="a b" class:active=[x]` adds the classes `css!("card")`, `css!("a")`, `css!("b")` and `css!("active")` with `MVIEW_CLASS_MACRO=css`. Dynamic `class={...}` values and `attr:class` are left alone. Elements with static classes are not pre-rendered by `"ssr-opt"` or hoisted by `"hoist"`, and the feature has no effect with `"delegate"`.
+
+The `"class-manifest"` feature writes every static class name to a file, so that a CSS purger like Tailwind can be pointed at it instead of scanning the Rust source. Set the `MVIEW_CLASS_MANIFEST` environment variable to a directory when building, and the class selectors, the names in string `class="..."` attributes and `class:` keys of each crate are added to `<directory>/<crate name>.classes`, one per line. Each name is only written once per crate, and the file is locked while it is written so that crates can be compiled in parallel. Names are never removed, so delete the directory for a clean list.
 
 # Syntax details
 
@@ -126,10 +194,14 @@ This crate also has a feature `"nightly"` that enables better proc-macro diagnos
 
 Elements have the following structure:
 
-1. Element / component tag name / path (`div`, `App`, `component::Codeblock`).
-2. Any classes or ids prefixed with a dot `.` or hash `#` respectively.
+1. Element / component tag name / path (`div`, `App`, `component::Codeblock`). This can be left out if the element starts with a class or id, which makes it a `div` (`.card` is the same as `div.card`).
+2. Any classes or ids prefixed with a dot `.` or hash `#` (or `@`) respectively. These can also be mixed in with the attributes (`input type="text" .wide disabled;`), and are merged in the same way wherever they are written.
 3. A space-separated list of attributes and directives (`class="primary"`, `on:click={...}`).
-4. Children in parens or braces (`("hi")` or `{ "hi!" }`), or a semi-colon for no children (`;`).
+4. Children in parens or braces (`("hi")` or `{ "hi!" }`), or a semi-colon for no children (`;`). A `/` can be used instead of the semi-colon, like a self-closing HTML tag (`img src="x" /`).
+
+Each element has at most one children block. A block written straight after the `;` (or `/`) of an element (on the same or next line) is an error, as it was probably meant to be the children; leave a blank line before it to keep it as a separate child.
+
+If a child can't be parsed, it is left out up to its `;` or children block, and the rest of its siblings are still expanded. This keeps type checking and completions working in the rest of the view while you fix it, instead of also reporting every variable it uses as unused. Other errors, like a missing value after `=`, still replace the whole view with `()`.
 
 Example:
 ```
@@ -165,19 +237,35 @@ pub fn App() -> impl IntoView {
 }
 ```
 
-Note that due to [Reserving syntax](https://doc.rust-lang.org/edition-guide/rust-2021/reserving-syntax.html), the `#` for ids must have a space before it.
+Note that due to [Reserving syntax](https://doc.rust-lang.org/edition-guide/rust-2021/reserving-syntax.html), the `#` for ids must have a space before it. Use `@` instead of `#` to write the id without the space, including after classes (`nav@primary`, `div.card@main`). Each element can only have one id, which must be a kebab-case identifier.
 
 ```
 # use leptos_mview::mview; use leptos::prelude::*;
 mview! {
     nav #primary ("...")
     // not allowed: nav#primary ("...")
+    // but this is the same as the first one:
+    nav@primary ("...")
 }
 # ;
 ```
 
+Class names can start with a hyphen, like Tailwind's negative utilities (`div.-mt-2`). For class names with characters that aren't allowed in identifiers, use a string literal instead: `div."-translate-x-1/2"`.
+
 Classes/ids created with the selector syntax can be mixed with the attribute `class="..."` and directive `class:a-class={signal}` as well.
 
+Elements with only one child element can be chained with `>` instead of nesting them. Each element in the chain can have its own selectors and attributes, and only the last one has children or a semicolon.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+mview! {
+    div.outer > div.inner > span.icon { "x" }
+    // same as...
+    div.outer { div.inner { span.icon { "x" } } }
+}
+# ;
+```
+
 There is also a special element `!DOCTYPE html;`, equivalent to `<!DOCTYPE html>`.
 
 ## Slots
@@ -232,6 +320,8 @@ pub fn App() -> impl IntoView {
 # }
 ```
 
+Only components have `slot:` children. To put an element in a named slot of a web component, use a plain `slot` attribute instead, which is rendered as it is: `sl-card { div slot="footer" { ... } }`.
+
 ## Values
 
 There are (currently) 3 main types of values you can pass in:
@@ -270,6 +360,11 @@ There are (currently) 3 main types of values you can pass in:
     # ;
     ```
 
+- A single **function call** like `count=len(items)` or `label=t("home.title")` can also be passed in directly, and is treated the same as a block. Anything more complex, like method calls or operators, must be wrapped in braces.
+- An **async block** like `future=async move { fetch_user(id).await }` (with or without `move`) can also be passed in directly, and is kept as a single expression, the same as `future={async move { ... }}`.
+- An **`if`** or **`match`** expression like `class=if dark { "dark" } else { "light" }` can also be passed in directly, including any `else if` branches. The whole expression is taken as the value, the same as wrapping it in braces.
+- A **closure** like `on:click=move |_| count.update(|c| *c += 1)` or `on_select=|id| select(id)` can also be passed in directly. The body is either a block, or an expression made of paths, literals, calls, method calls and operators, which ends before the next attribute, `;` or children block. A `/` or `>` followed by something other than a number or parenthesized expression ends the element or starts its child, so `on:click=move |_| go() /` is a closure followed by the element terminator. Closures that don't fit this (like ones using `|` as bitwise or, dividing or comparing by a variable like `a / b`, struct literals or `return`) still need to be wrapped in braces.
+
 - Values wrapped in **brackets** (like `value=[a_bool().to_string()]`) are shortcuts for a block with an empty closure `move || ...` (to `value={move || a_bool().to_string()}`).
     ```rust
     # use leptos::prelude::*; use leptos_mview::mview;
@@ -285,7 +380,7 @@ There are (currently) 3 main types of values you can pass in:
     # ;
     ```
 
-    - Note that this always expands to `move || ...`: for any closures that take an argument, use the full closure block instead.
+    - Note that this expands to `move || ...`: for any closures that take an argument, use the full closure block instead. A bracket that already starts with a closure, like `key=[|item| item.id]`, is used as that closure instead of being wrapped again.
         ```compile_error
         # use leptos_mview::mview;
         # use leptos::logging::log;
@@ -305,10 +400,13 @@ There are (currently) 3 main types of values you can pass in:
         ```
 
 The bracketed values can also have some special prefixes for even more common shortcuts!
-- Currently, the only one is `f` - e.g. `f["{:.2}", stuff()]`. Adding an `f` will add `format!` into the closure. This is equivalent to `[format!("{:.2}", stuff())]` or `{move || format!("{:.2}", stuff())}`.
+- `f` - e.g. `f["{:.2}", stuff()]`. Adding an `f` will add `format!` into the closure. This is equivalent to `[format!("{:.2}", stuff())]` or `{move || format!("{:.2}", stuff())}`.
+- `memo` - e.g. `memo[expensive_layout(data())]`. The value is computed in a `Memo`, so it only reruns when the signals it reads change, and the child or attribute is only updated when the result changes. The memo is created once with the view, outside of the closure that reads it. This is equivalent to `{let layout = Memo::new(move |_| expensive_layout(data())); move || layout.get()}`, so the value has to implement `PartialEq` and `Clone`.
 
 ## Attributes
 
+Attributes are separated by spaces. Commas are also allowed between attributes (and between children), and are ignored: `input type="text", class="x", disabled;` is the same as `input type="text" class="x" disabled;`.
+
 ### Key-value attributes
 
 Most attributes are `key=value` pairs. The `value` follows the rules from above. The `key` has a few variations:
@@ -344,20 +442,35 @@ Most attributes are `key=value` pairs. The `value` follows the rules from above.
     # ;
     ```
 
+    The shorthand also works with a field access, using the last field as the key: `{config.title}` is the same as `title={config.title}`, and `{self.data-index}` is the same as `data-index={self.data_index}`.
+    ```rust
+    # use leptos_mview::mview; use leptos::prelude::*;
+    # struct Config { title: &'static str, data_index: i32 }
+    # let config = Config { title: "hi", data_index: 0 };
+    mview! {
+        div {config.title} {config.data-index};
+    }
+    # ;
+    ```
+
     See also: [kebab-case identifiers with attribute shorthand](#kebab-case-identifiers-with-attribute-shorthand)
 
 Note that the special `node_ref` or `ref` or `_ref` or `ref_` attribute in Leptos to bind the element to a variable is just `ref={variable}` in here.
 
+`inner_html` only accepts a string literal, like `div inner_html="<b>bold</b>";`, as the HTML is inserted without escaping. To insert dynamic HTML that you trust (or have sanitized), use `unsafe_inner_html={html}` instead.
+
+On HTML elements, `data={pairs}` adds a `data-{key}` attribute for each `(key, value)` in `pairs`, which can be anything that iterates over pairs (like a `HashMap` or `Vec`) with keys and values that implement `Display`. The keys are used as they are after the `data-` prefix, and no attributes are added if there are no pairs. If `pairs` is a closure (like `data=[analytics.get()]`), the attributes are kept up to date in the browser, and attributes whose keys are no longer returned are removed. A literal `data="..."`, or `data` on an `<object>`, is still a normal attribute.
+
 ### Boolean attributes
 
-Another shortcut is that boolean attributes can be written without adding `=true`. Watch out though! `checked` is **very different** to `{checked}`.
+Another shortcut is that boolean attributes can be written without adding `=true`, and with a `!` in front instead of adding `=false`. Watch out though! `checked` is **very different** to `{checked}`.
 ```
 # use leptos::prelude::*; use leptos_mview::mview;
 // recommend usually adding #[prop(optional)] to all these
 #[component]
 fn LotsOfFlags(wide: bool, tall: bool, red: bool, curvy: bool, count: i32) -> impl IntoView {}
 
-mview! { LotsOfFlags wide tall red=false curvy count=3; }
+mview! { LotsOfFlags wide tall !red curvy count=3; }
 # ;
 // same as...
 mview! { LotsOfFlags wide=true tall=true red=false curvy=true count=3; }
@@ -369,15 +482,21 @@ See also: [boolean attributes on HTML elements](#boolean-attributes-on-html-elem
 ### Directives
 
 Some special attributes (distinguished by the `:`) called **directives** have special functionality. All have the same behaviour as Leptos. These include:
-- `class:class-name=[when to show]`
-- `style:style-key=[style value]`
-- `on:event={move |ev| event handler}`
-- `prop:property-name={signal}`
+- `class:class-name=[when to show]`, or just `class:class-name` to always add the class
+- `style:style-key=[style value]` or `style:style-key={signal}`
+- `on:event={move |ev| event handler}`, or `on:event=?{optional_handler}` to only handle the event if the `Option` is `Some`
+- `prop:property-name={signal}`, or just `prop:property-name` to set it to `true`
 - `attr:name={value}`
 - `clone:ident_to_clone`
 - `use:directive_name` or `use:directive_name={params}`
 - `bind:checked={rwsignal}` or `bind:value={(getter, setter)}`
 
+Event listeners must use `on:`. An attribute like `onclick` with a closure (`onclick={move |_| ...}`) is an error, as the closure would be turned into the string of an HTML attribute instead of being attached as a listener. A string like `onclick="history.back()"` is still added as an attribute, for JavaScript that should run before the page is hydrated.
+
+On HTML elements, `attr:` always adds a plain attribute with exactly the given name, skipping any special handling. For example, `attr:class="a"` sets the attribute `class` directly instead of going through Leptos' class handling, and `attr:ref="a"` adds an attribute named `ref` instead of binding a node ref.
+
+On custom elements (tags with a `-`, like `sl-input`), kebab-case `prop:` names are converted to camelCase, as that is how their properties are named in JavaScript: `prop:help-text={x}` sets the `helpText` property. Use a string for the name to set it exactly as written, like `prop:"help-text"={x}`. `prop:` names on other elements are used as they are.
+
 All of these directives except `clone` also support the attribute shorthand:
 
 ```
@@ -402,8 +521,53 @@ mview! {
 # ;
 ```
 
+A string literal `style:` value ending in `!important`, like `style:color="red !important"`, keeps its priority in the browser as well as in the server-rendered HTML. Leptos sets style values with `setProperty`, which ignores `!important` in the value, so the macro sets it again with the priority once the element is created. Dynamic values are passed to Leptos unchanged.
+
+A string `style="..."` attribute and the `style:` directives with string values are merged into a single `style` attribute at compile time, in the order they were written. Any dynamic `style:` directives are applied on top of that, so they take precedence over the static styles for the same property: `div style="color: red" style:margin="4px" style:color=[color()]` renders as `style="color: red;margin:4px;color:blue;"` when `color()` is `"blue"`. Nothing is merged if the `style` attribute itself is dynamic.
+
 Note that the `use:` directive automatically calls `.into()` on its argument, consistent with behaviour from Leptos.
 
+All directives except `bind:` can also be used on components, and are passed on to the component's top-level elements (like `add_any_attr` in Leptos). For example, `MyInput use:focus_trap;` runs `focus_trap` on the element(s) rendered by `MyInput`.
+
+There is also a `dbg:label={value}` directive, which is not from Leptos. In debug builds, it logs the value with `leptos::logging::log!` inside an effect, so signals and closures (including brackets) are logged again whenever they change. It works on elements and components, and is removed entirely in release builds.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+let count = RwSignal::new(0);
+mview! {
+    button dbg:count={count} dbg:doubled=[count.get() * 2] { "Click" }
+}
+# ;
+```
+
+`transition:name={when}` is another directive that is not from Leptos, for show/hide animations. `when` is a signal or closure returning a `bool`. When it changes, the `name-enter` or `name-leave` class is added to the element, and removed once the class's CSS transition or animation has finished (the longest `transition-duration` or `animation-duration`), at which point a leaving element gets `display: none`. The element starts with `display: none` if `when` is initially false, which is all that is rendered on the server. Pending timers are cleared when the element is disposed. It works on elements and components, but not in delegate mode.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+let open = RwSignal::new(false);
+mview! {
+    div.menu transition:fade={open} { "..." }
+}
+# ;
+```
+
+### Attribute groups
+
+Attributes that are used together can be defined once with `define_mview_attrs!`, and added to any element with `@@` and the group's name. Attributes written on the element win over the ones from the group, and earlier groups win over later ones. A single `@` is already an id (`@main`), so groups use two.
+
+```
+# use leptos_mview::{define_mview_attrs, mview}; use leptos::prelude::*;
+define_mview_attrs!(clickable { role="button" tabindex=0 on:keydown={move |_| ()} });
+
+mview! {
+    span @@clickable { "same as role=\"button\" tabindex=0 on:keydown=..." }
+    li @@clickable tabindex=-1 { "tabindex is -1" }
+}
+# ;
+```
+
+The group is a `macro_rules`, so it follows the same scoping rules: define it before it is used, and give it a visibility like `pub(crate)` to use it from other modules by path (`@@ui::clickable`). An unknown group is reported at the `@@`. Groups can't be used on slots.
+
 ## Children
 
 You may have noticed that the `let:data` prop was missing from the previous section on directive attributes!
@@ -423,7 +587,21 @@ mview! {
 # ;
 ```
 
-Note that you will usually need to add a `*` before the data you are using. If you forget that, rust-analyser will tell you to dereference here: `*{monkeys}`. This is obviously invalid - put it inside the braces.
+Note that you will usually need to add a `*` before the data you are using. If you forget that, the compiler will suggest dereferencing inside the braces: `{*monkeys}`.
+
+For one-off async content, an `await` child is a shorter way to write `Await`: the future follows the usual value rules, and the closure arguments and children receive the resolved value. Add `blocking` after `await` to set the `blocking` prop.
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+# leptos::task::Executor::init_futures_executor().unwrap();
+mview! {
+    div {
+        await {async { 3 }} |monkeys| {
+            p({*monkeys} " little monkeys, jumping on the bed.")
+        }
+    }
+}
+# ;
+```
 
 Children can be wrapped in either braces or parentheses, whichever you prefer.
 
@@ -441,6 +619,60 @@ Summary from the previous section on values in case you missed it: children can
 
 Children with closures are also supported on slots.
 
+The closure is passed straight to the `children` prop, so it works with any component that takes a plain `Fn(T) -> impl IntoView`, like Leptos' `For`:
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+#[derive(Clone)]
+struct Item { id: u32, name: String }
+# let _owner = Owner::new(); _owner.set();
+let items = RwSignal::new(Vec::<Item>::new());
+mview! {
+    ul {
+        For each=[items.get()] key=[|item| item.id] |item| {
+            li { {item.name} }
+        }
+    }
+}
+# ;
+```
+
+Inside a component, `children;` renders its `children` prop, and is the same as writing `{children()}`. This works for both `Children` and `ChildrenFn`.
+
+```
+# use leptos::prelude::*; use leptos_mview::mview;
+#[component]
+fn Card(children: Children) -> impl IntoView {
+    mview! {
+        div.card { children; }
+    }
+}
+```
+
+Long string children can be marked with `#[dedent]` to write them over multiple lines. The common indentation is removed and the lines are joined with single spaces at compile time, so the result is a single string. This is not allowed inside `pre` or `textarea`, where whitespace is significant.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+mview! {
+    p {
+        #[dedent] "
+            A very long paragraph that
+            goes over multiple lines.
+        "
+    }
+}
+# ;
+```
+
+Similarly, `#[entities]` decodes HTML entities like `&nbsp;`, `&mdash;` or `&#169;` at compile time (Leptos escapes text, so they would otherwise show up as-is). Unknown entities are a compile error. Both markers can be used on the same string.
+
+```
+# use leptos_mview::mview; use leptos::prelude::*;
+mview! {
+    p { #[entities] "Tom&nbsp;&amp;&nbsp;Jerry" }
+}
+# ;
+```
+
 # Extra details
 
 ## Kebab-case identifiers with attribute shorthand
@@ -472,6 +704,18 @@ If an attribute shorthand has hyphens:
     # ;
     ```
 
+- On `class:` and `style:` directives (on elements or components), the class or style name will keep hyphens, and the value will be turned into an identifier with underscores.
+    ```
+    # use leptos_mview::mview; use leptos::prelude::*;
+    let is_red = true;
+    let font_size = "12px";
+    mview! { div class:{is-red} style:{font-size}; }
+    # ;
+    // same as...
+    mview! { div class:is-red={is_red} style:font-size={font_size}; }
+    # ;
+    ```
+
 ## Boolean attributes on HTML elements
 
 Note the behaviour from Leptos: setting an HTML attribute to true adds the attribute with no value associated.
@@ -482,7 +726,9 @@ view! { <input type="checkbox" checked=true data-smth=true not-here=false /> }
 ```
 Becomes `<input type="checkbox" checked data-smth />`, NOT `checked="true"` or `data-smth="true"` or `not-here="false"`.
 
-To have the attribute have a value of the string "true" or "false", use `.to_string()` on the bool. Make sure that it's in a closure if you're working with signals too.
+The exceptions are enumerated attributes, which take the strings "true" and "false" instead of being present or absent: `draggable`, `spellcheck`, `contenteditable` and all `aria-*` attributes. On HTML elements, bools given to these (as literals, values or the result of closures) are converted to `"true"` or `"false"`, so `div draggable=true aria-hidden=[hidden.get()];` renders as `<div draggable="true" aria-hidden="false">`. `bool` signals like `aria-expanded={open}` are converted as well, and keep updating. `attr:aria-*` directives are converted too, on both elements and components, so `attr:aria-busy=false` renders as `aria-busy="false"`.
+
+For any other attribute, to have the attribute have a value of the string "true" or "false", use `.to_string()` on the bool. Make sure that it's in a closure if you're working with signals too.
 ```
 # use leptos::prelude::*;
 # use leptos_mview::mview;
@@ -494,6 +740,74 @@ mview! { input type="checkbox" checked=f["{}", boolean_signal()]; }
 # ;
 ```
 
+## Textarea values
+
+A `value` attribute does nothing on a `<textarea>` in the browser, so `value` on a `textarea` sets its contents instead. It is added as a child for the initial contents (including when rendered on the server), and as a `prop:value` so that reactive values keep updating after the user has typed in it.
+```
+# use leptos::prelude::*;
+# use leptos_mview::mview;
+let text = RwSignal::new(String::new());
+mview! { textarea value={text}; }
+# ;
+```
+The textarea can't have any other children when `value` is used.
+
+## Templates and declarative shadow DOM
+
+The children of a `template` are moved into its `content` in the browser, so they aren't part of the page, like in HTML. On the server they are rendered inside the `<template>`, so `template shadowrootmode="open" { ... }` inside a custom element renders a declarative shadow root. The `shadowrootmode`, `shadowrootdelegatesfocus`, `shadowrootclonable` and `shadowrootserializable` attributes are passed through as they are.
+```
+# use leptos_mview::mview;
+mview! {
+    my-card {
+        template shadowrootmode="open" {
+            style("h2 { margin: 0; }")
+            h2("Card")
+            slot;
+        }
+        span("light DOM")
+    }
+}
+# ;
+```
+
+## Attribute order in rendered HTML
+
+When rendered to HTML, the attributes of an element are always in this order:
+1. Key-value attributes and `attr:` directives, in the order they were written.
+2. The id from a `#id` selector.
+3. Attributes from spreads (`{..attrs}`).
+4. All classes (the `class` attribute, `class:` directives and `.class` selectors), then all styles.
+
+For example, `div.b #main data-x=1 class="a" style:color="red" title="t"` renders as `<div data-x="1" title="t" id="main" class="a b" style="color:red;">`.
+
+## Islands
+
+`mview!` works with Leptos' `islands` feature: `#[island]` components are used like any other component, and expand the same way as in `view!`.
+```no_run
+# use leptos_mview::mview; use leptos::prelude::*;
+#[island]
+fn Counter(initial: i32, children: Children) -> impl IntoView {
+    let count = RwSignal::new(initial);
+    mview! {
+        button on:click={move |_| count.update(|n| *n += 1)} { [count.get()] }
+        {children()}
+    }
+}
+
+mview! {
+    Counter initial=3 {
+        p("only rendered on the server")
+    }
+}
+# ;
+```
+
+The children of an island are rendered once on the server and sent as HTML, so an island's `children` must be `Children`. Closure children (`Counter |x| { ... }`) and slots can't be given to an island: these are type errors at compile time, not hydration errors. Island props are serialized, so they must implement `serde`'s traits and `serde` must be a dependency of your crate.
+
+## Nesting depth
+
+Elements can be nested 128 levels deep. Each level recurses while the macro runs, so a deeper view (usually from a code generator) is a `maximum nesting depth 128 exceeded` error at the innermost element, instead of overflowing the compiler's stack. Set the `MVIEW_MAX_NESTING_DEPTH` environment variable when building to change the limit, for example in the `[env]` section of `.cargo/config.toml`. Cargo doesn't know that the macro reads this variable, so crates that were already built keep the old limit until they are rebuilt (for example with `cargo clean -p your-crate`). A much higher limit can still overflow the stack.
+
 # Contributing
 
 Please feel free to make a PR/issue if you have feature ideas/bugs to report/feedback :)
@@ -505,8 +819,119 @@ Please feel free to make a PR/issue if you have feature ideas/bugs to report/fee
 // Some bits are slightly broken, fix up stray `compile_error`/
 // `ignore`, missing `rust` annotations and remove `#` lines.
 
-pub use leptos_mview_macro::mview;
+#[doc(hidden)]
+pub use leptos_mview_macro::__mixin;
+#[cfg(feature = "expand-str")]
+pub use leptos_mview_macro::mview_expand_str;
+#[cfg(feature = "to-html")]
+pub use leptos_mview_macro::mview_to_html;
+pub use leptos_mview_macro::{define_mview_attrs, mview, mview_file, mview_str};
 
 /// Not for public use. Do not implement anything on this.
 #[doc(hidden)]
 pub struct MissingValueAfterEq;
+
+/// Not for public use. Used by the macro's expansion.
+#[doc(hidden)]
+pub mod __private {
+    use core::cell::Cell;
+
+    use leptos::prelude::{Get, Signal};
+
+    mod data;
+    pub use data::*;
+    mod transition;
+    pub use transition::*;
+
+    /// Removes the `<!--hot-reload|...-->` comments that view markers render
+    /// in debug builds with the `nightly` feature, for `mview_to_html!`.
+    pub fn without_view_markers(html: String) -> String {
+        remove_all(html, "<!--hot-reload|", "-->")
+    }
+
+    /// Removes the attributes added by the `source-loc` feature, for
+    /// `mview_to_html!`.
+    pub fn without_source_locs(html: String) -> String {
+        remove_all(html, " data-source-loc=\"", "\"")
+    }
+
+    /// Removes everything from each `open` up to and including the next
+    /// `close`.
+    fn remove_all(mut html: String, open: &str, close: &str) -> String {
+        let mut from = 0;
+        while let Some(start) = html[from..].find(open).map(|i| from + i) {
+            let Some(len) = html[start + open.len()..].find(close) else {
+                break;
+            };
+            html.replace_range(start..start + open.len() + len + close.len(), "");
+            from = start;
+        }
+        html
+    }
+
+    /// Converts bools to `"true"` or `"false"` for enumerated attributes like
+    /// `draggable`, and leaves every other value as it is.
+    ///
+    /// Used as `(&Enumerated::new(value)).__mview_enumerated()`: the method
+    /// from [`EnumeratedBool`] is found first for bools, anything else falls
+    /// back to [`EnumeratedOther`] through the extra reference.
+    pub struct Enumerated<T>(Cell<Option<T>>);
+
+    impl<T> Enumerated<T> {
+        pub const fn new(value: T) -> Self { Self(Cell::new(Some(value))) }
+
+        pub fn take(&self) -> T { self.0.take().expect("value is only taken once") }
+    }
+
+    pub trait EnumeratedBool {
+        type Output;
+        fn __mview_enumerated(&self) -> Self::Output;
+    }
+
+    impl EnumeratedBool for Enumerated<bool> {
+        type Output = &'static str;
+
+        fn __mview_enumerated(&self) -> Self::Output {
+            if self.take() {
+                "true"
+            } else {
+                "false"
+            }
+        }
+    }
+
+    impl EnumeratedBool for Enumerated<Option<bool>> {
+        type Output = Option<&'static str>;
+
+        fn __mview_enumerated(&self) -> Self::Output {
+            self.take().map(|b| if b { "true" } else { "false" })
+        }
+    }
+
+    /// Converts `bool` signals to a derived signal of `"true"` or `"false"`,
+    /// so that they stay reactive.
+    pub trait EnumeratedSignal {
+        fn __mview_enumerated(&self) -> Signal<&'static str>;
+    }
+
+    impl<S> EnumeratedSignal for Enumerated<S>
+    where
+        S: Get<Value = bool> + Send + Sync + 'static,
+    {
+        fn __mview_enumerated(&self) -> Signal<&'static str> {
+            let signal = self.take();
+            Signal::derive(move || if signal.get() { "true" } else { "false" })
+        }
+    }
+
+    pub trait EnumeratedOther {
+        type Output;
+        fn __mview_enumerated(&self) -> Self::Output;
+    }
+
+    impl<T> EnumeratedOther for &Enumerated<T> {
+        type Output = T;
+
+        fn __mview_enumerated(&self) -> Self::Output { self.take() }
+    }
+}
@@ -0,0 +1,77 @@
+use leptos::prelude::*;
+use leptos_mview::{define_mview_attrs, mview};
+mod utils;
+use utils::check_str;
+
+define_mview_attrs!(clickable { role="button" tabindex=0 });
+define_mview_attrs!(link { role="link" });
+define_mview_attrs!(
+    /// A labelled card.
+    card { class="card" aria-label="card" }
+);
+
+mod ui {
+    use leptos_mview::define_mview_attrs;
+
+    define_mview_attrs!(pub(crate) muted { aria-hidden="true" data-muted });
+}
+
+#[test]
+fn adds_attrs() {
+    let r = mview! { span @@clickable { "x" } };
+    check_str(r, r#"<span role="button" tabindex="0">x</span>"#);
+}
+
+#[test]
+fn explicit_attrs_win() {
+    let r = mview! { span @@clickable tabindex=-1 role="link"; };
+    check_str(r, r#"<span tabindex="-1" role="link"></span>"#);
+}
+
+#[test]
+fn several_groups() {
+    let r = mview! { div @@card @@clickable @@ui::muted; };
+    check_str(
+        r,
+        r#"<div aria-label="card" role="button" tabindex="0" aria-hidden="true" data-muted class="card"></div>"#,
+    );
+
+    // the earlier group wins over the later one
+    let r = mview! { a @@link @@clickable; };
+    check_str(r, r#"<a role="link" tabindex="0"></a>"#);
+}
+
+#[test]
+fn with_selectors_and_children() {
+    let r = mview! {
+        ul.list@nav {
+            li @@clickable.item > a href="/" @@ui::muted("home")
+            @@card("implicit div")
+        }
+    };
+    check_str(
+        r,
+        r#"<ul id="nav" class="list"><li role="button" tabindex="0" class="item"><a aria-hidden="true" data-muted href="/">home</a></li><div aria-label="card" class="card">implicit div</div></ul>"#,
+    );
+}
+
+#[test]
+fn handlers_in_groups() {
+    define_mview_attrs!(counts { on:click={move |_| ()} data-count=1 });
+    let r = mview! { button @@counts data-count=2; };
+    check_str(r, r#"<button data-count="2"></button>"#);
+}
+
+#[test]
+fn inside_components() {
+    #[component]
+    fn Wrapper(children: Children) -> impl IntoView {
+        mview! { section { {children()} } }
+    }
+
+    let r = mview! { Wrapper { span @@clickable; } };
+    check_str(
+        r,
+        r#"<section><span role="button" tabindex="0"></span></section>"#,
+    );
+}
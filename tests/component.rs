@@ -43,6 +43,8 @@ fn clones() {
 }
 
 // TODO: not sure why this is creating an untracked resource warning
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
 #[test]
 fn children_args() {
     Executor::init_futures_executor().unwrap();
@@ -62,6 +64,43 @@ fn children_args() {
             {greeting.clone()} " " {name.clone()}
         }
     };
+
+    // async blocks can be passed without braces
+    let count = 3;
+    _ = mview! {
+        Await future=async move { count } |data| {
+            p { {*data} " little monkeys" }
+        }
+    };
+
+    // `await` children are the same as `Await`
+    _ = mview! {
+        div { await {async { 3 }} |data| { p { {*data} " monkeys" } } }
+    };
+    _ = mview! {
+        await blocking {async { "hi".to_string() }} |greeting| { {greeting.clone()} }
+    };
+}
+
+#[test]
+fn empty_view() {
+    #[component]
+    fn Nothing() -> impl IntoView {
+        mview! {}
+    }
+
+    let empty = mview! {};
+    check_str(empty, "<!>");
+    check_str(mview! { div { Nothing; } }, "<div><!></div>");
+
+    // can be used in a branch when both sides are converted to the same type
+    let show = false;
+    let view = if show {
+        mview! { #![into_view] p("shown") }
+    } else {
+        mview! { #![into_view] }
+    };
+    check_str(view, "<!>");
 }
 
 #[test]
@@ -137,6 +176,41 @@ fn let_patterns() {
     }
 }
 
+#[test]
+fn for_closure_children() {
+    #[derive(Clone)]
+    struct Item {
+        id: u32,
+        name: &'static str,
+    }
+    let items = move || [Item { id: 1, name: "a" }, Item { id: 2, name: "b" }];
+
+    Owner::new().with(|| {
+        // a bracket with a closure in it is the closure itself
+        let r = mview! {
+            ul { For each={items} key=[|i| i.id] |item| { li { {item.name} } } }
+        };
+        check_str(r, "<ul><li>a</li><li>b</li><!></ul>");
+    });
+}
+
+#[test]
+fn plain_fn_children() {
+    #[component]
+    fn Repeat<F, V>(times: usize, children: F) -> impl IntoView
+    where
+        F: Fn(usize) -> V + 'static,
+        V: IntoView + 'static,
+    {
+        (0..times).map(children).collect_view()
+    }
+
+    let r = mview! {
+        Repeat times=3 |i| { span({i}) }
+    };
+    check_str(r, "<span>0</span><span>1</span><span>2</span>");
+}
+
 #[component]
 fn TakesClass() -> impl IntoView {
     mview! {
@@ -163,6 +237,8 @@ fn selectors() {
 }
 
 // untracked signal warning... should be fine.
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
 #[test]
 fn class_dir() {
     let yes = RwSignal::new(true);
@@ -176,11 +252,278 @@ fn class_dir() {
     );
 }
 
+#[test]
+fn important_style_dir() {
+    let r = mview! {
+        TakesClass style:color="red !important";
+    };
+    check_str(r, r#"style="color:red !important;""#);
+}
+
 #[test]
 fn ids() {
     let r = mview! {
-        TakesIds #id-1 #id-number-two;
+        TakesIds #id-number-one;
+    };
+
+    check_str(r, r#"<div id="id-number-one" class="i-take-ids""#)
+}
+
+#[test]
+fn use_directive() {
+    use leptos::tachys::renderer::types::Element;
+
+    fn no_arg_dir(_el: Element) {}
+    fn arg_dir(_el: Element, _argument: i32) {}
+
+    // directives on components are added to the component's root element
+    let r = mview! {
+        TakesClass use:no_arg_dir;
+        TakesClass use:arg_dir=300;
+    };
+    check_str(r, r#"<div class="takes-class">I take more classes!</div>"#);
+}
+
+// `view!` evaluates directives after the props
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn props_evaluated_in_source_order() {
+    #[component]
+    fn Com(first: &'static str, second: &'static str) -> impl IntoView {
+        mview! { div { {first} {second} } }
+    }
+
+    let order = std::cell::RefCell::new(Vec::new());
+    let log = |name: &'static str| {
+        order.borrow_mut().push(name);
+        name
+    };
+
+    let r = mview! {
+        Com class:a={!log("class:a").is_empty()} first={log("first")} second={log("second")};
+    };
+
+    assert_eq!(*order.borrow(), ["class:a", "first", "second"]);
+    check_str(r, r#"<div class="a">first<!>second</div>"#);
+}
+
+#[test]
+fn reordered_props_keep_temporaries() {
+    #[component]
+    fn Com(#[prop(into)] label: String, #[prop(into)] title: String) -> impl IntoView {
+        mview! { div title={title} { {label} } }
+    }
+
+    // props evaluated before the directive still borrow from temporaries
+    // that must live until the props are built.
+    let n = 1;
+    let r = mview! {
+        Com class:a=true label={format!("label {n}").as_str()} title={&format!("title {n}")};
+    };
+    check_str(r, r#"<div title="title 1" class="a">label 1</div>"#);
+}
+
+#[test]
+fn prop_directive() {
+    #[component]
+    fn Input() -> impl IntoView {
+        mview! { input; }
+    }
+
+    // properties are only set on the DOM node, so nothing appears in the html
+    let (value, set_value) = signal("a".to_string());
+    let r = mview! {
+        Input prop:value={value} prop:"checked"=[value.get().is_empty()];
+    };
+    set_value.set(String::new());
+    check_str(r, "<input>");
+}
+
+#[test]
+fn non_ascii_props() {
+    #[component]
+    fn Label(τίτλος: &'static str, 标签_名: &'static str) -> impl IntoView {
+        mview! { span { {τίτλος} " " {标签_名} } }
+    }
+
+    let r = mview! { Label τίτλος="α" 标签-名="名"; };
+    check_str(r, "<span>α<!> <!>名</span>");
+}
+
+#[test]
+fn field_access_shorthand() {
+    #[component]
+    fn Com(label: &'static str, is_open: bool) -> impl IntoView {
+        mview! { span { {label} {is_open.to_string()} } }
+    }
+
+    struct Props {
+        label: &'static str,
+        is_open: bool,
+    }
+
+    impl Props {
+        fn view(&self) -> impl IntoView {
+            mview! { Com {self.label} {self.is-open}; }
+        }
+    }
+
+    let props = Props {
+        label: "a",
+        is_open: true,
+    };
+    check_str(props.view(), "<span>a<!>true</span>");
+}
+
+#[test]
+fn negated_boolean_props() {
+    #[component]
+    fn Flags(wide: bool, tall: bool, red: bool) -> impl IntoView {
+        mview! { span { {format!("{wide} {tall} {red}")} } }
+    }
+
+    let r = mview! { Flags wide !tall !red; };
+    check_str(r, "<span>true false false</span>");
+}
+
+#[test]
+fn optional_event_handler() {
+    #[component]
+    fn Button() -> impl IntoView {
+        mview! { button; }
+    }
+
+    let on_click: Option<Callback<leptos::ev::MouseEvent>> = None;
+    let r = mview! { Button on:click=?{on_click}; };
+    check_str(r, "<button></button>");
+}
+
+#[test]
+fn single_child_chain() {
+    #[component]
+    fn Wrapper(children: Children) -> impl IntoView {
+        mview! { section { {children()} } }
+    }
+
+    let r = mview! { Wrapper > .card("text") };
+    check_str(r, r#"<section><div class="card">text</div></section>"#);
+}
+
+#[test]
+fn dbg_directive() {
+    #[component]
+    fn Counter(count: ReadSignal<i32>) -> impl IntoView {
+        mview! { span({count}) }
+    }
+
+    let (count, _) = signal(3);
+    let r = mview! { Counter dbg:count={count} count={count} dbg:label=["counter"]; };
+    check_str(r, "<span>3</span>");
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn transition_directive() {
+    #[component]
+    fn Panel() -> impl IntoView {
+        mview! { div.panel("x") }
+    }
+
+    let visible = RwSignal::new(false);
+    let r = mview! { Panel transition:fade=[visible.get()]; };
+    check_str(r, r#"<div class="panel" style="display:none;">x</div>"#);
+}
+
+#[test]
+fn testid_directive() {
+    #[component]
+    fn Submit() -> impl IntoView {
+        mview! { button { "Submit" } }
+    }
+
+    let r = mview! { Submit testid:submit-button; };
+    if cfg!(feature = "testid") {
+        check_str(r, r#"<button data-testid="submit-button">Submit</button>"#);
+    } else {
+        check_str(r, "<button>Submit</button>");
+    }
+}
+
+#[test]
+fn children_shorthand() {
+    #[component]
+    fn Once(children: Children) -> impl IntoView {
+        mview! { section { children; } }
+    }
+
+    #[component]
+    fn Many(children: ChildrenFn) -> impl IntoView {
+        mview! {
+            Show when=[true] clone:children {
+                children;
+            }
+            children;
+        }
+    }
+
+    let r = mview! { Once { "once" } };
+    check_str(r, "<section>once</section>");
+    let r = mview! { Many { strong("many") } };
+    check_str(r, "<strong>many</strong><strong>many</strong>");
+}
+
+#[test]
+fn bare_closure_props() {
+    #[component]
+    fn Label(format: impl Fn(usize) -> String + 'static) -> impl IntoView {
+        mview! { span { {format(3)} } }
+    }
+
+    let count = RwSignal::new(0);
+    let r = mview! {
+        button on:click=move |_| count.update(|c| *c += 1) {
+            Label format=|n| format!("{n} items");
+        }
+    };
+    check_str(r, "<button><span>3 items</span></button>");
+
+    // the closure ends before the element terminator and child shorthand
+    let go = move || count.set(1);
+    let r = mview! {
+        button on:click=move |_| go() /
+        div on:click=move |_| go() > span("x")
     };
+    check_str(r, "<button></button><div><span>x</span></div>");
+}
+
+#[test]
+fn kebab_directive_shorthand_on_component() {
+    #[component]
+    fn Inner() -> impl IntoView {
+        mview! { div; }
+    }
+
+    let is_red = true;
+    let font_size = "12px";
+    let r = mview! { Inner class:{is-red} style:{font-size}; };
+    check_str(r, r#"<div class="is-red" style="font-size:12px;"></div>"#);
+}
+
+#[test]
+fn renamed_component() {
+    mod ui {
+        use leptos::prelude::*;
+        use leptos_mview::mview;
+
+        #[component]
+        pub fn Counter(initial: i32) -> impl IntoView {
+            mview! { span { {initial} } }
+        }
+    }
+    use ui::Counter as Renamed;
 
-    check_str(r, r#"<div id="id-1 id-number-two" class="i-take-ids""#)
+    // the tag resolves like any other path, so imports can rename components
+    let r = mview! { Renamed initial=1; ui::Counter initial=2; };
+    check_str(r, "<span>1</span><span>2</span>");
 }
@@ -0,0 +1,177 @@
+#![cfg(feature = "delegate")]
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::check_str;
+
+#[component]
+fn Wrapper(#[prop(optional, into)] label: String, children: Children) -> impl IntoView {
+    mview! { section data-label={label} { {children()} } }
+}
+
+#[slot]
+struct Item {
+    name: &'static str,
+}
+
+#[component]
+fn List(item: Vec<Item>) -> impl IntoView {
+    let names = item.iter().map(|i| i.name).collect::<Vec<_>>().join(",");
+    mview! { ul { {names} } }
+}
+
+#[test]
+fn elements() {
+    let r = mview! {
+        !DOCTYPE html;
+        div.card."a/b" #main data-index=1 !hidden {
+            "text "
+            strong("bold")
+            #[dedent] "
+                dedented
+            "
+            br;
+            input type="checkbox" checked;
+            my-element;
+        }
+    };
+    check_str(
+        r,
+        [
+            "<!DOCTYPE html>",
+            r#"<div id="main" data-index="1" class="card a/b">"#,
+            "text <strong>bold</strong>dedented<br>",
+            r#"<input type="checkbox" checked>"#,
+            "<my-element></my-element>",
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn values() {
+    let count = RwSignal::new(3);
+    let r = mview! {
+        p title=[count.get()] data-f=f["{}-x", count.get()] {
+            {count} " " [count.get() * 2] " " f["{}!", 1]
+        }
+    };
+    // leptos adds markers between dynamic text nodes
+    check_str(r, r#"<p title="3" data-f="3-x">3<!> <!>6<!> <!>1!</p>"#);
+}
+
+#[test]
+fn directives() {
+    use leptos::tachys::renderer::types::Element;
+
+    fn no_arg_dir(_el: Element) {}
+    fn arg_dir(_el: Element, _argument: i32) {}
+
+    let active = RwSignal::new(true);
+    let r = mview! {
+        button
+            class:active=[active.get()]
+            class:"w-1/2"
+            style:color="red"
+            attr:aria-label="label"
+            on:click={move |_| active.set(false)}
+            use:no_arg_dir
+            use:arg_dir=300;
+        input type="checkbox" bind:checked={active};
+    };
+    check_str(
+        r,
+        [
+            r#"aria-label="label""#,
+            r#"class="active w-1/2""#,
+            r#"style="color:red;""#,
+            // bindings are only set on the client
+            r#"<input type="checkbox">"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn optional_handlers_and_dbg() {
+    let count = RwSignal::new(1);
+    let handler: Option<Callback<leptos::ev::MouseEvent>> = None;
+    let r = mview! {
+        div dbg:count={count} {
+            button on:click=?{handler} dbg:doubled=[count.get() * 2] { "b" }
+        }
+        Wrapper dbg:count={count} { "c" }
+    };
+    check_str(
+        r,
+        [
+            "<div><button>b</button></div>",
+            r#"<section data-label="">c</section>"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn components() {
+    let label = String::from("cloned");
+    let r = mview! {
+        Wrapper.outer label="text" {
+            "child"
+            Show when=[true] clone:label {
+                span({label.clone()})
+            }
+        }
+        List {
+            slot:Item name="a";
+            slot:Item name="b";
+        }
+    };
+    check_str(
+        r,
+        [
+            r#"<section data-label="text" class="outer">child"#,
+            "<span>cloned</span>",
+            "<ul>a,b</ul>",
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn spread_and_generics() {
+    #[component]
+    fn Generic<T: Default + IntoView + 'static>(#[prop(optional)] value: T) -> impl IntoView {
+        mview! { span({value}) }
+    }
+
+    type Str = &'static str;
+    let attrs = view! { <{..} data-index=0 /> };
+    let r = mview! {
+        div {..attrs} {
+            Generic<i32> value=3;
+            Generic::<Str>;
+        }
+    };
+    check_str(
+        r,
+        [
+            r#"<div data-index="0">"#,
+            "<span>3</span>",
+            "<span> </span>",
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn closure_children() {
+    if false {
+        _ = mview! {
+            For each=[vec![1, 2]] key={|n| *n} |n| {
+                span({n})
+            }
+        };
+    }
+}
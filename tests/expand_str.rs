@@ -0,0 +1,19 @@
+#![cfg(feature = "expand-str")]
+
+use leptos_mview::mview_expand_str;
+
+#[test]
+fn single_element() {
+    let expanded = mview_expand_str! { div.x { "hi" } };
+    assert!(expanded.contains("::leptos::tachys::html::element::r#div()"));
+    assert!(expanded.contains(".child(\"hi\")"));
+    // formatted over multiple lines
+    assert!(expanded.lines().count() > 1);
+}
+
+#[test]
+fn multiple_children() {
+    let expanded = mview_expand_str! { "a" span("b") };
+    assert!(expanded.contains("::leptos::prelude::View::new(("));
+    assert!(expanded.contains("r#span().child(\"b\")"));
+}
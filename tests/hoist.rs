@@ -0,0 +1,83 @@
+#![cfg(feature = "hoist")]
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::check_str;
+
+fn counter(count: RwSignal<i32>) -> impl IntoView {
+    mview! {
+        main {
+            span([count.get()])
+            footer.end #bottom data-x=1 { "static " strong("text") br; }
+            svg viewBox="0 0 24 24" { path d="M0 0h24v24H0z"; }
+            button on:click={move |_| count.update(|c| *c += 1)} { "+1" }
+        }
+    }
+}
+
+#[test]
+fn hoisted_subtrees() {
+    let count = RwSignal::new(0);
+    let expected = |n: i32| {
+        [
+            format!("<main><span>{n}</span>"),
+            r#"<footer data-x="1" id="bottom" class="end">static <strong>text</strong><br></footer>"#
+                .to_string(),
+            r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"></path></svg>"#.to_string(),
+            "<button>+1</button></main>".to_string(),
+        ]
+    };
+
+    check_str(
+        counter(count),
+        expected(0).each_ref().map(String::as_str).as_slice(),
+    );
+    // the hoisted siblings are unchanged when the view is built again
+    count.set(5);
+    check_str(
+        counter(count),
+        expected(5).each_ref().map(String::as_str).as_slice(),
+    );
+}
+
+// `ssr-opt` pre-renders these instead
+#[cfg(not(feature = "ssr-opt"))]
+#[test]
+fn hoisted_templates() {
+    let r = mview! {
+        div {
+            footer.end #bottom data-x=1 { "static " strong("text") br; }
+            svg viewBox="0 0 24 24" { path d="M0 0h24v24H0z"; }
+        }
+    };
+    // both are cloned from a `<template>` when built in the browser
+    let ty = std::any::type_name_of_val(&r);
+    assert_eq!(ty.matches("ViewTemplate<").count(), 2, "{ty}");
+    check_str(
+        r,
+        concat!(
+            r#"<div><footer data-x="1" id="bottom" class="end">static <strong>text</strong><br></footer>"#,
+            r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"></path></svg></div>"#,
+        ),
+    );
+}
+
+#[test]
+fn hoisted_in_reactive_closure() {
+    let count = RwSignal::new(1);
+    let r = move || {
+        if count.get() % 2 == 0 {
+            mview! { p { "even " em("number") } }.into_any()
+        } else {
+            mview! { p { "odd " em("number") } span([count.get()]) }.into_any()
+        }
+    };
+    check_str(r, "<p>odd <em>number</em></p><span>1</span>");
+
+    // the same closure switches to the other hoisted branch
+    count.set(2);
+    check_str(r, "<p>even <em>number</em></p>");
+    count.set(3);
+    check_str(r, "<p>odd <em>number</em></p><span>3</span>");
+}
@@ -3,10 +3,12 @@ use leptos::{
     prelude::*,
     text_prop::TextProp,
 };
-use leptos_mview::mview;
+use leptos_mview::{mview, mview_to_html};
 mod utils;
-use utils::check_str;
+use utils::{check_str, without_source_locs, without_view_markers};
 
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
 #[test]
 fn strings() {
     let result: &str = mview! {
@@ -19,6 +21,8 @@ fn strings() {
 // so I am testing in a way similar to
 // https://github.com/leptos-rs/leptos/blob/main/leptos/tests/ssr.rs
 
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
 #[test]
 fn single_element() {
     let result: HtmlElement<html::Div, _, _> = mview! {
@@ -29,6 +33,20 @@ fn single_element() {
     check_str(result, r#"<div>hi</div>"#);
 }
 
+#[test]
+fn view_markers_match_view_macro() {
+    // `View` only shows its marker through `Debug`.
+    let marker = |view: String| view.split("view_marker: ").nth(1).map(str::to_owned);
+    #[rustfmt::skip]
+    let (mview, view) = (format!("{:?}", mview! { div; span; }), format!("{:?}", view! { <div/><span/> }));
+    assert_eq!(marker(mview), marker(view));
+    if cfg!(debug_assertions) {
+        assert!(
+            format!("{:?}", mview! { div; span; }).contains("view_marker: Some(\"tests-html.rs-")
+        );
+    }
+}
+
 #[test]
 fn multi_element_is_fragment() {
     let _fragment: View<_> = mview! {
@@ -75,7 +93,7 @@ fn directive_before_attr() {
     let result = mview! {
         span style:color="black" style="font-size: 1em;";
     };
-    check_str(result, "font-size: 1em;;color:black;");
+    check_str(result, "font-size: 1em;color:black;");
 }
 
 #[test]
@@ -96,7 +114,40 @@ fn multiple_directives() {
 
     check_str(
         result,
-        r#"class="normal here  also-here" style="line-height: 1.5;;color:white;background-color:red;""#,
+        r#"class="normal here  also-here" style="line-height: 1.5;background-color:red;color:white;""#,
+    );
+}
+
+#[test]
+fn control_flow_values() {
+    let dark = true;
+    let size = 2;
+    let result = mview! {
+        div class=if dark { "dark" } else { "light" }
+            title=match size { 0 => "empty", _ => "full" };
+    };
+    check_str(result, r#"<div title="full" class="dark"></div>"#);
+}
+
+#[test]
+fn merged_styles() {
+    // static styles are merged in source order
+    let result = mview! {
+        div style:margin="4px" style="color: red" style:padding="0";
+    };
+    check_str(
+        result,
+        r#"<div style="color: red;margin:4px;padding:0;"></div>"#,
+    );
+
+    // dynamic styles are added after, so they take precedence
+    let color = move || "blue";
+    let result = mview! {
+        div style:color=[color()] style="color: red;" style:margin="4px";
+    };
+    check_str(
+        result,
+        r#"<div style="color: red;margin:4px;color:blue;"></div>"#,
     );
 }
 
@@ -116,6 +167,14 @@ fn string_directives() {
     )
 }
 
+#[test]
+fn important_styles() {
+    let result = mview! {
+        div style:color="red !important" style:"font-size"="2em";
+    };
+    check_str(result, r#"style="color:red !important;font-size:2em;""#);
+}
+
 #[test]
 fn mixed_class_creation() {
     let class: TextProp = "some-class another-class".into();
@@ -174,3 +233,681 @@ fn doctype() {
 
     check_str(doctype, "<!DOCTYPE html><div></div>");
 }
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn attr_directive() {
+    let r = mview! {
+        div attr:class="not-a-class-call" attr:ref="literal" attr:data-index=0 attr:hidden;
+    };
+
+    check_str(
+        r,
+        r#"<div class="not-a-class-call" ref="literal" data-index="0" hidden>"#,
+    );
+}
+
+#[test]
+fn leading_hyphen_classes() {
+    let r = mview! {
+        div.-mt-2 ."-translate-x-1/2" class:-ml-4=true;
+    };
+
+    check_str(r, r#"<div class="-ml-4 -mt-2 -translate-x-1/2">"#);
+}
+
+#[test]
+fn attributes_evaluated_in_source_order() {
+    let order = std::cell::RefCell::new(Vec::new());
+    let log = |name: &'static str| {
+        order.borrow_mut().push(name);
+        name
+    };
+
+    let r = mview! {
+        div
+            class:a={!log("class:a").is_empty()}
+            data-b={log("data-b")}
+            style:color={log("style:color")}
+            id={log("id")}
+            on:click={move |_| ()};
+    };
+
+    assert_eq!(*order.borrow(), ["class:a", "data-b", "style:color", "id"]);
+    check_str(
+        r,
+        r#"<div data-b="data-b" id="id" class="a" style="color:style:color;">"#,
+    );
+}
+
+#[test]
+fn prop_signal_values() {
+    // properties are only set on the DOM node, so nothing appears in the html;
+    // this checks that signals and closures are both passed on as they are.
+    let (value, set_value) = signal("a".to_string());
+    let r = mview! {
+        input prop:value={value} prop:checked={move || value.get().is_empty()};
+    };
+    set_value.set("b".to_string());
+    check_str(r, "<input>");
+}
+
+#[test]
+fn non_ascii_idents() {
+    let r = mview! {
+        div.κουμπί-κύριο.按钮-主要 class:ενεργό=true class:选中=true data-όνομα="α" data-名字="名";
+    };
+
+    check_str(
+        r,
+        r#"<div data-όνομα="α" data-名字="名" class="ενεργό 选中 κουμπί-κύριο 按钮-主要">"#,
+    );
+}
+
+#[test]
+fn field_access_shorthand() {
+    struct Config {
+        title: &'static str,
+        data_index: i32,
+        active: bool,
+    }
+    let config = Config {
+        title: "hi",
+        data_index: 2,
+        active: true,
+    };
+
+    let r = mview! {
+        div {config.title} {config.data-index} class:{config.active};
+    };
+    check_str(r, r#"<div title="hi" data-index="2" class="active">"#);
+}
+
+#[test]
+fn negated_boolean_attributes() {
+    let r = mview! {
+        input type="checkbox" !checked disabled !readonly;
+    };
+    check_str(r, r#"<input type="checkbox" disabled>"#);
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn inner_html() {
+    let r = mview! {
+        span inner_html="<b>hi</b>";
+    };
+    check_str(r, "<span><b>hi</b></span>");
+
+    let html = String::from("<i>trusted</i>");
+    let r = mview! {
+        span unsafe_inner_html={html};
+    };
+    check_str(r, "<span><i>trusted</i></span>");
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn textarea_value() {
+    let r = mview! {
+        textarea value="static";
+    };
+    check_str(r, "<textarea>static</textarea>");
+
+    let text = RwSignal::new(String::from("reactive"));
+    let r = mview! {
+        textarea value={text};
+    };
+    check_str(r, "<textarea>reactive</textarea>");
+    let r = mview! {
+        textarea name="t" value=[text.get()];
+    };
+    check_str(r, r#"<textarea name="t">reactive</textarea>"#);
+}
+
+#[test]
+fn enumerated_boolean_attributes() {
+    let r = mview! {
+        div draggable=true spellcheck=false aria-hidden=true contenteditable="plaintext-only";
+    };
+    check_str(
+        r,
+        r#"<div draggable="true" spellcheck="false" aria-hidden="true" contenteditable="plaintext-only">"#,
+    );
+
+    let hidden = RwSignal::new(false);
+    let editable = true;
+    let r = mview! {
+        div aria-hidden=[hidden.get()] spellcheck={editable} draggable={move || !editable};
+    };
+    check_str(
+        r,
+        r#"<div aria-hidden="false" spellcheck="true" draggable="false">"#,
+    );
+
+    // bool signals are converted too, and stay reactive
+    let expanded = RwSignal::new(false);
+    let pressed = Signal::derive(|| true);
+    let r = mview! {
+        button aria-expanded={expanded} aria-pressed={pressed} attr:aria-busy=false;
+    };
+    check_str(
+        r,
+        r#"<button aria-expanded="false" aria-pressed="true" aria-busy="false">"#,
+    );
+
+    // genuine boolean attributes are still added or removed
+    let r = mview! {
+        input type="checkbox" checked=true disabled=false;
+    };
+    check_str(r, r#"<input type="checkbox" checked>"#);
+}
+
+#[test]
+fn comma_separators() {
+    let r = mview! {
+        div {
+            input type="text", class="x", disabled;
+            "a", span("b"), "c"
+        }
+    };
+    check_str(
+        r,
+        r#"<div><input type="text" disabled class="x">a<span>b</span>c</div>"#,
+    );
+}
+
+#[test]
+fn slash_terminator() {
+    let html = mview_to_html! {
+        div {
+            img src="x" /
+            br;
+            input.a /
+        }
+    };
+    assert_eq!(html, r#"<div><img src="x"><br><input class="a"></div>"#);
+}
+
+#[test]
+fn at_ids() {
+    let r = mview! {
+        nav@primary;
+        div.card@main.wide;
+        @sidebar;
+    };
+    check_str(
+        r,
+        [
+            r#"<nav id="primary"></nav>"#,
+            r#"<div id="main" class="card wide"></div>"#,
+            r#"<div id="sidebar"></div>"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn selectors_between_attributes() {
+    let r = mview! {
+        input type="text" .wide disabled @main class="first" .last;
+    };
+    check_str(
+        r,
+        r#"<input type="text" disabled id="main" class="first wide last">"#,
+    );
+}
+
+#[test]
+fn valueless_class_directive() {
+    let r = mview! {
+        div class:active class:"lg:grid-cols-3" class:hidden=false;
+    };
+    check_str(r, r#"<div class="active lg:grid-cols-3">"#);
+}
+
+#[test]
+fn valueless_prop_directive() {
+    // same as `prop:checked=true prop:disabled=true`
+    let r = mview! {
+        input type="checkbox" prop:checked prop:disabled;
+    };
+    check_str(r, r#"<input type="checkbox">"#);
+}
+
+#[test]
+fn optional_event_handlers() {
+    use leptos::ev::MouseEvent;
+
+    let callback: Option<Callback<MouseEvent>> = Some(Callback::new(|_| ()));
+    let closure = Some(|_: MouseEvent| ());
+    let none: Option<Callback<MouseEvent>> = None;
+
+    let r = mview! {
+        div {
+            button on:click=?{callback};
+            button on:click=?{closure};
+            button on:click=?{none};
+            button on:click={|_| ()};
+        }
+    };
+    check_str(
+        r,
+        "<div><button></button><button></button><button></button><button></button></div>",
+    );
+}
+
+#[test]
+fn dedented_strings() {
+    let r = mview! {
+        p {
+            #[dedent] "
+                Lorem ipsum dolor sit amet,
+                consectetur adipiscing elit.
+
+                Sed do eiusmod tempor.
+            "
+        }
+    };
+    check_str(
+        r,
+        "<p>Lorem ipsum dolor sit amet, consectetur adipiscing elit. Sed do eiusmod tempor.</p>",
+    );
+}
+
+#[test]
+fn html_entities() {
+    let r = mview! {
+        p { #[entities] "Tom&nbsp;&amp;&nbsp;Jerry &mdash; &#169;" }
+    };
+    check_str(r, "<p>Tom\u{a0}&amp;\u{a0}Jerry \u{2014} \u{a9}</p>");
+
+    let r = mview! {
+        p {
+            #[dedent] #[entities] "
+                one&nbsp;line
+            "
+        }
+    };
+    check_str(r, "<p>one\u{a0}line</p>");
+}
+
+#[test]
+fn implicit_div() {
+    let r = mview! {
+        .card.primary {
+            #sidebar data-index=0;
+            .body("text")
+        }
+    };
+    check_str(
+        r,
+        r#"<div class="card primary"><div data-index="0" id="sidebar"></div><div class="body">text</div></div>"#,
+    );
+}
+
+#[test]
+fn single_child_chain() {
+    let html = mview_to_html! {
+        div.outer > div.inner data-a=1 > span.icon { "x" }
+    };
+    assert_eq!(
+        html,
+        r#"<div class="outer"><div data-a="1" class="inner"><span class="icon">x</span></div></div>"#,
+    );
+
+    let html = mview_to_html! {
+        ul > li > input type="checkbox";
+    };
+    assert_eq!(html, r#"<ul><li><input type="checkbox"></li></ul>"#);
+}
+
+#[test]
+fn dbg_directive() {
+    let count = RwSignal::new(1);
+    let name = "mview";
+
+    let r = mview! {
+        div dbg:count={count} dbg:doubled=[count.get() * 2] dbg:name={name} {
+            span class="a" dbg:"with spaces"={vec![1, 2]} { "text" }
+        }
+    };
+    check_str(r, r#"<div><span class="a">text</span></div>"#);
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn transition_directive() {
+    // the server only renders the initial state, without the classes
+    let visible = RwSignal::new(false);
+    let r = mview! {
+        div.panel transition:fade={visible} { "hidden" }
+    };
+    check_str(
+        r,
+        r#"<div class="panel" style="display:none;">hidden</div>"#,
+    );
+
+    let shown = || true;
+    let r = mview! { p transition:slide={shown} { "shown" } };
+    check_str(r, "<p>shown</p>");
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn declarative_shadow_root() {
+    let title = "Card";
+    let r = mview! {
+        my-card {
+            template shadowrootmode="open" shadowrootdelegatesfocus {
+                style("h2 { margin: 0; }")
+                h2({title})
+                slot;
+            }
+            span("light DOM")
+        }
+    };
+    check_str(
+        r,
+        r#"<my-card><template shadowrootmode="open" shadowrootdelegatesfocus><style>h2 { margin: 0; }</style><h2>Card</h2><slot></slot></template><span>light DOM</span></my-card>"#,
+    );
+}
+
+#[test]
+fn named_slot_attributes() {
+    let slot = "header";
+    let r = mview! {
+        sl-card {
+            img slot="image" src="/card.png";
+            strong {slot} ("Title")
+            "body"
+            div slot="footer" { button("Ok") }
+        }
+    };
+    check_str(
+        r,
+        r#"<sl-card><img slot="image" src="/card.png"><strong slot="header">Title</strong>body<div slot="footer"><button>Ok</button></div></sl-card>"#,
+    );
+}
+
+#[test]
+fn testid_directive() {
+    let r = mview! {
+        form {
+            button type="submit" testid:submit-button { "Submit" }
+        }
+    };
+    if cfg!(feature = "testid") {
+        check_str(
+            r,
+            r#"<form><button type="submit" data-testid="submit-button">Submit</button></form>"#,
+        );
+    } else {
+        check_str(r, r#"<form><button type="submit">Submit</button></form>"#);
+    }
+}
+
+#[test]
+fn source_loc_attribute() {
+    #[component]
+    fn Inner() -> impl IntoView {
+        mview! { span; }
+    }
+
+    let (line, html) = (line!(), mview! { div { Inner; } }.to_html());
+    let html = without_view_markers(html);
+    if cfg!(feature = "source-loc") {
+        // the component only has the location of its own root element
+        let inner_line = line - 3;
+        assert_eq!(
+            html,
+            format!(
+                r#"<div data-source-loc="tests/html.rs:{line}"><span data-source-loc="tests/html.rs:{inner_line}"></span></div>"#
+            )
+        );
+    } else {
+        assert_eq!(html, "<div><span></span></div>");
+    }
+}
+
+#[test]
+fn static_subtrees() {
+    let count = RwSignal::new(1);
+    let r = mview! {
+        main {
+            p.intro #first title="a \"quote\"" { "static " em("text") br; }
+            input type="checkbox" checked !disabled;
+            span([count.get()])
+        }
+    };
+    check_str(
+        r,
+        [
+            r#"<p title="a &quot;quote&quot;" id="first" class="intro">static <em>text</em><br></p>"#,
+            r#"<input type="checkbox" checked>"#,
+            "<span>1</span>",
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn large_fragments() {
+    #[component]
+    fn Wrapper(children: Children) -> impl IntoView {
+        mview! { div { {children()} } }
+    }
+
+    // more children than the largest tuple that leptos supports
+    let expected = (0..40).map(|n| format!("<i>{n}</i>")).collect::<String>();
+    let r = mview! {
+        i("0") i("1") i("2") i("3") i("4") i("5") i("6") i("7") i("8") i("9")
+        i("10") i("11") i("12") i("13") i("14") i("15") i("16") i("17") i("18") i("19")
+        i("20") i("21") i("22") i("23") i("24") i("25") i("26") i("27") i("28") i("29")
+        i("30") i("31") i("32") i("33") i("34") i("35") i("36") i("37") i("38") i("39")
+    };
+    check_str(r, expected.as_str());
+
+    let r = mview! {
+        Wrapper {
+            i("0") i("1") i("2") i("3") i("4") i("5") i("6") i("7") i("8") i("9")
+            i("10") i("11") i("12") i("13") i("14") i("15") i("16") i("17") i("18") i("19")
+            i("20") i("21") i("22") i("23") i("24") i("25") i("26") i("27") i("28") i("29")
+            i("30") i("31") i("32") i("33") i("34") i("35") i("36") i("37") i("38") i("39")
+        }
+    };
+    check_str(r, format!("<div>{expected}</div>").as_str());
+}
+
+#[test]
+fn svg_and_math_elements() {
+    let r = mview! {
+        svg viewBox="0 0 24 24" { path d="M0 0h24v24H0z"; }
+        math { mi("x") }
+    };
+    check_str(
+        r,
+        r#"<svg viewBox="0 0 24 24"><path d="M0 0h24v24H0z"></path></svg><math><mi>x</mi></math>"#,
+    );
+}
+
+#[test]
+fn static_classes_are_merged() {
+    let r = mview! {
+        div.card."w-1/2" #main class="x y" data-index=0 {
+            span.a.b;
+            span.c class=[String::from("dynamic")];
+        }
+    };
+    check_str(
+        r,
+        [
+            r#"<div data-index="0" id="main" class="x y card w-1/2">"#,
+            r#"<span class="a b">"#,
+            r#"<span class="dynamic c">"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn checked_and_custom_attributes() {
+    // known attributes use the element's own methods, like `.r#type(...)`
+    // others (with a `-`, or on svg/web components) use `.attr(...)`
+    let r = mview! {
+        input type="text" maxlength=10 aria-label="name" data-index=0 hx-post="/submit";
+        my-element any_name="a" maxlength="b";
+        svg { rect fill-opacity=0.5 width=2; }
+    };
+    check_str(
+        r,
+        [
+            r#"<input type="text" maxlength="10" aria-label="name" data-index="0" hx-post="/submit">"#,
+            r#"<my-element any_name="a" maxlength="b"></my-element>"#,
+            r#"<rect fill-opacity="0.5" width="2"></rect>"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+#[allow(deprecated)] // the expansion is shown as a deprecation warning
+fn debug_attribute() {
+    let r = mview! {
+        #![debug]
+        div.a("debug")
+    };
+    check_str(r, r#"<div class="a">debug</div>"#);
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn data_attrs() {
+    let pairs =
+        std::collections::BTreeMap::from([("id", "7".to_string()), ("track-as", "nav".into())]);
+    let r = mview! { div data={pairs} title="t"; };
+    check_str(
+        r,
+        r#"<div data-id="7" data-track-as="nav" title="t"></div>"#,
+    );
+
+    // closures are rendered with their current pairs
+    let count = RwSignal::new(2);
+    let r = mview! { span data=[[("count", count.get())]]; };
+    check_str(r, r#"<span data-count="2"></span>"#);
+
+    let r = mview! { div data={Vec::<(&str, &str)>::new()}; };
+    check_str(r, "<div></div>");
+
+    // `data` is a normal attribute on `object`
+    let r = mview! { object data={"movie.swf"}; };
+    check_str(r, r#"<object data="movie.swf"></object>"#);
+}
+
+// not supported in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn form_props_attribute() {
+    let checked = RwSignal::new(true);
+    let text = RwSignal::new(String::from("text"));
+    let r = mview! {
+        #![form_props]
+        input type="checkbox" checked={checked};
+        input value=[text.get()];
+        select value={text} {
+            option value="text" selected=[text.get() == "text"] ("Text")
+        }
+    };
+    check_str(
+        r,
+        [
+            r#"<input type="checkbox" checked>"#,
+            r#"<input value="text">"#,
+            r#"<select><option value="text" selected>Text</option></select>"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn into_view_attribute() {
+    let count = RwSignal::new(1);
+    let views: Vec<AnyView> = vec![
+        mview! { #![into_view] div.a("element") },
+        mview! { #![into_view] "fragment " span([count.get()]) },
+        mview! { #![into_view] "text" },
+        mview! { #![into_view] #![debug] Show when=[true] { "shown" } },
+        mview! { #![debug] #![into_view] {count} },
+    ];
+    let html = views
+        .into_iter()
+        .map(|view| without_source_locs(without_view_markers(view.to_html())))
+        .collect::<Vec<_>>();
+    assert_eq!(html, [
+        r#"<div class="a">element</div>"#,
+        "fragment <span>1</span>",
+        "text",
+        "shown",
+        "1",
+    ]);
+}
+
+#[test]
+fn rendered_attribute_order() {
+    // plain attributes in source order, then the selector id, then spread
+    // attributes, then all classes and all styles
+    let spread = view! { <{..} data-spread="s" /> };
+    let r = mview! {
+        div.sel #main
+            title="t"
+            class:dir=true
+            attr:data-a="a"
+            style="margin: 0"
+            class="attr"
+            on:click={|_| ()}
+            style:color="red"
+            aria-label="l"
+            prop:value="v"
+            data-b=1
+            {..spread}
+            hidden;
+    };
+    check_str(
+        r,
+        r#"<div title="t" data-a="a" aria-label="l" data-b="1" hidden id="main" data-spread="s" class="attr dir sel" style="margin: 0;color:red;"></div>"#,
+    );
+}
+
+#[test]
+fn kebab_directive_shorthand() {
+    let is_red = true;
+    let font_size = "12px";
+    let result = mview! { div class:{is-red} style:{font-size}; };
+    check_str(
+        result,
+        r#"<div class="is-red" style="font-size:12px;"></div>"#,
+    );
+    let result = mview! { div.base class:{is-red} style="color: red" style:{font-size}; };
+    check_str(
+        result,
+        r#"<div class="is-red base" style="color: red;font-size:12px;"></div>"#,
+    );
+}
+
+#[test]
+fn inline_handler_strings() {
+    // for JavaScript that runs before the page is hydrated, `#![strict]` would
+    // error on it
+    let r = mview! {
+        #[allow(strict)]
+        button onclick="history.back()" on:click={|_| ()} { "Back" }
+    };
+    check_str(r, r#"<button onclick="history.back()">Back</button>"#);
+}
@@ -0,0 +1,75 @@
+//! Checks that the bindings made by the expansion don't capture or shadow
+//! user variables with the same names.
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::check_str;
+
+#[component]
+fn Wrapper(#[prop(optional, into)] label: String, children: Children) -> impl IntoView {
+    mview! { section data-label={label} { {children()} } }
+}
+
+#[test]
+fn internal_names_as_locals() {
+    let value = "value";
+    let handler = Some(|_: leptos::ev::MouseEvent| ());
+    let ev = "ev";
+    let attrs = "attrs";
+    let view = "view";
+    let props = "props";
+    let __x = "x";
+    let __mview_value_0 = "value_0";
+    let __mview_traced = "traced";
+
+    let r = mview! {
+        div
+            class:active=[value.starts_with('v')]
+            data-value={value}
+            data-ev={ev}
+            on:click=?{handler}
+            dbg:value={value}
+            data-attrs={attrs}
+            title=[view]
+        {
+            span.kebab-class data-x={__x} data-props={props};
+            [__mview_value_0]
+            f["{__mview_traced}!"]
+        }
+        Wrapper label={value} { {attrs} " " {view} }
+    };
+    check_str(
+        r,
+        [
+            r#"<div data-value="value" data-ev="ev" data-attrs="attrs" title="view" class="active">"#,
+            r#"<span data-x="x" data-props="props" class="kebab-class"></span>"#,
+            "value_0",
+            "traced!",
+            r#"<section data-label="value">attrs"#,
+        ]
+        .as_slice(),
+    );
+}
+
+#[test]
+fn children_local() {
+    #[component]
+    fn Outer(children: ChildrenFn) -> impl IntoView {
+        // `children` is only used by the expansion through the shorthand
+        let inner = children.clone();
+        mview! {
+            Wrapper { children; }
+            Wrapper { {inner()} }
+        }
+    }
+
+    let children = "not the children prop";
+    let r = mview! {
+        Outer { strong({children}) }
+    };
+    check_str(
+        r,
+        r#"<section data-label=""><strong>not the children prop</strong></section><section data-label=""><strong>not the children prop</strong></section>"#,
+    );
+}
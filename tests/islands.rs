@@ -0,0 +1,77 @@
+use std::sync::Arc;
+
+use hydration_context::SsrSharedContext;
+use leptos::{prelude::*, reactive::owner::Owner};
+use leptos_mview::mview;
+mod utils;
+use utils::{without_source_locs, without_view_markers};
+
+/// Islands need a shared context while rendering, like on the server.
+#[track_caller]
+fn render(view: impl FnOnce() -> AnyView) -> String {
+    let owner = Owner::new_root(Some(Arc::new(SsrSharedContext::new())));
+    owner.with(|| without_source_locs(without_view_markers(view().to_html())))
+}
+
+#[island]
+fn Counter(initial: i32, children: Children) -> impl IntoView {
+    let count = RwSignal::new(initial);
+    mview! {
+        button on:click={move |_| count.update(|n| *n += 1)} { [count.get()] }
+        {children()}
+    }
+}
+
+#[island]
+fn NoProps() -> impl IntoView {
+    mview! { span("island") }
+}
+
+#[island]
+fn OnlyChildren(children: Children) -> impl IntoView {
+    mview! { div { {children()} } }
+}
+
+#[test]
+fn island_props_and_children() {
+    let html = render(|| {
+        mview! {
+            main {
+                Counter initial=3 {
+                    p("server only")
+                    NoProps;
+                }
+                OnlyChildren { "text" }
+            }
+        }
+        .into_any()
+    });
+    assert!(html.contains(r#"data-props="{&quot;initial&quot;:3}"><button>3</button>"#));
+    assert!(html
+        .contains(r#"<leptos-children><p>server only</p><leptos-island data-component="NoProps_"#));
+    assert!(html.contains("<div><leptos-children>text</leptos-children></div>"));
+}
+
+#[test]
+fn same_as_view_macro() {
+    // leptos adds hot-reloading comments in `view!`
+    let strip_comments = |html: String| {
+        html.split("<!--")
+            .enumerate()
+            .map(|(i, part)| if i == 0 { part } else { part.split_once("-->").unwrap().1 })
+            .collect::<String>()
+    };
+    let mview = render(|| {
+        mview! {
+            Counter initial=1 { OnlyChildren { "a" } }
+        }
+        .into_any()
+    });
+    let view = render(|| {
+        view! {
+            <Counter initial=1><OnlyChildren>"a"</OnlyChildren></Counter>
+        }
+        .into_any()
+    });
+    assert_eq!(strip_comments(mview), strip_comments(view));
+}
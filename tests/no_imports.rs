@@ -0,0 +1,57 @@
+//! Checks that the expansion works with no `leptos` items in scope.
+
+use leptos_mview::mview;
+// the utils module imports the leptos prelude, but only inside the module
+mod utils;
+
+#[track_caller]
+fn check_str(view: impl ::leptos::prelude::IntoView, contains: &str) {
+    let html = ::leptos::prelude::RenderHtml::to_html(::leptos::prelude::IntoView::into_view(view));
+    let html = utils::without_source_locs(utils::without_view_markers(html));
+    assert!(html.contains(contains), "expected {contains:?} in {html}");
+}
+
+#[::leptos::component]
+fn Wrapper(
+    #[prop(optional, into)] label: String,
+    children: ::leptos::children::Children,
+) -> impl ::leptos::prelude::IntoView {
+    mview! { section data-label={label} { {children()} } }
+}
+
+#[test]
+fn elements() {
+    let node_ref = ::leptos::prelude::NodeRef::<::leptos::html::Input>::new();
+    let r = mview! {
+        div.a #b title="t" aria-label="l" data-x=1 class:c=[true] style:color="red" {
+            "text"
+            input type="checkbox" checked ref={node_ref} prop:value="v" on:click={|_| ()};
+            span inner_html="<b>hi</b>";
+        }
+    };
+    check_str(
+        r,
+        r#"<div title="t" aria-label="l" data-x="1" id="b" class="c a" style="color:red;">text"#,
+    );
+}
+
+#[test]
+fn components() {
+    let count = ::leptos::prelude::RwSignal::new(1);
+    let r = mview! {
+        Wrapper.outer label="x" on:click={move |_| ::leptos::prelude::Set::set(&count, 2)} {
+            span(f["{}", ::leptos::prelude::Get::get(&count)])
+        }
+        "fragment"
+    };
+    check_str(
+        r,
+        r#"<section data-label="x" class="outer"><span>1</span></section>fragment"#,
+    );
+}
+
+#[test]
+fn spread() {
+    let attrs = ::leptos::view! { <{..} data-index=0 /> };
+    check_str(mview! { div {..attrs}; }, r#"<div data-index="0"></div>"#);
+}
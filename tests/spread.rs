@@ -35,6 +35,25 @@ fn spread_in_component() {
     );
 }
 
+#[test]
+fn aria_attrs_on_component() {
+    #[component]
+    fn Spreadable() -> impl IntoView {
+        mview! {
+            div;
+        }
+    }
+
+    let open = RwSignal::new(false);
+    let res = mview! {
+        Spreadable attr:aria-expanded={open} attr:aria-hidden=true;
+    };
+    check_str(
+        res,
+        r#"<div aria-expanded="false" aria-hidden="true"></div>"#,
+    );
+}
+
 #[test]
 fn spread_on_component() {
     #[component]
@@ -0,0 +1,55 @@
+#![cfg(feature = "ssr-opt")]
+
+//! Static elements are pre-rendered with the feature, so their text and
+//! attributes are escaped at compile time. The output should match what leptos
+//! escapes at runtime.
+//!
+//! Only elements inside of another element are pre-rendered, so each view is
+//! wrapped in a `main`.
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+mod utils;
+use utils::without_view_markers;
+
+#[track_caller]
+fn assert_same_html(mview: impl IntoView, view: impl IntoView) {
+    assert_eq!(
+        without_view_markers(mview.into_view().to_html()),
+        without_view_markers(view.into_view().to_html())
+    );
+}
+
+#[test]
+fn text_escaping() {
+    assert_same_html(
+        mview! { main { p { "a < b & \"c\" > 'd'" } } },
+        view! { <main><p>"a < b & \"c\" > 'd'"</p></main> },
+    );
+    assert_same_html(
+        mview! { main { div { span("&amp; &lt;") b("</p><script>") } } },
+        view! { <main><div><span>"&amp; &lt;"</span><b>"</p><script>"</b></div></main> },
+    );
+}
+
+#[test]
+fn attribute_escaping() {
+    assert_same_html(
+        mview! { main { a href="/?a=1&b=2" title="a < b & \"c\" > 'd'"; } },
+        view! { <main><a href="/?a=1&b=2" title="a < b & \"c\" > 'd'"></a></main> },
+    );
+    assert_same_html(
+        mview! { main { div.x class="\"y\" &" data-x="&quot;" { "text" } } },
+        // mview puts classes after all other attributes
+        view! { <main><div data-x="&quot;" class="\"y\" & x">"text"</div></main> },
+    );
+}
+
+#[test]
+fn marked_strings() {
+    // entities are decoded first, so they are escaped again like any other text
+    assert_same_html(
+        mview! { main { p { #[entities] "&lt;b&gt; &amp;" } } },
+        view! { <main><p>"<b> &"</p></main> },
+    );
+}
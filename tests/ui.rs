@@ -11,3 +11,17 @@ fn ui() {
     // t.pass("tests/ui/pass/*.rs");
     // t.compile_fail("tests/ui/errors/*.rs");
 }
+
+/// Checks that errors about children and attributes point at them, not the
+/// whole macro, that `mview_file!` and `mview_str!` errors say where they
+/// are, that unknown attribute groups are reported at the `@@`, and that
+/// `#![strict]` lints point at the attribute key.
+///
+/// The expected output is for the default features, as features that change
+/// the expansion also change the errors and warnings.
+#[cfg(not(any(feature = "source-loc", feature = "delegate", feature = "strict")))]
+#[test]
+fn child_spans() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/spans/*.rs");
+}
@@ -0,0 +1,19 @@
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        div; { "oops" }
+    };
+
+    _ = mview! {
+        br;
+        {"next line"}
+    };
+
+    // fine, separated by a blank line
+    _ = mview! {
+        br;
+
+        {"separate"}
+    };
+}
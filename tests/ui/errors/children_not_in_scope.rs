@@ -0,0 +1,8 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        div { children; }
+    };
+}
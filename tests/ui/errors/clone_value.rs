@@ -0,0 +1,14 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn Com(children: Children) -> impl IntoView { children() }
+
+fn main() {
+    let data = String::new();
+    _ = mview! {
+        Com clone:data=[data.len()] {
+            {data.clone()}
+        }
+    };
+}
@@ -0,0 +1,21 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn TwoArgs<F, IV>(children: F) -> impl IntoView
+where
+    F: Fn(usize, &'static str) -> IV + Send + 'static,
+    IV: IntoView,
+{
+    children(0, "a")
+}
+
+fn wrong_arity() {
+    _ = mview! {
+        TwoArgs |item| {
+            {item}
+        }
+    };
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn MyComponent(some_prop: i32, other: &'static str) -> impl IntoView {
+    let _ = (some_prop, other);
+}
+
+fn str_keys() {
+    _ = mview! {
+        MyComponent "some-prop"=3 other="a";
+    };
+    _ = mview! {
+        MyComponent some-prop=3 "other"="a";
+    };
+}
+
+fn main() {}
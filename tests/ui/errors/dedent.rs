@@ -0,0 +1,25 @@
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        pre {
+            #[dedent] "
+                fn main() {}
+            "
+        }
+    };
+
+    _ = mview! {
+        pre {
+            code { #[dedent] "nested" }
+        }
+    };
+
+    _ = mview! {
+        p { #[indent] "text" }
+    };
+
+    _ = mview! {
+        p { #[dedent] {"not a literal"} }
+    };
+}
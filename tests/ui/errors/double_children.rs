@@ -0,0 +1,12 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        div { "a" } { "b" }
+    };
+
+    _ = mview! {
+        For each=[[1, 2]] key={|i| *i} |i| { {i} } { "extra" }
+    };
+}
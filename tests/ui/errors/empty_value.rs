@@ -0,0 +1,16 @@
+use leptos_mview::mview;
+
+fn empty_block() {
+    _ = mview! {
+        div class={};
+        button on:click={};
+    };
+}
+
+fn empty_bracket() {
+    _ = mview! {
+        a href=[];
+    };
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        div<String> { "text" }
+    };
+
+    _ = mview! {
+        section {
+            span<u32, i32>.label("text")
+        }
+    };
+}
@@ -0,0 +1,23 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn Com() -> impl IntoView {}
+
+fn main() {
+    _ = mview! {
+        div #1st-item { "text" }
+    };
+
+    _ = mview! {
+        div #"sidebar" { "text" }
+    };
+
+    _ = mview! {
+        div #first #second { "text" }
+    };
+
+    _ = mview! {
+        Com #first .class #second;
+    };
+}
@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    _ = mview! {
+        input !checked=true;
+    };
+
+    let value = true;
+    _ = mview! {
+        input !{value};
+    };
+}
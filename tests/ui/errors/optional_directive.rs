@@ -0,0 +1,9 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    let active = true;
+    _ = mview! {
+        div class:active=?{active};
+    };
+}
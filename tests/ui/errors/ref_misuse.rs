@@ -0,0 +1,29 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn not_braced() {
+    _ = mview! {
+        div ref="my-node";
+    };
+    _ = mview! {
+        div ref=[NodeRef::new()];
+    };
+}
+
+fn no_value() {
+    _ = mview! {
+        div ref;
+    };
+}
+
+fn leptos_names() {
+    let node_ref = NodeRef::new();
+    _ = mview! {
+        div node_ref={node_ref};
+    };
+    _ = mview! {
+        div _ref={node_ref};
+    };
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use leptos::prelude::*;
+use leptos_mview::{define_mview_attrs, mview};
+
+define_mview_attrs!(clickable { role="button" div { "child" } });
+
+#[slot]
+struct Item {}
+
+#[component]
+fn List(item: Vec<Item>) -> impl IntoView {
+    let _ = item;
+}
+
+fn main() {
+    // unknown groups are reported at the `@@`
+    _ = mview! { span @@not_a_group; };
+
+    // groups can't add props to slots
+    _ = mview! { List { slot:Item @@clickable; } };
+}
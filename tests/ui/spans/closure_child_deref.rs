@@ -0,0 +1,15 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn Counted(count: u32, children: impl Fn(&u32) -> u32 + 'static) -> impl IntoView {
+    children(&count)
+}
+
+fn main() {
+    let _ = mview! {
+        Counted count=3 |count| {
+            {count}
+        }
+    };
+}
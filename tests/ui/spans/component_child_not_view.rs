@@ -0,0 +1,17 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+struct NotView;
+
+#[component]
+fn Wrapper(children: Children) -> impl IntoView { children() }
+
+fn main() {
+    let _ = mview! {
+        Wrapper {
+            "a"
+            {NotView}
+            "c"
+        }
+    };
+}
@@ -0,0 +1,14 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+struct NotView;
+
+fn main() {
+    let _ = mview! {
+        div {
+            "a"
+            {NotView}
+            "c"
+        }
+    };
+}
@@ -0,0 +1,13 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn Labelled(aria_label: &'static str) -> impl IntoView { aria_label }
+
+fn main() {
+    let _ = mview! {
+        Labelled {aria-label};
+        input {aria-label};
+        button class:{active} prop:{value};
+    };
+}
@@ -0,0 +1,12 @@
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+fn main() {
+    let count = RwSignal::new(0);
+    let label = "count";
+    let _ = mview! {
+        span({label})
+        div @"invalid" { "left out" }
+        button on:click={move |_| count.update(|c| *c += 1)} { "+1" }
+    };
+}
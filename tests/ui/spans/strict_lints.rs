@@ -0,0 +1,25 @@
+#![deny(deprecated)]
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+
+#[component]
+fn Card(max_width: i32) -> impl IntoView {
+    let _ = max_width;
+}
+
+fn main() {
+    // warnings are shown at the key
+    _ = mview! {
+        #![strict]
+        div aria-label="a" {
+            meta http_equiv="refresh";
+            Card max-width=2;
+            span class:active=true style:color="red";
+            #[allow(strict)] meta http_equiv="refresh";
+        }
+    };
+
+    // inline handlers are errors
+    _ = mview! { #![strict] button onclick="go()"; };
+}
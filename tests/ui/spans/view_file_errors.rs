@@ -0,0 +1,9 @@
+use leptos_mview::mview_file;
+
+fn main() {
+    let _ = mview_file!("tests/views/missing.mview");
+    let _ = mview_file!(footer);
+    // trybuild builds from `target/tests/trybuild/leptos-mview`
+    let _ = mview_file!("../../../../tests/views/invalid.mview");
+    let _ = mview_file!("../../../../tests/views/unbalanced.mview");
+}
@@ -0,0 +1,8 @@
+use leptos_mview::mview_str;
+
+fn main() {
+    let _ = mview_str!(include_str!("footer.mview"));
+    let _ = mview_str!(footer);
+    let _ = mview_str!("div {\n    span class=;\n}");
+    let _ = mview_str!("div { \"a\" )");
+}
@@ -4,7 +4,7 @@ use leptos::prelude::*;
 
 #[track_caller]
 pub fn check_str<'a>(component: impl IntoView, contains: impl Into<Contains<'a>>) {
-    let component_str = component.into_view().to_html();
+    let component_str = without_source_locs(without_view_markers(component.into_view().to_html()));
     match contains.into() {
         Contains::Str(s) => {
             assert!(
@@ -61,6 +61,23 @@ pub fn check_str<'a>(component: impl IntoView, contains: impl Into<Contains<'a>>
     };
 }
 
+/// Removes the `<!--hot-reload|...-->` comments that view markers render in
+/// debug builds with the `nightly` feature.
+pub fn without_view_markers(html: String) -> String { remove_all(html, "<!--hot-reload|", "-->") }
+
+/// Removes the attributes added by the `source-loc` feature.
+pub fn without_source_locs(html: String) -> String { remove_all(html, " data-source-loc=\"", "\"") }
+
+/// Removes everything from each `open` up to and including the next `close`.
+fn remove_all(mut html: String, open: &str, close: &str) -> String {
+    while let Some(start) = html.find(open) {
+        let end = start + open.len();
+        let end = end + html[end..].find(close).unwrap() + close.len();
+        html.replace_range(start..end, "");
+    }
+    html
+}
+
 pub enum Contains<'a> {
     Str(&'a str),
     All(&'a [&'a str]),
@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use leptos_mview::mview;
+use leptos_mview::{mview, mview_to_html};
 use utils::check_str;
 mod utils;
 
@@ -19,3 +19,52 @@ fn f_value() {
     };
     check_str(r, r#"<input type="number" value="2.13""#);
 }
+
+#[test]
+fn memo_value() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let expensive = |n: i32| {
+        RUNS.fetch_add(1, Ordering::Relaxed);
+        n * 100
+    };
+    let count = RwSignal::new(3);
+    let html = mview_to_html! {
+        div title=memo[expensive(count.get()).to_string()] {
+            memo[expensive(count.get())]
+        }
+    };
+    assert_eq!(html, r#"<div title="300">300</div>"#);
+    // each memo only runs once for the value it renders
+    assert_eq!(RUNS.load(Ordering::Relaxed), 2);
+}
+
+#[test]
+fn style_signal_values() {
+    let width = RwSignal::new("10px".to_string());
+    let width_signal = Signal::derive(move || width.get());
+    let height = Memo::new(move |_| width.get().replace("px", "em"));
+    let r = mview! {
+        div style:width={width_signal} style:height={height} style:color={move || "red"};
+    };
+    width.set("20px".to_string());
+    check_str(r, r#"<div style="width:20px;height:20em;color:red;">"#);
+}
+
+#[test]
+fn call_values() {
+    fn len(items: &[i32]) -> usize { items.len() }
+    mod i18n {
+        pub fn t(key: &str) -> String { key.replace('.', " ") }
+    }
+
+    let items = vec![1, 2, 3];
+    let r = mview! {
+        div data-count=len(&items) aria-label=i18n::t("home.title") id="after";
+    };
+    check_str(
+        r,
+        r#"<div data-count="3" aria-label="home title" id="after">"#,
+    );
+}
@@ -0,0 +1,17 @@
+use leptos::prelude::*;
+use leptos_mview::mview_file;
+mod utils;
+use utils::check_str;
+
+#[component]
+fn Copyright(year: u32) -> impl IntoView { format!("© {year}") }
+
+#[test]
+fn expands_file() {
+    let url = "https://example.com";
+    let r = mview_file!("tests/views/footer.mview");
+    check_str(
+        r,
+        r#"<footer class="site-footer"><p>Made by <a href="https://example.com">us</a></p>© 2024</footer>"#,
+    );
+}
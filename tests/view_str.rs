@@ -0,0 +1,22 @@
+use leptos_mview::mview_str;
+mod utils;
+use utils::check_str;
+
+#[test]
+fn expands_string() {
+    let r = mview_str!("div.card { \"hi\" }");
+    check_str(r, r#"<div class="card">hi</div>"#);
+}
+
+#[test]
+fn raw_and_multiline_strings() {
+    let r = mview_str!(
+        r#"
+        ul {
+            li("one")
+            li.last("two")
+        }
+        "#
+    );
+    check_str(r, r#"<ul><li>one</li><li class="last">two</li></ul>"#);
+}
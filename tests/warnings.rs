@@ -0,0 +1,215 @@
+//! Checks that expansions don't cause any warnings, for crates that deny them.
+//!
+//! Clippy lints are only checked when running `cargo clippy --all-targets`.
+
+#![deny(warnings, clippy::all, clippy::pedantic)]
+
+use leptos::prelude::*;
+use leptos_mview::mview;
+// only the macro expansions are being checked
+#[allow(clippy::all, clippy::pedantic)]
+mod utils;
+use utils::check_str;
+
+#[component]
+fn Wrapper(
+    #[prop(optional, into)] label: String,
+    #[prop(optional)] count: Option<i32>,
+    children: Children,
+) -> impl IntoView {
+    mview! { section data-label={label} data-count={count} { {children()} } }
+}
+
+#[component]
+fn Each(items: Vec<i32>, children: ChildrenFn) -> impl IntoView {
+    let _ = items;
+    mview! { ul { {children()} } }
+}
+
+fn get_count() -> i32 { 3 }
+
+#[test]
+fn values() {
+    let count = RwSignal::new(1);
+    let label = "label";
+    let text = String::from("text");
+    let r = mview! {
+        "top level"
+        input
+            class="a"
+            data-lit=1
+            data-block={count}
+            data-bracket=[count.get()]
+            data-format=f["{}-{}", count.get(), 1]
+            data-call=get_count()
+            title={label}
+            checked
+            !hidden;
+        div {
+            "string"
+            {text.clone()}
+            [count.get() * 2]
+            f["{}", count.get()]
+            #[dedent] "
+                dedented
+                string
+            "
+            #[entities] "&amp;"
+            span("parens")
+            .card > strong("chained")
+        }
+    };
+    check_str(r, "top level");
+}
+
+#[test]
+fn single_values() {
+    let count = RwSignal::new(1);
+    check_str(mview! { [count.get()] }, "1");
+    check_str(mview! { {count} }, "1");
+    check_str(mview! { "just a string" }, "just a string");
+    check_str(mview! { f["{}", count.get()] }, "1");
+}
+
+#[test]
+fn directives() {
+    let active = RwSignal::new(true);
+    let color = "red";
+    let handler: Option<Callback<leptos::ev::MouseEvent>> = None;
+    let r = mview! {
+        div
+            class:enabled=[active.get()]
+            class:always
+            class:{active}
+            style:color={color}
+            style:width=["10px"]
+            prop:value={1}
+            prop:checked
+            attr:data-raw="raw"
+            on:click={move |_| active.set(false)}
+            on:keydown=?{None::<fn(leptos::ev::KeyboardEvent)>}
+            on:mouseup=?{handler}
+            dbg:active={active}
+            testid:root
+        {
+            input type="checkbox" bind:checked={active};
+        }
+    };
+    check_str(r, "div");
+}
+
+#[test]
+fn components() {
+    let count = RwSignal::new(1);
+    let label = String::from("label");
+    let items = vec![1, 2];
+    let r = mview! {
+        Wrapper.card #main
+            label="text"
+            count={count.get()}
+            class:active=[count.get() > 0]
+            on:click={move |_| count.set(0)}
+            dbg:count={count}
+        {
+            "child"
+            [count.get()]
+            Each items={items} clone:label {
+                span({label.clone()})
+            }
+        }
+        Wrapper label="other" > span("chained")
+    };
+    check_str(r, "section");
+}
+
+#[test]
+fn closure_children() {
+    let r = mview! {
+        Show when=[true] fallback={|| "hidden"} {
+            "shown"
+        }
+    };
+    check_str(r, "shown");
+
+    if false {
+        _ = mview! {
+            For each=[vec![1, 2]] key={|n| *n} |n| {
+                span({n})
+            }
+        };
+    }
+}
+
+#[component]
+fn Once(children: Children) -> impl IntoView {
+    mview! { section { children; } }
+}
+
+#[slot]
+struct Item {
+    name: &'static str,
+}
+
+#[component]
+fn List(item: Vec<Item>) -> impl IntoView {
+    let names = item.iter().map(|i| i.name).collect::<Vec<_>>().join(",");
+    mview! { ul { {names} } }
+}
+
+#[component]
+fn Generic<T: Default + IntoView + 'static>(#[prop(optional)] value: T) -> impl IntoView {
+    mview! { span({value}) }
+}
+
+struct Config {
+    title: &'static str,
+    data_index: i32,
+    active: bool,
+}
+
+// only plain type names can be used as generics in delegate mode
+#[cfg(not(feature = "delegate"))]
+#[test]
+fn turbofish_generics() {
+    let r = mview! { Generic::<&'static str>; };
+    check_str(r, "");
+}
+
+#[test]
+fn other_syntax() {
+    use leptos::tachys::renderer::types::Element;
+
+    fn no_arg_dir(_el: Element) {}
+    fn arg_dir(_el: Element, _argument: i32) {}
+
+    let config = Config {
+        title: "title",
+        data_index: 2,
+        active: true,
+    };
+    let node_ref = NodeRef::<leptos::html::Div>::new();
+    let spread = view! { <{..} data-spread="s" /> };
+    let r = mview! {
+        !DOCTYPE html;
+        .card."w-1/2" #main class="static" {config.title} {config.data-index} class:{config.active} {
+            #sidebar ref={node_ref} use:no_arg_dir use:arg_dir=1 {..spread};
+            span inner_html="<b>html</b>";
+            svg viewBox="0 0 24 24" { path d="M0 0h24v24H0z"; }
+            Once { "once" }
+            List {
+                slot:Item name="a";
+                slot:Item name="b";
+            }
+            Generic<i32> value=1;
+        }
+    };
+    check_str(r, "<section>once</section>");
+
+    if false {
+        _ = mview! {
+            For each=[vec![(1, 'a')].into_iter()] key={|(i, _)| *i} |(i, letter)| {
+                {i} {letter}
+            }
+        };
+    }
+}